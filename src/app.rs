@@ -2,14 +2,45 @@
 //!
 //! Central application state management for tone.
 
+use crate::config::{load_history, parse_refresh_interval, save_history, Config, HISTORY_LIMIT};
+use crate::keymap::KeyMap;
+use crate::one::xmlrpc::pretty_print_xml;
 use crate::one::OneClient;
 use crate::resource::{
-    extract_json_value, fetch_resources_paginated, get_all_resource_keys, get_resource,
-    ResourceDef, ResourceFilter,
+    column_matches, compare_column_values, current_host_name, extract_json_value,
+    extract_json_values, fetch_raw_xml, fetch_resources, fetch_resources_paginated,
+    format_display_value, format_lcm_state, get_all_resource_keys, get_json_path, get_resource,
+    invoke_sdk_method, is_transitional_lcm_state, nic_ips, parse_column_filter, vm_state_code,
+    ColumnDef, InputConfig, PaginatedResult, ResourceDef, ResourceFilter, SelectConfig,
 };
+use crate::ui::theme::Theme;
 use anyhow::Result;
 use crossterm::event::KeyCode;
+use ratatui::layout::{Position, Rect};
 use serde_json::Value;
+use std::cell::Cell;
+use std::collections::{HashMap, HashSet, VecDeque};
+use tokio::sync::mpsc;
+use zeroize::Zeroize;
+
+/// Built-in `:` commands, which win over a fuzzy-matched resource suggestion
+const BUILTIN_COMMANDS: &[&str] = &[
+    "q",
+    "quit",
+    "back",
+    "capacity",
+    "log",
+    "col",
+    "login",
+    "refresh-interval",
+    "pipe",
+    "readonly",
+    "export",
+];
+
+/// Service of the pending action behind `:readonly off`, which goes through
+/// the confirm dialog but never reaches OpenNebula
+pub const READ_WRITE_SERVICE: &str = "tone";
 
 /// Application modes
 #[derive(Debug, Clone, PartialEq)]
@@ -20,6 +51,18 @@ pub enum Mode {
     Confirm,  // Confirmation dialog
     Warning,  // Warning/info dialog (OK only)
     Describe, // Viewing JSON details of selected item
+    Select,   // Picking a parameter for an action from a list
+    Input,    // Typing a text value for an action
+    Log,      // Scrolling the history of actions and API errors
+}
+
+/// One line of the in-app action log
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    /// Local time of the event (HH:MM:SS)
+    pub time: String,
+    pub text: String,
+    pub ok: bool,
 }
 
 /// Pending action that requires confirmation
@@ -27,12 +70,226 @@ pub enum Mode {
 pub struct PendingAction {
     pub service: String,
     pub sdk_method: String,
+    /// Registry key of the action (several actions can share an `sdk_method`)
+    pub action_key: String,
     pub resource_id: String,
+    /// What the action does, e.g. "Terminate VM 'web-1'"
+    pub summary: String,
     pub message: String,
     #[allow(dead_code)]
     pub default_no: bool,
     pub destructive: bool,
     pub selected_yes: bool,
+    pub warning: Option<String>,
+    /// Resolved XML-RPC call this action will send (dry-run preview)
+    pub preview: Option<String>,
+    /// Additional parameters collected before confirmation (e.g. target host)
+    pub extra_params: serde_json::Map<String, Value>,
+    /// Resource to switch to once the action succeeds
+    pub navigate_to: Option<String>,
+    /// Text the user must type before the action runs (type-to-confirm)
+    pub confirm_text: Option<String>,
+    /// Marked items the action runs on instead of `resource_id`: ID and per-item params
+    pub batch: Vec<(String, serde_json::Map<String, Value>)>,
+    /// Params typed into masked fields (e.g. a password), wiped once no longer needed
+    pub secrets: Vec<String>,
+}
+
+impl PendingAction {
+    /// Parameters passed to `invoke_sdk_method` when the action runs
+    ///
+    /// Fails on a malformed ID rather than acting on the wrong resource.
+    pub fn params(&self) -> Result<Value> {
+        let id = self
+            .resource_id
+            .parse::<i64>()
+            .map_err(|_| anyhow::anyhow!("Invalid resource ID '{}'", self.resource_id))?;
+        let mut params = self.extra_params.clone();
+        params.insert("id".to_string(), Value::from(id));
+        Ok(Value::Object(params))
+    }
+
+    /// Zeroize and drop the secret params, e.g. before keeping the action for repeat
+    pub fn wipe_secrets(&mut self) {
+        wipe_secret_params(&mut self.extra_params, &self.secrets);
+    }
+}
+
+impl Drop for PendingAction {
+    fn drop(&mut self) {
+        self.wipe_secrets();
+    }
+}
+
+/// Zeroize and remove the named params from a parameter map
+pub fn wipe_secret_params(params: &mut serde_json::Map<String, Value>, secrets: &[String]) {
+    for name in secrets {
+        if let Some(Value::String(mut value)) = params.remove(name) {
+            value.zeroize();
+        }
+    }
+}
+
+/// List picker state for actions that need a second parameter
+#[derive(Debug, Clone)]
+pub struct SelectState {
+    /// What is picked (e.g. "host")
+    pub label: String,
+    /// Candidates as (id, name)
+    pub options: Vec<(String, String)>,
+    pub selected: usize,
+    /// Param receiving the chosen ID
+    pub param: String,
+    /// Word joining the confirm message and the choice
+    pub preposition: String,
+    /// Picker shown after this one, if any
+    pub next: Option<SelectConfig>,
+    /// Whether an earlier picker of the same action already ran
+    pub chained: bool,
+    /// Action run with the chosen ID once confirmed
+    pub pending: PendingAction,
+}
+
+/// Text prompt state for actions that need typed values
+#[derive(Debug, Clone)]
+pub struct InputState {
+    pub fields: Vec<InputField>,
+    /// Index of the field receiving keystrokes
+    pub focused: usize,
+    /// Ask for a yes/no confirmation before running the action
+    pub confirm: bool,
+    /// Picker shown once the values are typed, if any
+    pub next: Option<SelectConfig>,
+    /// Action run with the values on Enter
+    pub pending: PendingAction,
+}
+
+impl InputState {
+    pub fn current_mut(&mut self) -> Option<&mut InputField> {
+        self.fields.get_mut(self.focused)
+    }
+
+    pub fn focus_next(&mut self) {
+        if !self.fields.is_empty() {
+            self.focused = (self.focused + 1) % self.fields.len();
+        }
+    }
+
+    pub fn focus_previous(&mut self) {
+        if !self.fields.is_empty() {
+            self.focused = (self.focused + self.fields.len() - 1) % self.fields.len();
+        }
+    }
+}
+
+/// A single editable text field
+#[derive(Debug, Clone)]
+pub struct InputField {
+    pub prompt: String,
+    pub buffer: String,
+    /// Cursor position in chars
+    pub cursor: usize,
+    /// Param receiving the typed value
+    pub param: String,
+    /// Checkbox state; None for text fields
+    pub toggle: Option<bool>,
+    /// Echo `*` instead of the typed text, and zeroize it on drop
+    pub masked: bool,
+}
+
+impl Drop for InputField {
+    fn drop(&mut self) {
+        if self.masked {
+            self.buffer.zeroize();
+        }
+    }
+}
+
+impl InputField {
+    /// Flip a checkbox field
+    pub fn flip(&mut self) {
+        if let Some(on) = self.toggle.as_mut() {
+            *on = !*on;
+        }
+    }
+
+    fn byte_index(&self) -> usize {
+        self.buffer
+            .char_indices()
+            .nth(self.cursor)
+            .map(|(i, _)| i)
+            .unwrap_or(self.buffer.len())
+    }
+
+    pub fn insert(&mut self, c: char) {
+        let index = self.byte_index();
+        self.buffer.insert(index, c);
+        self.cursor += 1;
+    }
+
+    pub fn backspace(&mut self) {
+        if self.cursor > 0 {
+            self.cursor -= 1;
+            let index = self.byte_index();
+            self.buffer.remove(index);
+        }
+    }
+
+    pub fn delete(&mut self) {
+        if self.cursor < self.buffer.chars().count() {
+            let index = self.byte_index();
+            self.buffer.remove(index);
+        }
+    }
+
+    pub fn move_left(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    pub fn move_right(&mut self) {
+        self.cursor = (self.cursor + 1).min(self.buffer.chars().count());
+    }
+
+    pub fn move_home(&mut self) {
+        self.cursor = 0;
+    }
+
+    pub fn move_end(&mut self) {
+        self.cursor = self.buffer.chars().count();
+    }
+
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+        self.cursor = 0;
+    }
+}
+
+/// How the describe view renders the item
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum DescribeFormat {
+    #[default]
+    Json,
+    Yaml,
+    /// OpenNebula's original XML, fetched on demand
+    Xml,
+}
+
+impl DescribeFormat {
+    pub fn label(self) -> &'static str {
+        match self {
+            DescribeFormat::Json => "JSON",
+            DescribeFormat::Yaml => "YAML",
+            DescribeFormat::Xml => "XML",
+        }
+    }
+}
+
+/// Output of a describe `:pipe` command
+#[derive(Debug, Clone)]
+pub struct PipeOutput {
+    pub command: String,
+    pub text: String,
+    pub success: bool,
 }
 
 /// Parent context for hierarchical navigation
@@ -41,16 +298,79 @@ pub struct ParentContext {
     pub resource_key: String,
     pub item: Value,
     pub display_name: String,
+    /// Command filters of the parent view, restored when going back
+    pub command_filters: Vec<ResourceFilter>,
 }
 
 /// Pagination state
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct PaginationState {
+    /// Token of the page currently shown (`None` for the first page)
+    pub current_token: Option<String>,
     pub next_token: Option<String>,
+    /// Tokens of the pages before this one, for going back
+    pub previous_tokens: Vec<Option<String>>,
     pub current_page: usize,
     pub has_more: bool,
 }
 
+impl Default for PaginationState {
+    fn default() -> Self {
+        Self {
+            current_token: None,
+            next_token: None,
+            previous_tokens: Vec::new(),
+            current_page: 1,
+            has_more: false,
+        }
+    }
+}
+
+/// What a list fetch does to the view once it lands
+enum FetchKind {
+    /// Reload the current page
+    Refresh,
+    /// Move to the next page, remembering the token of the current one
+    NextPage(Option<String>),
+    /// Move back one page
+    PrevPage,
+    /// First page of a newly entered view; Esc goes back to the snapshot
+    View(Box<ViewSnapshot>),
+}
+
+/// A list fetch, answered from the cache or by a background task
+struct Fetch {
+    id: u64,
+    kind: FetchKind,
+    page_token: Option<String>,
+    /// Resource, filters and page the result is cached under
+    cache_key: String,
+    /// Cursor position and item when the fetch started, to follow it afterwards
+    prev_selected: usize,
+    selected_id: Option<String>,
+}
+
+/// Result of a background list fetch, sent back to the UI loop
+struct FetchDone {
+    id: u64,
+    result: Result<PaginatedResult>,
+}
+
+/// View state captured before navigating, restored if the fetch is canceled
+struct ViewSnapshot {
+    resource_key: String,
+    parent_context: Option<ParentContext>,
+    navigation_stack: Vec<ParentContext>,
+    selected: usize,
+    filter_text: String,
+    filter_active: bool,
+    command_filters: Vec<ResourceFilter>,
+    sort_column: Option<usize>,
+    sort_desc: bool,
+    column_offset: usize,
+    pagination: PaginationState,
+}
+
 /// Main application state
 pub struct App {
     // OpenNebula Client
@@ -61,14 +381,36 @@ pub struct App {
 
     // Dynamic data storage (JSON)
     pub items: Vec<Value>,
+    /// Table rows visible below the header, recorded on each render
+    pub table_viewport: Cell<usize>,
+    /// Table area inside the border (header row included) from the last render
+    pub table_area: Cell<Rect>,
+    /// Index of the item drawn on the first table row
+    pub table_offset: Cell<usize>,
+    /// Resource the loaded `items` came from (lags behind during navigation)
+    items_resource_key: String,
     pub filtered_items: Vec<Value>,
 
     // Navigation state
     pub selected: usize,
     pub mode: Mode,
     pub filter_text: String,
+    /// Regex filter that failed to compile, with the reason
+    filter_error: Option<(String, String)>,
     pub filter_active: bool,
 
+    /// Server-side filters given on the resource command (e.g. `:one-vms state=running`)
+    pub command_filters: Vec<ResourceFilter>,
+
+    // Sorting: index into visible_columns(), None = API order
+    pub sort_column: Option<usize>,
+    pub sort_desc: bool,
+
+    // Horizontal scrolling: index into visible_columns() of the first column drawn
+    pub column_offset: usize,
+    /// Whether columns past the right edge were left out of the last render
+    pub columns_hidden_right: Cell<bool>,
+
     // Hierarchical navigation
     pub parent_context: Option<ParentContext>,
     pub navigation_stack: Vec<ParentContext>,
@@ -78,21 +420,83 @@ pub struct App {
     pub command_suggestions: Vec<String>,
     pub command_suggestion_selected: usize,
     pub command_preview: Option<String>,
+    /// Executed commands, oldest first, persisted across restarts
+    pub command_history: Vec<String>,
+    /// Entry recalled with Ctrl+p/Ctrl+n, None while typing a new command
+    pub history_index: Option<usize>,
 
     // Confirmation
     pub pending_action: Option<PendingAction>,
+    pub last_action: Option<PendingAction>,
+    pub confirm_last_activity: Option<std::time::Instant>,
+
+    // List selection
+    pub select: Option<SelectState>,
+
+    // Text input
+    pub input: Option<InputState>,
 
     // UI state
     pub loading: bool,
+    /// Loading spinner frame, advanced on each UI tick while `loading`
+    pub spinner_frame: usize,
+    /// Connection check (one.system.version) in flight, started every
+    /// HEALTH_CHECK_INTERVAL
+    health_task: Option<tokio::task::JoinHandle<Result<Value>>>,
+    last_health_check: std::time::Instant,
+    /// Describe-view detail fetch still in flight
+    detail_task: Option<tokio::task::JoinHandle<Result<Option<Value>>>>,
+    /// Action running in the background (see `start_action`)
+    action_task: Option<tokio::task::JoinHandle<ActionDone>>,
+    /// VM being fetched for `c`, answered with the VM to connect to
+    connect_task: Option<tokio::task::JoinHandle<Value>>,
+    /// List fetch in flight with its task, its sequence number and the channel it reports on
+    fetch: Option<(Fetch, tokio::task::JoinHandle<()>)>,
+    fetch_seq: u64,
+    /// A refresh asked for while a fetch was running, started once it finishes
+    refresh_queued: bool,
+    /// Recent list results by resource, filters and page, reused for `LIST_CACHE_TTL`
+    list_cache: HashMap<String, (std::time::Instant, PaginatedResult)>,
+    fetch_tx: mpsc::UnboundedSender<FetchDone>,
+    fetch_rx: mpsc::UnboundedReceiver<FetchDone>,
     pub error_message: Option<String>,
+    /// Confirmation of a successful action and when it was set; cleared after a few seconds
+    pub status_message: Option<(String, std::time::Instant)>,
+    /// IDs of items marked with Space; actions run on all of them
+    pub selected_set: HashSet<String>,
+    /// Recent actions and API errors, oldest first (see `ACTION_LOG_CAPACITY`)
+    pub action_log: VecDeque<LogEntry>,
     pub describe_scroll: usize,
     pub describe_data: Option<Value>,
+    pub describe_format: DescribeFormat,
+    /// Pretty-printed raw XML of the described item, shown in the XML format
+    pub describe_xml: Option<String>,
+    /// Lines visible in the describe view, recorded on each render
+    pub describe_viewport: Cell<usize>,
+    /// Soft-wrap long lines at the describe view width (toggled with `w`)
+    pub describe_wrap: bool,
+    /// Text width of the describe view, recorded on each render
+    pub describe_width: Cell<usize>,
+    /// Describe-view search: query, typing state, matching line numbers and the current match
+    pub describe_search: String,
+    pub describe_search_active: bool,
+    pub describe_matches: Vec<usize>,
+    pub describe_match: usize,
+    pub pipe_output: Option<PipeOutput>,
+    /// Console command (`c` on a VM) waiting for the terminal to be released
+    pub pending_connect: Option<String>,
+    /// Describe scroll offsets by "resource:id", with the line count they applied to
+    pub describe_scroll_memory: HashMap<String, (usize, usize)>,
 
     // Auto-refresh
     pub last_refresh: std::time::Instant,
+    /// Auto-refresh period; None = off
+    pub refresh_interval: Option<std::time::Duration>,
 
     // Key press tracking
     pub last_key_press: Option<(KeyCode, std::time::Instant)>,
+    /// Last left click: item index and time, for double-click detection
+    pub last_click: Option<(usize, std::time::Instant)>,
 
     // Read-only mode
     pub readonly: bool,
@@ -106,48 +510,219 @@ pub struct App {
     // Endpoint info
     pub endpoint: String,
     pub username: String,
+    /// OpenNebula version of the server, once known
+    pub server_version: Option<String>,
+    /// Whether the last request to the frontend got through
+    pub connected: bool,
+    /// Federation zone switched to with `:zone`, if any
+    pub zone: Option<String>,
+
+    // Transient columns added with `:col add`, by resource key
+    pub custom_columns: HashMap<String, Vec<ColumnDef>>,
+
+    // User configuration
+    pub config: Config,
+    pub theme: Theme,
+    pub keymap: KeyMap,
 }
 
 impl App {
     /// Create App from pre-initialized components
-    pub fn from_initialized(client: OneClient, initial_items: Vec<Value>, readonly: bool) -> Self {
+    pub fn from_initialized(
+        client: OneClient,
+        initial_items: Vec<Value>,
+        config: Config,
+        readonly: bool,
+    ) -> Self {
         let filtered_items = initial_items.clone();
         let endpoint = client.endpoint().to_string();
         let username = client.username().to_string();
+        let current_resource_key = config.initial_resource().to_string();
+        // The saved filter belongs to the saved resource
+        let filter_text = config
+            .last_filter
+            .clone()
+            .filter(|_| config.last_resource.as_deref() == Some(current_resource_key.as_str()))
+            .unwrap_or_default();
 
-        Self {
+        let (fetch_tx, fetch_rx) = mpsc::unbounded_channel();
+
+        let mut app = Self {
             client,
-            current_resource_key: "one-vms".to_string(),
+            items_resource_key: current_resource_key.clone(),
+            current_resource_key,
             items: initial_items,
+            table_viewport: Cell::new(DEFAULT_TABLE_VIEWPORT),
+            table_area: Cell::new(Rect::default()),
+            table_offset: Cell::new(0),
             filtered_items,
             selected: 0,
             mode: Mode::Normal,
-            filter_text: String::new(),
+            filter_text,
+            filter_error: None,
             filter_active: false,
+            command_filters: Vec::new(),
+            sort_column: None,
+            sort_desc: false,
+            column_offset: 0,
+            columns_hidden_right: Cell::new(false),
             parent_context: None,
             navigation_stack: Vec::new(),
             command_text: String::new(),
             command_suggestions: Vec::new(),
             command_suggestion_selected: 0,
             command_preview: None,
+            command_history: load_history(),
+            history_index: None,
             pending_action: None,
+            last_action: None,
+            confirm_last_activity: None,
+            status_message: None,
+            selected_set: HashSet::new(),
+            action_log: VecDeque::new(),
+            select: None,
+            input: None,
             loading: false,
+            spinner_frame: 0,
+            health_task: None,
+            last_health_check: std::time::Instant::now(),
+            detail_task: None,
+            connect_task: None,
+            action_task: None,
+            fetch: None,
+            fetch_seq: 0,
+            refresh_queued: false,
+            list_cache: HashMap::new(),
+            fetch_tx,
+            fetch_rx,
             error_message: None,
             describe_scroll: 0,
             describe_data: None,
+            describe_format: DescribeFormat::default(),
+            describe_xml: None,
+            describe_viewport: Cell::new(DEFAULT_DESCRIBE_VIEWPORT),
+            describe_wrap: false,
+            describe_width: Cell::new(0),
+            describe_search: String::new(),
+            describe_search_active: false,
+            describe_matches: Vec::new(),
+            describe_match: 0,
+            pipe_output: None,
+            pending_connect: None,
+            describe_scroll_memory: HashMap::new(),
             last_refresh: std::time::Instant::now(),
+            refresh_interval: config.refresh_interval,
+            theme: Theme::from_config(&config.theme),
+            keymap: KeyMap::from_config(&config.keybindings),
             last_key_press: None,
+            last_click: None,
             readonly,
             warning_message: None,
             pagination: PaginationState::default(),
             endpoint,
             username,
+            server_version: None,
+            connected: true,
+            zone: None,
+            custom_columns: HashMap::new(),
+            config,
+        };
+        app.apply_filter();
+        app
+    }
+
+    /// Remember the current top-level resource and filter for the next launch
+    pub fn save_session(&mut self) {
+        let (resource_key, filter) = match self
+            .navigation_stack
+            .first()
+            .or(self.parent_context.as_ref())
+        {
+            // In a sub-resource view: reopen its root, whose filter was cleared on the way in
+            Some(root) => (root.resource_key.clone(), None),
+            None => (
+                self.current_resource_key.clone(),
+                Some(self.filter_text.clone()).filter(|f| !f.is_empty()),
+            ),
+        };
+        self.config.remember_session(&resource_key, filter);
+        if let Err(e) = self.config.save() {
+            tracing::warn!("Failed to save config: {}", e);
+        }
+        if let Err(e) = save_history(&self.command_history) {
+            tracing::warn!("Failed to save command history: {}", e);
         }
     }
 
-    /// Check if auto-refresh is needed (disabled)
+    /// Check if auto-refresh is due (only while browsing the list)
     pub fn needs_refresh(&self) -> bool {
-        false
+        self.mode == Mode::Normal
+            && !self.is_loading()
+            && self
+                .refresh_interval
+                .is_some_and(|interval| self.last_refresh.elapsed() >= interval)
+    }
+
+    /// Handle `:export csv|json`: write the filtered list to a timestamped file
+    /// in the config dir (CSV has the visible columns, JSON the full objects)
+    fn export_command(&mut self, args: &[&str]) {
+        let format = match args {
+            [format] if matches!(*format, "csv" | "json") => *format,
+            _ => {
+                self.error_message = Some("Usage: export csv|json".to_string());
+                return;
+            }
+        };
+        let content = if format == "csv" {
+            list_to_csv(&self.filtered_items, &self.visible_columns())
+        } else {
+            match serde_json::to_string_pretty(&self.filtered_items) {
+                Ok(json) => json,
+                Err(e) => {
+                    self.error_message = Some(format!("Export failed: {}", e));
+                    return;
+                }
+            }
+        };
+
+        let path = crate::config::config_dir().join(format!(
+            "{}-{}.{}",
+            self.current_resource_key,
+            chrono::Local::now().format("%Y%m%d-%H%M%S"),
+            format
+        ));
+        let written = std::fs::create_dir_all(crate::config::config_dir())
+            .and_then(|_| std::fs::write(&path, content));
+        match written {
+            Ok(()) => self.set_status(format!(
+                "Exported {} items to {}",
+                self.filtered_items.len(),
+                path.display()
+            )),
+            Err(e) => {
+                tracing::error!("Failed to write {:?}: {}", path, e);
+                self.error_message = Some(format!("Export failed: {}", e));
+            }
+        }
+    }
+
+    /// Handle `:refresh-interval <duration>|off`
+    fn refresh_interval_command(&mut self, args: &[&str]) {
+        match args {
+            ["off"] | ["0"] => self.refresh_interval = None,
+            [value] => match parse_refresh_interval(value) {
+                Ok(interval) => self.refresh_interval = Some(interval),
+                Err(e) => {
+                    self.error_message = Some(format!("Invalid interval: {}", e));
+                    return;
+                }
+            },
+            _ => {
+                self.error_message = Some("Usage: :refresh-interval <5s|1m|off>".to_string());
+                return;
+            }
+        }
+        self.mark_refreshed();
     }
 
     /// Reset refresh timer
@@ -163,6 +738,57 @@ impl App {
         get_resource(&self.current_resource_key)
     }
 
+    /// Columns of the current resource followed by any `:col add` columns
+    pub fn visible_columns(&self) -> Vec<ColumnDef> {
+        let mut columns = self
+            .current_resource()
+            .map(|r| r.columns.clone())
+            .unwrap_or_default();
+        if let Some(custom) = self.custom_columns.get(&self.current_resource_key) {
+            columns.extend(custom.iter().cloned());
+        }
+        columns
+    }
+
+    /// Handle `:col add <header> <json_path>` and `:col rm <header>`
+    fn column_command(&mut self, args: &[&str]) {
+        match args {
+            ["add", header, json_path] => {
+                let columns = self
+                    .custom_columns
+                    .entry(self.current_resource_key.clone())
+                    .or_default();
+                columns.retain(|c| !c.header.eq_ignore_ascii_case(header));
+                columns.push(ColumnDef {
+                    header: header.to_uppercase(),
+                    json_path: json_path.to_string(),
+                    width: CUSTOM_COLUMN_WIDTH,
+                    color_map: None,
+                    format: None,
+                    flag_symbol: None,
+                });
+            }
+            ["rm", header] => {
+                let removed = self
+                    .custom_columns
+                    .get_mut(&self.current_resource_key)
+                    .map(|columns| {
+                        let before = columns.len();
+                        columns.retain(|c| !c.header.eq_ignore_ascii_case(header));
+                        before != columns.len()
+                    })
+                    .unwrap_or(false);
+                if !removed {
+                    self.error_message = Some(format!("No custom column '{}'", header));
+                }
+            }
+            _ => {
+                self.error_message =
+                    Some("Usage: col add <header> <json_path> | col rm <header>".to_string());
+            }
+        }
+    }
+
     pub fn get_available_commands(&self) -> Vec<String> {
         let mut commands: Vec<String> = get_all_resource_keys()
             .iter()
@@ -177,45 +803,187 @@ impl App {
     // Data Fetching
     // =========================================================================
 
-    pub async fn refresh_current(&mut self) -> Result<()> {
-        self.fetch_page(self.pagination.next_token.clone()).await
+    /// Reload the current page in the background, after the running fetch if there is one
+    pub fn refresh_current(&mut self) -> Result<()> {
+        if self.fetch.is_some() {
+            // The running fetch may predate an action's changes; reload after it
+            self.refresh_queued = true;
+        } else {
+            self.fetch_page(self.pagination.current_token.clone(), FetchKind::Refresh);
+        }
+        Ok(())
+    }
+
+    /// ID of the selected item, if the loaded items belong to the current resource
+    fn selected_id(&self) -> Option<String> {
+        if self.items_resource_key != self.current_resource_key {
+            return None;
+        }
+        let resource = self.current_resource()?;
+        Some(extract_json_value(
+            self.selected_item()?,
+            &resource.id_field,
+        ))
+    }
+
+    /// Load the next page, if the last fetch returned a full one
+    pub fn next_page(&mut self) -> Result<()> {
+        let Some(token) = self.pagination.next_token.clone() else {
+            return Ok(());
+        };
+        if self.fetch.is_none() {
+            let current = self.pagination.current_token.clone();
+            self.fetch_page(Some(token), FetchKind::NextPage(current));
+        }
+        Ok(())
+    }
+
+    /// Go back to the previous page
+    pub fn prev_page(&mut self) -> Result<()> {
+        let Some(token) = self.pagination.previous_tokens.last().cloned() else {
+            return Ok(());
+        };
+        if self.fetch.is_none() {
+            self.fetch_page(token, FetchKind::PrevPage);
+        }
+        Ok(())
     }
 
-    async fn fetch_page(&mut self, page_token: Option<String>) -> Result<()> {
+    /// Start fetching a page of the current resource on a background task; the
+    /// result arrives through the fetch channel (see `poll_fetch`)
+    fn fetch_page(&mut self, page_token: Option<String>, mut kind: FetchKind) {
         if self.current_resource().is_none() {
             self.error_message = Some(format!("Unknown resource: {}", self.current_resource_key));
-            return Ok(());
+            return;
+        }
+
+        // A newer fetch replaces a running one; a view that never loaded
+        // keeps the view from before it for Esc
+        if let Some((running, task)) = self.fetch.take() {
+            task.abort();
+            if let (FetchKind::View(_), FetchKind::View(previous)) = (&kind, running.kind) {
+                kind = FetchKind::View(previous);
+            }
         }
 
-        self.loading = true;
         self.error_message = None;
+        self.fetch_seq += 1;
+        let id = self.fetch_seq;
+
+        let mut filters = self.build_filters_from_context();
+        filters.extend(self.command_filters.iter().cloned());
+        let cache_key = format!(
+            "{}|{:?}|{:?}",
+            self.current_resource_key, filters, page_token
+        );
+        let fetch = Fetch {
+            id,
+            kind,
+            page_token: page_token.clone(),
+            cache_key,
+            prev_selected: self.selected,
+            selected_id: self.selected_id(),
+        };
+
+        // Navigation reuses a recent result; `R` and auto-refresh always go to the server
+        if !matches!(fetch.kind, FetchKind::Refresh) {
+            let cached = self
+                .list_cache
+                .get(&fetch.cache_key)
+                .filter(|(fetched, _)| fetched.elapsed() < LIST_CACHE_TTL)
+                .map(|(_, result)| result.clone());
+            if let Some(result) = cached {
+                self.apply_fetch(fetch, Ok(result));
+                return;
+            }
+        }
 
-        let filters = self.build_filters_from_context();
+        let client = self.client.clone();
+        let resource_key = self.current_resource_key.clone();
+        let token = page_token.clone();
+        let tx = self.fetch_tx.clone();
+        let task = tokio::spawn(async move {
+            let result =
+                fetch_resources_paginated(&resource_key, &client, &filters, token.as_deref()).await;
+            // The receiver only goes away with the app
+            let _ = tx.send(FetchDone { id, result });
+        });
 
-        match fetch_resources_paginated(
-            &self.current_resource_key,
-            &self.client,
-            &filters,
-            page_token.as_deref(),
-        )
-        .await
-        {
+        self.fetch = Some((fetch, task));
+    }
+
+    /// Apply list fetches that finished since the last UI tick
+    pub fn poll_fetch(&mut self) {
+        while let Ok(done) = self.fetch_rx.try_recv() {
+            // Results of replaced or canceled fetches are dropped
+            if self.fetch.as_ref().map(|(f, _)| f.id) != Some(done.id) {
+                continue;
+            }
+            if let Some((fetch, _)) = self.fetch.take() {
+                if let Ok(result) = &done.result {
+                    let entry = (std::time::Instant::now(), result.clone());
+                    self.list_cache.insert(fetch.cache_key.clone(), entry);
+                }
+                self.apply_fetch(fetch, done.result);
+                if std::mem::take(&mut self.refresh_queued) {
+                    let _ = self.refresh_current();
+                }
+            }
+        }
+    }
+
+    fn apply_fetch(&mut self, fetch: Fetch, result: Result<PaginatedResult>) {
+        match result {
             Ok(result) => {
-                let prev_selected = self.selected;
+                if self.items_resource_key != self.current_resource_key {
+                    self.selected_set.clear();
+                }
                 self.items = result.items;
+                self.items_resource_key = self.current_resource_key.clone();
+                // Forget marks on items that are gone
+                if let Some(resource) = self.current_resource() {
+                    let ids: HashSet<String> = self
+                        .items
+                        .iter()
+                        .map(|item| extract_json_value(item, &resource.id_field))
+                        .collect();
+                    self.selected_set.retain(|id| ids.contains(id));
+                }
                 self.apply_filter();
 
                 self.pagination.has_more = result.next_token.is_some();
                 self.pagination.next_token = result.next_token;
-
-                if prev_selected < self.filtered_items.len() {
-                    self.selected = prev_selected;
-                } else {
-                    self.selected = 0;
+                self.pagination.current_token = fetch.page_token;
+
+                // Follow the selected item if rows moved; clamp if it's gone
+                let id_field = self.current_resource().map(|r| r.id_field.as_str());
+                let moved_to = fetch
+                    .selected_id
+                    .zip(id_field)
+                    .and_then(|(id, field)| position_by_id(&self.filtered_items, field, &id));
+                self.selected = moved_to
+                    .unwrap_or(fetch.prev_selected)
+                    .min(self.filtered_items.len().saturating_sub(1));
+
+                match fetch.kind {
+                    FetchKind::NextPage(previous_token) => {
+                        self.pagination.previous_tokens.push(previous_token);
+                        self.pagination.current_page += 1;
+                        self.selected = 0;
+                    }
+                    FetchKind::PrevPage => {
+                        self.pagination.previous_tokens.pop();
+                        self.pagination.current_page -= 1;
+                        self.selected = 0;
+                    }
+                    FetchKind::Refresh | FetchKind::View(_) => {}
                 }
             }
             Err(e) => {
-                self.error_message = Some(crate::one::client::format_one_error(&e));
+                let message = crate::one::client::format_one_error(&e);
+                let text = format!("list {} → err: {}", self.current_resource_key, message);
+                self.log_event(text, false);
+                self.error_message = Some(message);
                 self.items.clear();
                 self.filtered_items.clear();
                 self.selected = 0;
@@ -223,15 +991,105 @@ impl App {
             }
         }
 
-        self.loading = false;
         self.mark_refreshed();
-        Ok(())
+    }
+
+    /// Whether a list fetch is running
+    pub fn fetch_in_flight(&self) -> bool {
+        self.fetch.is_some()
+    }
+
+    /// Whether a newly entered view is still waiting for its first page
+    pub fn view_pending(&self) -> bool {
+        matches!(
+            self.fetch,
+            Some((
+                Fetch {
+                    kind: FetchKind::View(_),
+                    ..
+                },
+                _
+            ))
+        )
+    }
+
+    /// Stop the running list fetch; a view that never loaded goes back to the
+    /// previous one so the old items stay consistent with the header
+    pub fn cancel_fetch(&mut self) {
+        let Some((fetch, task)) = self.fetch.take() else {
+            return;
+        };
+        task.abort();
+        self.refresh_queued = false;
+        if let FetchKind::View(previous) = fetch.kind {
+            self.current_resource_key = previous.resource_key;
+            self.parent_context = previous.parent_context;
+            self.navigation_stack = previous.navigation_stack;
+            self.selected = previous.selected;
+            self.filter_text = previous.filter_text;
+            self.filter_active = previous.filter_active;
+            self.command_filters = previous.command_filters;
+            self.sort_column = previous.sort_column;
+            self.sort_desc = previous.sort_desc;
+            self.column_offset = previous.column_offset;
+            self.pagination = previous.pagination;
+            self.apply_filter();
+        }
+    }
+
+    /// Forget cached lists, e.g. after an action changed the cloud
+    pub fn invalidate_list_cache(&mut self) {
+        self.list_cache.clear();
+    }
+
+    /// Whether anything is loading: a list fetch, a describe detail or an action
+    pub fn is_loading(&self) -> bool {
+        self.loading
+            || self.fetch.is_some()
+            || self.detail_task.is_some()
+            || self.connect_task.is_some()
+            || self.action_task.is_some()
+    }
+
+    /// Whether the describe view is waiting for the item's full object
+    pub fn detail_loading(&self) -> bool {
+        self.detail_task.is_some()
     }
 
     pub fn reset_pagination(&mut self) {
         self.pagination = PaginationState::default();
     }
 
+    /// Page state of the startup fetch, so `]` works before the first refresh
+    pub fn set_initial_page(&mut self, next_token: Option<String>) {
+        self.pagination.has_more = next_token.is_some();
+        self.pagination.next_token = next_token;
+    }
+
+    fn view_snapshot(&self) -> ViewSnapshot {
+        ViewSnapshot {
+            resource_key: self.current_resource_key.clone(),
+            parent_context: self.parent_context.clone(),
+            navigation_stack: self.navigation_stack.clone(),
+            selected: self.selected,
+            filter_text: self.filter_text.clone(),
+            filter_active: self.filter_active,
+            command_filters: self.command_filters.clone(),
+            sort_column: self.sort_column,
+            sort_desc: self.sort_desc,
+            column_offset: self.column_offset,
+            pagination: self.pagination.clone(),
+        }
+    }
+
+    /// Start loading the first page of a newly entered view; canceling the
+    /// fetch goes back to `previous`
+    fn load_view(&mut self, previous: ViewSnapshot) -> Result<()> {
+        self.reset_pagination();
+        self.fetch_page(None, FetchKind::View(Box::new(previous)));
+        Ok(())
+    }
+
     fn build_filters_from_context(&self) -> Vec<ResourceFilter> {
         let Some(parent) = &self.parent_context else {
             return Vec::new();
@@ -240,10 +1098,25 @@ impl App {
         if let Some(parent_resource) = get_resource(&parent.resource_key) {
             for sub in &parent_resource.sub_resources {
                 if sub.resource_key == self.current_resource_key {
-                    let parent_id = extract_json_value(&parent.item, &sub.parent_id_field);
-                    if parent_id != "-" {
-                        return vec![ResourceFilter::new(&sub.filter_param, vec![parent_id])];
-                    }
+                    // The parent field may hold several IDs, e.g. SECURITY_GROUPS = "0,100"
+                    let parent_ids: Vec<String> =
+                        extract_json_values(&parent.item, &sub.parent_id_field)
+                            .iter()
+                            .flat_map(|v| v.split(','))
+                            .map(|id| id.trim().to_string())
+                            .filter(|id| !id.is_empty() && id != "-")
+                            .collect();
+                    let filter = match &sub.filter_field {
+                        // No IDs on the parent: show nothing rather than everything
+                        Some(field) => {
+                            ResourceFilter::client_side(&sub.filter_param, field, parent_ids)
+                        }
+                        None if !parent_ids.is_empty() => {
+                            ResourceFilter::new(&sub.filter_param, parent_ids)
+                        }
+                        None => return Vec::new(),
+                    };
+                    return vec![filter];
                 }
             }
         }
@@ -257,19 +1130,49 @@ impl App {
 
     pub fn apply_filter(&mut self) {
         let filter = self.filter_text.to_lowercase();
+        // `~pattern`, or any filter with `regex_filter` set, is a regex
+        let regex_pattern = match self.filter_text.strip_prefix('~') {
+            Some(pattern) => Some(pattern.to_string()),
+            None if self.config.regex_filter => Some(self.filter_text.clone()),
+            None => None,
+        };
 
-        if filter.is_empty() {
+        let columns = self.visible_columns();
+        if filter.is_empty() || regex_pattern.as_deref() == Some("") {
             self.filtered_items = self.items.clone();
+        } else if let Some(pattern) = regex_pattern {
+            // An invalid pattern (often one still being typed) keeps the last results
+            match self.regex_filtered_items(&pattern, &columns) {
+                Ok(items) => self.filtered_items = items,
+                Err(e) => {
+                    self.filter_error = Some((self.filter_text.clone(), e));
+                    return;
+                }
+            }
+        } else if let Some((col, value)) = parse_column_filter(&filter, &columns) {
+            // `header:value` only tests that column
+            self.filtered_items = self
+                .items
+                .iter()
+                .filter(|item| column_matches(item, col, value))
+                .cloned()
+                .collect();
         } else {
             let resource = self.current_resource();
+            let custom_columns = self
+                .custom_columns
+                .get(&self.current_resource_key)
+                .cloned()
+                .unwrap_or_default();
             self.filtered_items = self
                 .items
                 .iter()
                 .filter(|item| {
                     if let Some(res) = resource {
-                        let name = extract_json_value(item, &res.name_field).to_lowercase();
-                        let id = extract_json_value(item, &res.id_field).to_lowercase();
-                        name.contains(&filter) || id.contains(&filter)
+                        res.matches_filter(item, &filter)
+                            || custom_columns
+                                .iter()
+                                .any(|col| column_matches(item, col, &filter))
                     } else {
                         item.to_string().to_lowercase().contains(&filter)
                     }
@@ -278,11 +1181,99 @@ impl App {
                 .collect();
         }
 
+        self.apply_sort();
+
         if self.selected >= self.filtered_items.len() && !self.filtered_items.is_empty() {
             self.selected = self.filtered_items.len() - 1;
         }
     }
 
+    /// Items matching a regex filter, case-insensitively; `header:pattern` only
+    /// tests that column
+    fn regex_filtered_items(
+        &self,
+        pattern: &str,
+        columns: &[ColumnDef],
+    ) -> std::result::Result<Vec<Value>, String> {
+        let (column, pattern) = match parse_column_filter(pattern, columns) {
+            Some((col, value)) => (Some(col), value),
+            None => (None, pattern),
+        };
+        let regex = regex::RegexBuilder::new(pattern)
+            .case_insensitive(true)
+            .build()
+            .map_err(|e| regex_error_reason(&e))?;
+
+        let resource = self.current_resource();
+        Ok(self
+            .items
+            .iter()
+            .filter(|item| match (column, resource) {
+                (Some(col), _) => regex.is_match(&format_display_value(item, col)),
+                (None, Some(res)) => regex.is_match(&res.row_text(item, columns)),
+                (None, None) => regex.is_match(&item.to_string()),
+            })
+            .cloned()
+            .collect())
+    }
+
+    /// Why the current `/` filter's regex doesn't compile, if it doesn't
+    pub fn filter_error(&self) -> Option<&str> {
+        self.filter_error
+            .as_ref()
+            .filter(|(text, _)| *text == self.filter_text)
+            .map(|(_, reason)| reason.as_str())
+    }
+
+    /// Order `filtered_items` by the sort column, if any (stable, so ties keep API order)
+    pub fn apply_sort(&mut self) {
+        let Some(index) = self.sort_column else {
+            return;
+        };
+        let Some(col) = self.visible_columns().get(index).cloned() else {
+            return;
+        };
+        self.filtered_items.sort_by(|a, b| {
+            let order = compare_column_values(a, b, &col);
+            if self.sort_desc {
+                order.reverse()
+            } else {
+                order
+            }
+        });
+    }
+
+    /// Sort by the next column; past the last column, go back to API order
+    pub fn cycle_sort_column(&mut self) {
+        let count = self.visible_columns().len();
+        self.sort_column = match self.sort_column {
+            None if count > 0 => Some(0),
+            Some(index) if index + 1 < count => Some(index + 1),
+            _ => None,
+        };
+        self.sort_desc = false;
+        self.apply_filter();
+    }
+
+    pub fn toggle_sort_direction(&mut self) {
+        if self.sort_column.is_some() {
+            self.sort_desc = !self.sort_desc;
+            self.apply_filter();
+        }
+    }
+
+    /// Shift the table one column right, while columns are hidden on that side
+    pub fn scroll_columns_right(&mut self) {
+        if self.columns_hidden_right.get() {
+            self.column_offset += 1;
+        }
+    }
+
+    /// Shift the table one column left
+    pub fn scroll_columns_left(&mut self) {
+        self.column_offset = self.column_offset.saturating_sub(1);
+    }
+
     pub fn clear_filter(&mut self) {
         self.filter_text.clear();
         self.filter_active = false;
@@ -293,7 +1284,11 @@ impl App {
     // Navigation
     // =========================================================================
 
+    /// Item under the cursor; none while a newly entered view is loading
     pub fn selected_item(&self) -> Option<&Value> {
+        if self.view_pending() {
+            return None;
+        }
         self.filtered_items.get(self.selected)
     }
 
@@ -305,21 +1300,185 @@ impl App {
             .map(|item| serde_json::to_string_pretty(item).unwrap_or_default())
     }
 
-    pub fn describe_line_count(&self) -> usize {
-        self.selected_item_json()
-            .map(|s| s.lines().count())
-            .unwrap_or(0)
+    /// Text shown in the describe view: pipe output if any, otherwise the item
+    /// in the active format
+    pub fn describe_text(&self) -> Option<String> {
+        if let Some(ref output) = self.pipe_output {
+            return Some(output.text.clone());
+        }
+        match self.describe_format {
+            DescribeFormat::Json => self.selected_item_json(),
+            DescribeFormat::Xml => self
+                .describe_xml
+                .clone()
+                .or_else(|| self.selected_item_json()),
+            DescribeFormat::Yaml => self
+                .describe_data
+                .as_ref()
+                .or_else(|| self.selected_item())
+                .map(|item| serde_yaml_ng::to_string(item).unwrap_or_default()),
+        }
     }
 
-    pub fn describe_scroll_to_bottom(&mut self, visible_lines: usize) {
-        let total = self.describe_line_count();
-        self.describe_scroll = total.saturating_sub(visible_lines);
+    /// Switch the describe view between JSON and YAML
+    pub fn toggle_describe_format(&mut self) {
+        self.describe_format = match self.describe_format {
+            DescribeFormat::Json => DescribeFormat::Yaml,
+            DescribeFormat::Yaml | DescribeFormat::Xml => DescribeFormat::Json,
+        };
+        self.describe_scroll = 0;
+        self.update_describe_matches();
     }
 
-    pub fn next(&mut self) {
-        if !self.filtered_items.is_empty() {
-            self.selected = (self.selected + 1).min(self.filtered_items.len() - 1);
-        }
+    /// Switch the describe view between the parsed item and OpenNebula's raw
+    /// XML, which keeps attributes and element order the JSON loses
+    pub async fn toggle_describe_xml(&mut self) {
+        if self.describe_format == DescribeFormat::Xml {
+            self.describe_format = DescribeFormat::Json;
+        } else {
+            let Some(resource) = self.current_resource() else {
+                return;
+            };
+            if resource.detail_sdk_method.is_none() {
+                self.error_message = Some(format!("No XML view for {}", resource.display_name));
+                return;
+            }
+            let Some(item) = self.describe_data.as_ref().or_else(|| self.selected_item()) else {
+                return;
+            };
+            let Ok(id) = extract_json_value(item, &resource.id_field).parse::<i64>() else {
+                return;
+            };
+            let xml = fetch_raw_xml(&resource.service, &self.client, id)
+                .await
+                .and_then(|xml| pretty_print_xml(&xml));
+            match xml {
+                Ok(xml) => {
+                    self.describe_xml = Some(xml);
+                    self.describe_format = DescribeFormat::Xml;
+                }
+                Err(e) => {
+                    self.error_message = Some(crate::one::client::format_one_error(&e));
+                    return;
+                }
+            }
+        }
+        self.describe_scroll = 0;
+        self.update_describe_matches();
+    }
+
+    /// Drop the raw XML of the previously described item
+    fn reset_describe_xml(&mut self) {
+        self.describe_xml = None;
+        if self.describe_format == DescribeFormat::Xml {
+            self.describe_format = DescribeFormat::Json;
+        }
+    }
+
+    /// Start typing a describe-view search, replacing the previous query
+    pub fn start_describe_search(&mut self) {
+        self.describe_search.clear();
+        self.describe_search_active = true;
+        self.update_describe_matches();
+    }
+
+    /// Drop the describe-view search and its highlights
+    pub fn clear_describe_search(&mut self) {
+        self.describe_search.clear();
+        self.describe_search_active = false;
+        self.describe_matches.clear();
+        self.describe_match = 0;
+    }
+
+    /// Recompute matching lines and jump to the first one at or below the scroll position
+    pub fn update_describe_matches(&mut self) {
+        let text = self.describe_text().unwrap_or_default();
+        self.describe_matches = find_matching_lines(&text, &self.describe_search);
+        self.describe_match = self
+            .describe_matches
+            .iter()
+            .position(|&line| line >= self.describe_scroll)
+            .unwrap_or(0);
+        if let Some(&line) = self.describe_matches.get(self.describe_match) {
+            self.describe_scroll = line;
+        }
+    }
+
+    /// Jump to the next search match, wrapping around
+    pub fn describe_next_match(&mut self) {
+        if self.describe_matches.is_empty() {
+            return;
+        }
+        self.describe_match = (self.describe_match + 1) % self.describe_matches.len();
+        self.describe_scroll = self.describe_matches[self.describe_match];
+    }
+
+    /// Jump to the previous search match, wrapping around
+    pub fn describe_previous_match(&mut self) {
+        if self.describe_matches.is_empty() {
+            return;
+        }
+        let count = self.describe_matches.len();
+        self.describe_match = (self.describe_match + count - 1) % count;
+        self.describe_scroll = self.describe_matches[self.describe_match];
+    }
+
+    pub fn describe_line_count(&self) -> usize {
+        // The log view shares the describe scroll state
+        if self.mode == Mode::Log {
+            return self.action_log.len();
+        }
+        self.describe_text().map(|s| s.lines().count()).unwrap_or(0)
+    }
+
+    /// Last scroll offset that still fills the describe viewport
+    pub fn describe_max_scroll(&self) -> usize {
+        let viewport = self.describe_viewport.get();
+        let width = self.describe_width.get();
+        if !self.describe_wrap || width == 0 || self.mode == Mode::Log {
+            return self.describe_line_count().saturating_sub(viewport);
+        }
+        // Scroll counts source lines; stop once the wrapped rows below fit on screen
+        let text = self.describe_text().unwrap_or_default();
+        let lines: Vec<&str> = text.lines().collect();
+        let mut rows = 0;
+        for (index, line) in lines.iter().enumerate().rev() {
+            rows += wrapped_rows(line, width);
+            if rows > viewport {
+                return index + 1;
+            }
+        }
+        0
+    }
+
+    /// Turn soft-wrapping of the describe view on or off
+    pub fn toggle_describe_wrap(&mut self) {
+        self.describe_wrap = !self.describe_wrap;
+        self.describe_scroll = self.describe_scroll.min(self.describe_max_scroll());
+    }
+
+    pub fn describe_scroll_to_bottom(&mut self) {
+        self.describe_scroll = self.describe_max_scroll();
+    }
+
+    /// Scroll the describe view by `lines` (negative = up), stopping at the bottom
+    pub fn scroll_describe(&mut self, lines: isize) {
+        self.describe_scroll = self
+            .describe_scroll
+            .saturating_add_signed(lines)
+            .min(self.describe_max_scroll());
+    }
+
+    /// Scroll the describe view by whole pages (negative = up)
+    pub fn page_describe(&mut self, pages: isize) {
+        let page = self.describe_viewport.get().max(1) as isize;
+        self.scroll_describe(pages * page);
+    }
+
+    pub fn next(&mut self) {
+        if !self.filtered_items.is_empty() {
+            self.selected = (self.selected + 1).min(self.filtered_items.len() - 1);
+        }
     }
 
     pub fn previous(&mut self) {
@@ -336,13 +1495,28 @@ impl App {
         }
     }
 
-    pub fn page_down(&mut self, page_size: usize) {
+    /// Item drawn at screen cell (`column`, `row`) in the last rendered table
+    pub fn item_at(&self, column: u16, row: u16) -> Option<usize> {
+        let area = self.table_area.get();
+        if !area.contains(Position::new(column, row)) {
+            return None;
+        }
+        // The first line inside the border is the header
+        let line = row.checked_sub(area.y + 1)? as usize;
+        Some(self.table_offset.get() + line).filter(|&index| index < self.filtered_items.len())
+    }
+
+    /// Move the cursor down by one screen of table rows
+    pub fn page_down(&mut self) {
+        let page_size = self.table_viewport.get().max(1);
         if !self.filtered_items.is_empty() {
             self.selected = (self.selected + page_size).min(self.filtered_items.len() - 1);
         }
     }
 
-    pub fn page_up(&mut self, page_size: usize) {
+    /// Move the cursor up by one screen of table rows
+    pub fn page_up(&mut self) {
+        let page_size = self.table_viewport.get().max(1);
         self.selected = self.selected.saturating_sub(page_size);
     }
 
@@ -356,6 +1530,23 @@ impl App {
         self.command_suggestions = self.get_available_commands();
         self.command_suggestion_selected = 0;
         self.command_preview = None;
+        self.history_index = None;
+    }
+
+    /// Open command mode pre-filled with `pipe ` (describe mode only)
+    pub fn enter_pipe_command(&mut self) {
+        self.enter_command_mode();
+        self.command_text = "pipe ".to_string();
+        self.update_command_suggestions();
+    }
+
+    /// Leave command mode, returning to the describe view it was opened from
+    pub fn exit_command_mode(&mut self) {
+        if self.describe_data.is_some() {
+            self.mode = Mode::Describe;
+        } else {
+            self.exit_mode();
+        }
     }
 
     pub fn update_command_suggestions(&mut self) {
@@ -365,10 +1556,13 @@ impl App {
         if input.is_empty() {
             self.command_suggestions = all_commands;
         } else {
-            self.command_suggestions = all_commands
+            let mut scored: Vec<(i64, String)> = all_commands
                 .into_iter()
-                .filter(|cmd| cmd.contains(&input))
+                .filter_map(|cmd| Some((fuzzy_match(&input, &cmd)?.0, cmd)))
                 .collect();
+            // Stable: equal scores keep alphabetical order
+            scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+            self.command_suggestions = scored.into_iter().map(|(_, cmd)| cmd).collect();
         }
 
         if self.command_suggestion_selected >= self.command_suggestions.len() {
@@ -415,27 +1609,307 @@ impl App {
         }
     }
 
+    /// Recall the previous history entry into the command line
+    pub fn history_prev(&mut self) {
+        let index = match self.history_index {
+            Some(0) => return,
+            Some(index) => index - 1,
+            None if self.command_history.is_empty() => return,
+            None => self.command_history.len() - 1,
+        };
+        self.recall_history(Some(index));
+    }
+
+    /// Recall the next history entry, or return to an empty command line
+    pub fn history_next(&mut self) {
+        match self.history_index {
+            Some(index) if index + 1 < self.command_history.len() => {
+                self.recall_history(Some(index + 1))
+            }
+            Some(_) => self.recall_history(None),
+            None => {}
+        }
+    }
+
+    fn recall_history(&mut self, index: Option<usize>) {
+        self.history_index = index;
+        self.command_text = index
+            .and_then(|i| self.command_history.get(i).cloned())
+            .unwrap_or_default();
+        self.update_command_suggestions();
+    }
+
+    /// Append an executed command, skipping repeats of the previous one
+    fn push_history(&mut self, command: &str) {
+        if self.command_history.last().map(String::as_str) != Some(command) {
+            self.command_history.push(command.to_string());
+        }
+        let excess = self.command_history.len().saturating_sub(HISTORY_LIMIT);
+        self.command_history.drain(..excess);
+    }
+
     pub fn enter_help_mode(&mut self) {
         self.mode = Mode::Help;
     }
 
-    pub async fn enter_describe_mode(&mut self) {
-        if self.filtered_items.is_empty() {
+    pub fn enter_describe_mode(&mut self) {
+        if self.selected_item().is_none() {
             return;
         }
 
         self.mode = Mode::Describe;
         self.describe_scroll = 0;
         self.describe_data = None;
+        self.reset_describe_xml();
+        self.cancel_detail_fetch();
 
         if let Some(item) = self.selected_item().cloned() {
+            // Show the list item right away; the full object replaces it when it arrives
+            let resource = self
+                .current_resource()
+                .filter(|r| r.detail_sdk_method.is_some());
+            if let Some(resource) = resource {
+                if let Ok(id) = extract_json_value(&item, &resource.id_field).parse() {
+                    let client = self.client.clone();
+                    self.detail_task = Some(tokio::spawn(async move {
+                        fetch_detail(&client, resource, id).await
+                    }));
+                }
+            }
             self.describe_data = Some(item);
         }
+
+        // The remembered offset belongs to the full object, not the list item
+        if self.detail_task.is_none() {
+            self.restore_describe_scroll();
+        }
+    }
+
+    /// Swap in the describe view's detail once its fetch has finished
+    pub async fn poll_detail_fetch(&mut self) {
+        if !self.detail_task.as_ref().is_some_and(|t| t.is_finished()) {
+            return;
+        }
+        let Some(task) = self.detail_task.take() else {
+            return;
+        };
+        match task.await.map_err(anyhow::Error::from).and_then(|r| r) {
+            Ok(Some(item)) => {
+                self.describe_data = Some(item);
+                self.restore_describe_scroll();
+            }
+            Ok(None) => {}
+            Err(e) => tracing::warn!("Detail fetch failed, showing the list item: {}", e),
+        }
+    }
+
+    /// Check the frontend is still reachable every HEALTH_CHECK_INTERVAL, in
+    /// the background, and record the outcome once the check finishes
+    pub async fn poll_health_check(&mut self) {
+        if self.health_task.as_ref().is_some_and(|t| t.is_finished()) {
+            if let Some(task) = self.health_task.take() {
+                match task.await.map_err(anyhow::Error::from).and_then(|r| r) {
+                    Ok(version) => self.set_server_version(&version),
+                    Err(e) => {
+                        if self.connected {
+                            tracing::warn!("Frontend unreachable: {}", e);
+                        }
+                        self.connected = false;
+                    }
+                }
+            }
+        } else if self.health_task.is_none()
+            && self.last_health_check.elapsed() >= HEALTH_CHECK_INTERVAL
+        {
+            self.last_health_check = std::time::Instant::now();
+            let client = self.client.clone();
+            self.health_task = Some(tokio::spawn(async move { client.get_version().await }));
+        }
+    }
+
+    /// Record a successful one.system.version reply
+    pub fn set_server_version(&mut self, version: &Value) {
+        if let Some(version) = version.as_str() {
+            self.server_version = Some(version.to_string());
+        }
+        self.connected = true;
+    }
+
+    /// Drop a detail fetch that's no longer wanted
+    fn cancel_detail_fetch(&mut self) {
+        if let Some(task) = self.detail_task.take() {
+            task.abort();
+        }
+    }
+
+    /// Advance the loading spinner; called on every UI tick
+    pub fn tick_spinner(&mut self) {
+        if self.is_loading() {
+            self.spinner_frame = self.spinner_frame.wrapping_add(1);
+        }
+    }
+
+    /// Memory key for the selected item's describe scroll
+    fn describe_memory_key(&self) -> Option<String> {
+        let resource = self.current_resource()?;
+        let id = extract_json_value(self.selected_item()?, &resource.id_field);
+        Some(format!("{}:{}", self.current_resource_key, id))
+    }
+
+    fn remember_describe_scroll(&mut self) {
+        if self.pipe_output.is_some() {
+            return;
+        }
+        if let Some(key) = self.describe_memory_key() {
+            let entry = (self.describe_scroll, self.describe_line_count());
+            self.describe_scroll_memory.insert(key, entry);
+        }
+    }
+
+    /// Restore a remembered scroll offset unless the item's content changed size
+    fn restore_describe_scroll(&mut self) {
+        let Some(key) = self.describe_memory_key() else {
+            return;
+        };
+        if let Some((scroll, line_count)) = self.describe_scroll_memory.get(&key).copied() {
+            if line_count == self.describe_line_count() {
+                self.describe_scroll = scroll;
+            } else {
+                self.describe_scroll_memory.remove(&key);
+            }
+        }
     }
 
     pub fn enter_confirm_mode(&mut self, pending: PendingAction) {
         self.pending_action = Some(pending);
         self.mode = Mode::Confirm;
+        self.confirm_last_activity = Some(std::time::Instant::now());
+    }
+
+    /// Whether a confirmation is waiting: the yes/no dialog or a typed confirmation
+    fn confirm_armed(&self) -> bool {
+        match self.mode {
+            Mode::Confirm => true,
+            Mode::Input => self
+                .input
+                .as_ref()
+                .is_some_and(|input| input.pending.confirm_text.is_some()),
+            _ => false,
+        }
+    }
+
+    /// Record activity in the confirm dialog, postponing the auto-cancel
+    pub fn touch_confirm(&mut self) {
+        if self.confirm_armed() {
+            self.confirm_last_activity = Some(std::time::Instant::now());
+        }
+    }
+
+    /// Cancel an armed confirm dialog left idle past the configured timeout
+    pub fn check_confirm_timeout(&mut self) {
+        if !self.confirm_armed() {
+            return;
+        }
+        let (Some(secs), Some(last)) =
+            (self.config.confirm_timeout_secs, self.confirm_last_activity)
+        else {
+            return;
+        };
+        if last.elapsed() >= std::time::Duration::from_secs(secs) {
+            tracing::info!("Confirmation timed out after {}s", secs);
+            self.exit_mode();
+        }
+    }
+
+    /// Record an action or API outcome in the action log
+    pub fn log_event(&mut self, text: String, ok: bool) {
+        if ok {
+            tracing::info!("{}", text);
+        } else {
+            tracing::warn!("{}", text);
+        }
+        if self.action_log.len() >= ACTION_LOG_CAPACITY {
+            self.action_log.pop_front();
+        }
+        self.action_log.push_back(LogEntry {
+            time: chrono::Local::now().format("%H:%M:%S").to_string(),
+            text,
+            ok,
+        });
+    }
+
+    /// Open the action log, scrolled to the newest entries
+    pub fn enter_log_mode(&mut self) {
+        self.mode = Mode::Log;
+        self.describe_scroll_to_bottom();
+    }
+
+    /// Show a transient confirmation in the crumb bar
+    pub fn set_status(&mut self, message: String) {
+        self.status_message = Some((message, std::time::Instant::now()));
+    }
+
+    /// Drop the status message once it has been shown long enough
+    pub fn check_status_timeout(&mut self) {
+        if self
+            .status_message
+            .as_ref()
+            .is_some_and(|(_, set_at)| set_at.elapsed() >= STATUS_MESSAGE_DURATION)
+        {
+            self.status_message = None;
+        }
+    }
+
+    /// Fetch the selected VM in the background to build its console command
+    /// (see `poll_connect`)
+    pub fn connect_selected(&mut self) {
+        if self.current_resource_key != "one-vms" || self.connect_task.is_some() {
+            return;
+        }
+        let Some(item) = self.selected_item().cloned() else {
+            return;
+        };
+        // The list entry may lag behind; prefer the VM's current placement and ports
+        let id = extract_json_value(&item, "ID").parse::<i64>();
+        let resource = self.current_resource();
+        let client = self.client.clone();
+        self.connect_task = Some(tokio::spawn(async move {
+            let detail = match (id, resource) {
+                (Ok(id), Some(resource)) => fetch_detail(&client, resource, id)
+                    .await
+                    .unwrap_or_else(|e| {
+                        tracing::warn!("Failed to fetch VM {} for connect: {}", id, e);
+                        None
+                    }),
+                _ => None,
+            };
+            detail.unwrap_or(item)
+        }));
+    }
+
+    /// Once the VM for `c` is fetched, build its console command from
+    /// `connect_command` for the main loop to run with the TUI suspended
+    pub async fn poll_connect(&mut self) {
+        if !self.connect_task.as_ref().is_some_and(|t| t.is_finished()) {
+            return;
+        }
+        let Some(task) = self.connect_task.take() else {
+            return;
+        };
+        let vm = match task.await {
+            Ok(vm) => vm,
+            Err(e) => {
+                tracing::warn!("Connect task failed: {}", e);
+                return;
+            }
+        };
+        match connect_command(self.config.connect_command(), &vm) {
+            Ok(command) => {
+                tracing::info!("Connecting to VM: {}", command);
+                self.pending_connect = Some(command);
+            }
+            Err(e) => self.show_warning(&e),
+        }
     }
 
     pub fn show_warning(&mut self, message: &str) {
@@ -443,12 +1917,18 @@ impl App {
         self.mode = Mode::Warning;
     }
 
-    pub fn create_pending_action(
+    pub async fn create_pending_action(
         &self,
         action: &crate::resource::ActionDef,
         resource_id: &str,
     ) -> Option<PendingAction> {
-        let config = action.get_confirm_config()?;
+        let config = action
+            .get_confirm_config()
+            .or_else(|| action.input.as_ref().map(|_| Default::default()))?;
+        let message = config
+            .message
+            .clone()
+            .unwrap_or_else(|| action.display_name.clone());
         let resource_name = self
             .selected_item()
             .and_then(|item| {
@@ -461,116 +1941,724 @@ impl App {
                 None
             })
             .unwrap_or_else(|| resource_id.to_string());
-
-        let message = config
-            .message
-            .unwrap_or_else(|| action.display_name.clone());
+        // A new resource has no name yet; the typed values say what gets created
+        let target = if action.creates {
+            String::new()
+        } else {
+            format!(" '{}'", resource_name)
+        };
         let default_no = !config.default_yes;
+        let warning = self.transitional_state_warning();
 
-        Some(PendingAction {
+        let mut pending = PendingAction {
             service: self.current_resource()?.service.clone(),
             sdk_method: action.sdk_method.clone(),
+            action_key: action.key.clone(),
             resource_id: resource_id.to_string(),
-            message: format!("{} '{}'?", message, resource_name),
+            summary: format!("{}{}", message, target),
+            message: format!("{}{}?", message, target),
             default_no,
             destructive: config.destructive,
-            selected_yes: config.default_yes,
-        })
+            selected_yes: config.default_yes && warning.is_none(),
+            warning,
+            preview: None,
+            extra_params: action.params.clone(),
+            navigate_to: action.navigate_to.clone(),
+            confirm_text: (config.destructive && config.require_typed)
+                .then(|| resource_name.clone()),
+            batch: Vec::new(),
+            secrets: Vec::new(),
+        };
+
+        if !action.creates && !self.selected_set.is_empty() {
+            let resource = self.current_resource()?;
+            let marked: Vec<&Value> = self
+                .items
+                .iter()
+                .filter(|item| {
+                    self.selected_set
+                        .contains(&extract_json_value(item, &resource.id_field))
+                })
+                .collect();
+            // Items whose state forbids the action are left out rather than failing
+            let (allowed, blocked): (Vec<&Value>, Vec<&Value>) = marked
+                .into_iter()
+                .partition(|item| action.check_state(item).is_none());
+            pending.batch = allowed
+                .iter()
+                .map(|item| {
+                    (
+                        extract_json_value(item, &resource.id_field),
+                        item_params(action, item),
+                    )
+                })
+                .collect();
+            pending.summary = format!(
+                "{}: {} {}",
+                message,
+                pending.batch.len(),
+                resource.display_name
+            );
+            pending.message = format!("{}?", pending.summary);
+            if pending.confirm_text.is_some() {
+                pending.confirm_text = Some(BATCH_CONFIRM_TEXT.to_string());
+            }
+            pending.warning = (!blocked.is_empty()).then(|| {
+                format!(
+                    "{} marked item(s) skipped: state does not allow {}",
+                    blocked.len(),
+                    action.display_name
+                )
+            });
+            pending.selected_yes = config.default_yes && pending.warning.is_none();
+            return Some(pending);
+        }
+
+        if let Some(item) = self.selected_item().filter(|_| !action.creates) {
+            pending.extra_params.extend(item_params(action, item));
+        }
+        pending.preview = self.preview_action(&pending).await;
+        Some(pending)
     }
 
-    pub fn exit_mode(&mut self) {
-        self.mode = Mode::Normal;
-        self.pending_action = None;
-        self.describe_data = None;
+    /// Mark or unmark the selected item for a batch action, then move down
+    pub fn toggle_selected_mark(&mut self) {
+        let Some(id) = self.selected_id() else {
+            return;
+        };
+        if !self.selected_set.remove(&id) {
+            self.selected_set.insert(id);
+        }
+        self.next();
     }
 
-    // =========================================================================
-    // Resource Navigation
-    // =========================================================================
+    /// Whether an item is marked for a batch action
+    pub fn is_marked(&self, item: &Value) -> bool {
+        !self.selected_set.is_empty()
+            && self.current_resource().is_some_and(|resource| {
+                self.selected_set
+                    .contains(&extract_json_value(item, &resource.id_field))
+            })
+    }
 
-    pub async fn navigate_to_resource(&mut self, resource_key: &str) -> Result<()> {
-        if get_resource(resource_key).is_none() {
-            self.error_message = Some(format!("Unknown resource: {}", resource_key));
-            return Ok(());
-        }
+    /// Fetch the candidates of `config.resource` and open the list picker
+    ///
+    /// `exclude` drops one ID from the list (e.g. a VM's current host).
+    pub async fn enter_select(
+        &mut self,
+        pending: PendingAction,
+        config: &SelectConfig,
+        exclude: Option<String>,
+    ) {
+        let options: Vec<(String, String)> = if config.options.is_empty() {
+            match self.fetch_select_options(config, exclude).await {
+                Some(options) => config
+                    .default_option
+                    .iter()
+                    .map(|option| (option.id.to_string(), option.name.clone()))
+                    .chain(options)
+                    .collect(),
+                None => return,
+            }
+        } else {
+            config
+                .options
+                .iter()
+                .map(|option| (option.id.to_string(), option.name.clone()))
+                .collect()
+        };
 
-        self.parent_context = None;
-        self.navigation_stack.clear();
-        self.current_resource_key = resource_key.to_string();
-        self.selected = 0;
-        self.filter_text.clear();
-        self.filter_active = false;
-        self.mode = Mode::Normal;
+        if options.is_empty() {
+            self.show_warning(&format!("No {} available", config.label));
+            return;
+        }
 
-        self.reset_pagination();
-        self.refresh_current().await?;
-        Ok(())
+        self.select = Some(SelectState {
+            label: config.label.clone(),
+            options,
+            selected: 0,
+            param: config.param.clone(),
+            preposition: config
+                .preposition
+                .clone()
+                .unwrap_or_else(|| "to".to_string()),
+            next: config.then.as_deref().cloned(),
+            chained: false,
+            pending,
+        });
+        self.mode = Mode::Select;
     }
 
-    pub async fn navigate_to_sub_resource(&mut self, sub_resource_key: &str) -> Result<()> {
-        let Some(selected_item) = self.selected_item().cloned() else {
-            return Ok(());
+    /// Items of `config.resource` as (id, name), or None after reporting an error
+    async fn fetch_select_options(
+        &mut self,
+        config: &SelectConfig,
+        exclude: Option<String>,
+    ) -> Option<Vec<(String, String)>> {
+        let resource_key = config.resource.as_deref()?;
+        let Some(resource) = get_resource(resource_key) else {
+            self.error_message = Some(format!("Unknown resource: {}", resource_key));
+            return None;
         };
 
-        let Some(current_resource) = self.current_resource() else {
-            return Ok(());
-        };
+        let filters = config.filters();
+        self.loading = true;
+        let result = fetch_resources(resource_key, &self.client, &filters).await;
+        self.loading = false;
 
-        let is_valid = current_resource
-            .sub_resources
-            .iter()
-            .any(|s| s.resource_key == sub_resource_key);
+        match result {
+            Ok(items) => Some(
+                items
+                    .iter()
+                    .map(|item| {
+                        (
+                            extract_json_value(item, &resource.id_field),
+                            extract_json_value(item, &resource.name_field),
+                        )
+                    })
+                    .filter(|(id, _)| exclude.as_ref() != Some(id))
+                    .collect(),
+            ),
+            Err(e) => {
+                self.error_message = Some(crate::one::client::format_one_error(&e));
+                None
+            }
+        }
+    }
 
-        if !is_valid {
-            self.error_message = Some(format!(
-                "{} is not a sub-resource of {}",
-                sub_resource_key, self.current_resource_key
-            ));
-            return Ok(());
+    pub fn select_next(&mut self) {
+        if let Some(ref mut select) = self.select {
+            select.selected = (select.selected + 1).min(select.options.len().saturating_sub(1));
         }
+    }
 
-        let display_name = extract_json_value(&selected_item, &current_resource.name_field);
-        let id = extract_json_value(&selected_item, &current_resource.id_field);
-        let display = if display_name != "-" {
-            display_name
-        } else {
-            id
-        };
+    pub fn select_previous(&mut self) {
+        if let Some(ref mut select) = self.select {
+            select.selected = select.selected.saturating_sub(1);
+        }
+    }
 
-        if let Some(ctx) = self.parent_context.take() {
-            self.navigation_stack.push(ctx);
+    pub fn select_first(&mut self) {
+        if let Some(ref mut select) = self.select {
+            select.selected = 0;
         }
+    }
 
-        self.parent_context = Some(ParentContext {
-            resource_key: self.current_resource_key.clone(),
-            item: selected_item,
-            display_name: display,
+    pub fn select_last(&mut self) {
+        if let Some(ref mut select) = self.select {
+            select.selected = select.options.len().saturating_sub(1);
+        }
+    }
+
+    /// Apply the chosen item to the pending action and ask for confirmation
+    pub async fn confirm_selection(&mut self) {
+        let Some(select) = self.select.take() else {
+            return;
+        };
+        let Some((id, name)) = select.options.get(select.selected).cloned() else {
+            self.exit_mode();
+            return;
+        };
+
+        let Ok(id) = id.parse::<i64>() else {
+            self.exit_mode();
+            self.show_warning(&format!("Invalid {} ID '{}'", select.label, id));
+            return;
+        };
+        let mut pending = select.pending;
+        pending.extra_params.insert(select.param, Value::from(id));
+        pending.message = format!(
+            "{}{} {} '{}'?",
+            pending.message.trim_end_matches('?'),
+            if select.chained {
+                ",".to_string()
+            } else {
+                format!(" {}", select.preposition)
+            },
+            select.label,
+            name
+        );
+        if let Some(next) = select.next {
+            self.enter_select(pending, &next, None).await;
+            if let Some(state) = self.select.as_mut() {
+                state.chained = true;
+            }
+            return;
+        }
+        pending.preview = self.preview_action(&pending).await;
+        self.enter_confirm_mode(pending);
+    }
+
+    /// Open the text prompt for an action, each field pre-filled from `initial`
+    pub fn enter_input(
+        &mut self,
+        mut pending: PendingAction,
+        config: &InputConfig,
+        initial: Vec<String>,
+    ) {
+        let fields = config
+            .fields
+            .iter()
+            .zip(initial.into_iter().chain(std::iter::repeat(String::new())))
+            .map(|(field, value)| InputField {
+                prompt: field.prompt.clone(),
+                cursor: value.chars().count(),
+                buffer: value,
+                param: field.param.clone(),
+                toggle: field.toggle.then_some(false),
+                masked: field.masked,
+            })
+            .collect();
+        pending.secrets = config
+            .fields
+            .iter()
+            .filter(|field| field.masked)
+            .map(|field| field.param.clone())
+            .collect();
+        self.input = Some(InputState {
+            fields,
+            focused: 0,
+            confirm: config.confirm,
+            next: config.then.clone(),
+            pending,
+        });
+        self.mode = Mode::Input;
+    }
+
+    /// Ask the user to type the confirmation text of the pending action
+    pub fn enter_typed_confirm(&mut self) {
+        let Some(pending) = self.pending_action.take() else {
+            return;
+        };
+        let Some(expected) = pending.confirm_text.clone() else {
+            return;
+        };
+        self.input = Some(InputState {
+            fields: vec![InputField {
+                prompt: format!("Type '{}' to confirm:", expected),
+                buffer: String::new(),
+                cursor: 0,
+                param: String::new(),
+                toggle: None,
+                masked: false,
+            }],
+            focused: 0,
+            confirm: false,
+            next: None,
+            pending,
+        });
+        self.mode = Mode::Input;
+        self.confirm_last_activity = Some(std::time::Instant::now());
+    }
+
+    /// Take the pending action with the typed values applied.
+    /// Empty text fields are left out; at least one value is required.
+    /// For type-to-confirm, the text must match exactly and is not sent.
+    pub fn take_input_action(&mut self) -> Option<PendingAction> {
+        let input = self.input.as_ref()?;
+        if let Some(expected) = &input.pending.confirm_text {
+            let typed = input.fields.first().map(|field| field.buffer.trim());
+            if typed != Some(expected.as_str()) {
+                self.error_message = Some(format!("Type '{}' to confirm", expected));
+                return None;
+            }
+            self.error_message = None;
+            let mut pending = self.input.take()?.pending;
+            pending.confirm_text = None;
+            return Some(pending);
+        }
+        let values: Vec<(String, Value)> = input
+            .fields
+            .iter()
+            .filter_map(|field| {
+                let value = match field.toggle {
+                    Some(on) => Value::Bool(on),
+                    // Secrets are sent exactly as typed
+                    None if field.masked => Some(field.buffer.as_str())
+                        .filter(|value| !value.is_empty())
+                        .map(Value::from)?,
+                    None => Some(field.buffer.trim())
+                        .filter(|value| !value.is_empty())
+                        .map(Value::from)?,
+                };
+                Some((field.param.clone(), value))
+            })
+            .collect();
+        if values.is_empty() {
+            return None;
+        }
+        let mut pending = self.input.take()?.pending;
+        for (param, value) in values {
+            pending.extra_params.insert(param, value);
+        }
+        Some(pending)
+    }
+
+    /// Show the resolved call of an action whose values were typed, then ask to confirm
+    pub async fn confirm_input(&mut self, mut pending: PendingAction) {
+        pending.preview = self.preview_action(&pending).await;
+        self.enter_confirm_mode(pending);
+    }
+
+    /// Resolve the XML-RPC call(s) an action would send, without sending them.
+    /// Secret params are shown as `****`.
+    async fn preview_action(&self, pending: &PendingAction) -> Option<String> {
+        let dry_run = self.client.dry_run();
+        let mut params = pending.params().ok()?;
+        if let Value::Object(map) = &mut params {
+            for name in &pending.secrets {
+                if let Some(Value::String(value)) = map.get_mut(name) {
+                    value.zeroize();
+                    *value = "****".to_string();
+                }
+            }
+        }
+        invoke_sdk_method(&pending.service, &pending.sdk_method, &dry_run, &params)
+            .await
+            .ok()?;
+        let calls = dry_run.recorded_calls();
+        if calls.is_empty() {
+            None
+        } else {
+            Some(calls.join("; "))
+        }
+    }
+
+    /// Warn when the selected VM is mid-transition (e.g. PROLOG_MIGRATE)
+    fn transitional_state_warning(&self) -> Option<String> {
+        if self.current_resource()?.service != "vm" {
+            return None;
+        }
+        let lcm_state = extract_json_value(self.selected_item()?, "LCM_STATE")
+            .parse::<i32>()
+            .ok()?;
+        if !is_transitional_lcm_state(lcm_state) {
+            return None;
+        }
+        Some(format!(
+            "VM is in transitional state {}; OpenNebula may reject this action",
+            format_lcm_state(lcm_state)
+        ))
+    }
+
+    /// Rebuild the last executed action against the currently selected item.
+    /// Also returns whether choices picked last time (e.g. the target host) were reused.
+    pub async fn create_repeat_action(&mut self) -> Option<(PendingAction, bool)> {
+        let Some(mut last) = self.last_action.clone() else {
+            self.error_message = Some("No action to repeat".to_string());
+            return None;
+        };
+
+        let resource = self.current_resource()?;
+        let action = resource
+            .actions
+            .iter()
+            .find(|a| resource.service == last.service && a.key == last.action_key);
+        let Some(action) = action else {
+            self.error_message = Some(format!(
+                "Cannot repeat '{}' on {}",
+                last.action_key, self.current_resource_key
+            ));
+            return None;
+        };
+
+        if action.input.is_some() {
+            self.error_message = Some(format!(
+                "Cannot repeat '{}': it needs a new value",
+                action.display_name
+            ));
+            return None;
+        }
+
+        let item = self.selected_item()?;
+        let resource_id = extract_json_value(item, &resource.id_field);
+        let mut pending = self.create_pending_action(action, &resource_id).await?;
+        // Reuse what was picked last time; item params stay those of the current item
+        let picked = action
+            .select
+            .as_ref()
+            .map(|select| select.params())
+            .unwrap_or_default();
+        for param in &picked {
+            if let Some(value) = last.extra_params.remove(*param) {
+                pending.extra_params.insert(param.to_string(), value);
+            }
+        }
+        pending.preview = self.preview_action(&pending).await;
+        Some((pending, !picked.is_empty()))
+    }
+
+    pub fn exit_mode(&mut self) {
+        if self.mode == Mode::Describe {
+            self.remember_describe_scroll();
+        }
+        self.mode = Mode::Normal;
+        self.pending_action = None;
+        self.confirm_last_activity = None;
+        self.select = None;
+        self.input = None;
+        self.describe_data = None;
+        self.reset_describe_xml();
+        self.cancel_detail_fetch();
+        self.pipe_output = None;
+        self.clear_describe_search();
+    }
+
+    // =========================================================================
+    // Resource Navigation
+    // =========================================================================
+
+    pub fn navigate_to_resource(&mut self, resource_key: &str) -> Result<()> {
+        self.navigate_with_filters(resource_key, Vec::new())
+    }
+
+    /// Switch to a resource, fetching it with server-side `filters`
+    pub fn navigate_with_filters(
+        &mut self,
+        resource_key: &str,
+        filters: Vec<ResourceFilter>,
+    ) -> Result<()> {
+        if get_resource(resource_key).is_none() {
+            self.error_message = Some(format!("Unknown resource: {}", resource_key));
+            return Ok(());
+        }
+
+        let previous = self.view_snapshot();
+        self.parent_context = None;
+        self.navigation_stack.clear();
+        self.describe_data = None;
+        self.pipe_output = None;
+        self.current_resource_key = resource_key.to_string();
+        self.command_filters = filters;
+        self.selected = 0;
+        self.filter_text.clear();
+        self.filter_active = false;
+        self.sort_column = None;
+        self.column_offset = 0;
+        self.mode = Mode::Normal;
+
+        self.load_view(previous)
+    }
+
+    pub fn navigate_to_sub_resource(&mut self, sub_resource_key: &str) -> Result<()> {
+        let Some(selected_item) = self.selected_item().cloned() else {
+            return Ok(());
+        };
+
+        let Some(current_resource) = self.current_resource() else {
+            return Ok(());
+        };
+
+        let is_valid = current_resource
+            .sub_resources
+            .iter()
+            .any(|s| s.resource_key == sub_resource_key);
+
+        if !is_valid {
+            self.error_message = Some(format!(
+                "{} is not a sub-resource of {}",
+                sub_resource_key, self.current_resource_key
+            ));
+            return Ok(());
+        }
+
+        let display_name = extract_json_value(&selected_item, &current_resource.name_field);
+        let id = extract_json_value(&selected_item, &current_resource.id_field);
+        let display = if display_name != "-" {
+            display_name
+        } else {
+            id
+        };
+
+        let previous = self.view_snapshot();
+        if let Some(ctx) = self.parent_context.take() {
+            self.navigation_stack.push(ctx);
+        }
+
+        self.parent_context = Some(ParentContext {
+            resource_key: self.current_resource_key.clone(),
+            item: selected_item,
+            display_name: display,
+            command_filters: std::mem::take(&mut self.command_filters),
         });
 
         self.current_resource_key = sub_resource_key.to_string();
+        self.describe_data = None;
+        self.pipe_output = None;
         self.selected = 0;
         self.filter_text.clear();
         self.filter_active = false;
+        self.sort_column = None;
+        self.column_offset = 0;
 
-        self.reset_pagination();
-        self.refresh_current().await?;
-        Ok(())
+        self.load_view(previous)
     }
 
-    pub async fn navigate_back(&mut self) -> Result<()> {
+    pub fn navigate_back(&mut self) -> Result<()> {
+        let previous = self.view_snapshot();
         if let Some(parent) = self.parent_context.take() {
             self.parent_context = self.navigation_stack.pop();
             self.current_resource_key = parent.resource_key;
+            self.command_filters = parent.command_filters;
+            self.describe_data = None;
+            self.pipe_output = None;
             self.selected = 0;
             self.filter_text.clear();
             self.filter_active = false;
+            self.sort_column = None;
+            self.column_offset = 0;
+
+            self.load_view(previous)?;
+        }
+        Ok(())
+    }
+
+    /// Describe the item with `id` in `resource_key`, switching to that resource.
+    /// Items outside the loaded page are fetched with the resource's get method.
+    pub async fn jump_to_id(&mut self, resource_key: &str, id: i64) -> Result<()> {
+        let Some(resource) = get_resource(resource_key) else {
+            self.error_message = Some(format!("Unknown resource: {}", resource_key));
+            return Ok(());
+        };
+        if self.current_resource_key != resource_key {
+            self.navigate_to_resource(resource_key)?;
+        }
+        if !self.filter_text.is_empty() {
+            self.clear_filter();
+        }
+
+        // Until a newly entered view loads, the items are the previous resource's
+        let loaded = if self.view_pending() {
+            None
+        } else {
+            position_by_id(&self.filtered_items, &resource.id_field, &id.to_string())
+        };
+        if let Some(index) = loaded {
+            self.selected = index;
+            self.enter_describe_mode();
+            return Ok(());
+        }
 
-            self.reset_pagination();
-            self.refresh_current().await?;
+        match fetch_detail(&self.client, resource, id).await {
+            Ok(Some(item)) => {
+                self.describe_data = Some(item);
+                self.reset_describe_xml();
+                self.describe_scroll = 0;
+                self.mode = Mode::Describe;
+            }
+            Ok(None) => {
+                self.error_message = Some(format!("{} {} not found", resource.display_name, id));
+            }
+            Err(e) => {
+                self.error_message = Some(crate::one::client::format_one_error(&e));
+            }
         }
         Ok(())
     }
 
+    /// Resource key and ID of the parent item, if it can be refetched
+    fn parent_ref(&self) -> Option<(String, i64)> {
+        let parent = self.parent_context.as_ref()?;
+        let resource = get_resource(&parent.resource_key)?;
+        let id = extract_json_value(&parent.item, &resource.id_field)
+            .parse()
+            .ok()?;
+        Some((parent.resource_key.clone(), id))
+    }
+
+    /// Run an action (on every marked item for a batch) on a background task;
+    /// `poll_action` reports the outcome once it finishes
+    pub fn start_action(&mut self, pending: PendingAction) {
+        if self.action_task.is_some() {
+            self.error_message = Some("Another action is still running".to_string());
+            return;
+        }
+        let client = self.client.clone();
+        let parent = pending
+            .navigate_to
+            .is_none()
+            .then(|| self.parent_ref())
+            .flatten();
+        self.action_task = Some(tokio::spawn(run_action(client, pending, parent)));
+    }
+
+    /// Report an action that finished since the last UI tick
+    pub async fn poll_action(&mut self) {
+        if !self.action_task.as_ref().is_some_and(|t| t.is_finished()) {
+            return;
+        }
+        let Some(task) = self.action_task.take() else {
+            return;
+        };
+        match task.await {
+            Ok(done) => self.finish_action(done),
+            Err(e) => self.error_message = Some(format!("Action failed: {}", e)),
+        }
+    }
+
+    fn finish_action(&mut self, mut done: ActionDone) {
+        let mut succeeded = 0;
+        let mut failures = Vec::new();
+        for call in &done.calls {
+            match &call.result {
+                Ok(()) => {
+                    self.log_event(format!("invoked {} → ok", call.text), true);
+                    succeeded += 1;
+                }
+                Err(message) => {
+                    self.log_event(format!("invoked {} → err: {}", call.text, message), false);
+                    failures.push(format!("{}: {}", call.resource_id, message));
+                }
+            }
+        }
+        if succeeded > 0 {
+            self.invalidate_list_cache();
+        }
+        // The parent item the action may have changed, unless the user left it
+        if let Some((parent_ref, item)) = done.parent.take() {
+            if self.parent_ref() == Some(parent_ref) {
+                if let Some(parent) = self.parent_context.as_mut() {
+                    parent.item = item;
+                }
+            }
+        }
+
+        let pending = done.pending;
+        if !done.batch {
+            if let Some(message) = done.calls.into_iter().find_map(|c| c.result.err()) {
+                self.error_message = Some(message);
+                return;
+            }
+            let navigate_to = pending.navigate_to.clone();
+            self.set_status(format!("{}: done", pending.summary));
+            self.last_action = Some(pending);
+            let navigated = match navigate_to {
+                Some(resource_key) => self.navigate_to_resource(&resource_key),
+                None => self.refresh_current(),
+            };
+            if let Err(e) = navigated {
+                self.error_message = Some(e.to_string());
+            }
+            return;
+        }
+
+        self.selected_set.clear();
+        let _ = self.refresh_current();
+        // Reported after the refresh, which clears the error area
+        if failures.is_empty() {
+            self.set_status(format!("{}: done", pending.summary));
+        } else {
+            self.error_message = Some(format!(
+                "{}: {} ok, {} failed ({})",
+                pending.summary,
+                succeeded,
+                failures.len(),
+                failures[0]
+            ));
+        }
+        self.last_action = Some(pending);
+    }
+
     pub fn get_breadcrumb(&self) -> Vec<String> {
         let mut path = Vec::new();
 
@@ -590,11 +2678,151 @@ impl App {
     // Command Execution
     // =========================================================================
 
+    /// Feed the current describe JSON to an external command and show its output
+    pub async fn pipe_describe(&mut self, command: &str) {
+        if self.describe_data.is_none() {
+            self.error_message = Some("pipe is only available in describe mode".to_string());
+            return;
+        }
+        if command.is_empty() {
+            self.error_message = Some("Usage: pipe <command>".to_string());
+            return;
+        }
+        let input = self.selected_item_json().unwrap_or_default();
+
+        self.loading = true;
+        match run_pipe_command(command, &input).await {
+            Ok(output) => {
+                self.pipe_output = Some(output);
+                self.describe_scroll = 0;
+                self.mode = Mode::Describe;
+                self.update_describe_matches();
+            }
+            Err(e) => {
+                tracing::error!("pipe command failed: {}", e);
+                self.error_message = Some(format!("pipe: {}", e));
+            }
+        }
+        self.loading = false;
+    }
+
+    /// Reload credentials and reconnect, keeping the current view
+    pub async fn login(&mut self) -> Result<()> {
+        let mut client = self.client.clone();
+        if let Err(e) = client.reload_credentials() {
+            self.error_message = Some(format!("Login failed: {}", e));
+            return Ok(());
+        }
+        if let Some(lifetime) = self.config.session_token_lifetime() {
+            if let Err(e) = client.login(lifetime).await {
+                tracing::warn!("Token login unavailable, using password auth: {}", e);
+            }
+        }
+        let version = match client.get_version().await {
+            Ok(version) => version,
+            Err(e) => {
+                let message = crate::one::client::format_one_error(&e);
+                self.log_event(format!("login → err: {}", message), false);
+                self.error_message = Some(format!("Login failed: {}", message));
+                return Ok(());
+            }
+        };
+        self.set_server_version(&version);
+
+        self.log_event(format!("login as {} → ok", client.username()), true);
+        self.username = client.username().to_string();
+        self.client = client;
+        self.invalidate_list_cache();
+        self.refresh_current()
+    }
+
+    /// Handle `:readonly [on|off]`; bare `:readonly` toggles. Allowing writes
+    /// asks for confirmation first.
+    fn readonly_command(&mut self, args: &[&str]) {
+        let readonly = match args {
+            [] => !self.readonly,
+            ["on"] => true,
+            ["off"] => false,
+            _ => {
+                self.error_message = Some("Usage: readonly [on|off]".to_string());
+                return;
+            }
+        };
+        if readonly {
+            self.readonly = true;
+            self.set_status("Read-only mode: actions are disabled".to_string());
+        } else if self.readonly {
+            self.enter_confirm_mode(PendingAction {
+                service: READ_WRITE_SERVICE.to_string(),
+                sdk_method: "read-write".to_string(),
+                action_key: String::new(),
+                resource_id: String::new(),
+                summary: "Leave read-only mode".to_string(),
+                message: "Leave read-only mode and allow actions?".to_string(),
+                default_no: true,
+                destructive: false,
+                selected_yes: false,
+                warning: None,
+                preview: None,
+                extra_params: serde_json::Map::new(),
+                navigate_to: None,
+                confirm_text: None,
+                batch: Vec::new(),
+                secrets: Vec::new(),
+            });
+        }
+    }
+
+    /// Point the client at a federation zone's endpoint (`:zone <id>`), keeping
+    /// the login and the current view
+    pub async fn switch_zone(&mut self, arg: &str) -> Result<()> {
+        let Ok(zone_id) = arg.parse::<i64>() else {
+            self.error_message = Some("Usage: zone <id>".to_string());
+            return Ok(());
+        };
+        let zone = match self.client.get_zone(zone_id).await {
+            Ok(zone) => zone,
+            Err(e) => {
+                let message = crate::one::client::format_one_error(&e);
+                self.error_message = Some(format!("Zone {}: {}", zone_id, message));
+                return Ok(());
+            }
+        };
+        let name = extract_json_value(&zone, "ZONE.NAME");
+        let endpoint = extract_json_value(&zone, "ZONE.TEMPLATE.ENDPOINT");
+        if endpoint == "-" {
+            self.error_message = Some(format!("Zone {} has no ENDPOINT", name));
+            return Ok(());
+        }
+
+        let mut client = self.client.clone();
+        client.set_endpoint(&endpoint);
+        let version = match client.get_version().await {
+            Ok(version) => version,
+            Err(e) => {
+                let message = crate::one::client::format_one_error(&e);
+                self.log_event(format!("zone {} → err: {}", name, message), false);
+                self.error_message = Some(format!("Zone {} unreachable: {}", name, message));
+                return Ok(());
+            }
+        };
+        self.set_server_version(&version);
+
+        self.log_event(format!("zone {} ({}) → ok", name, endpoint), true);
+        self.endpoint = client.endpoint().to_string();
+        self.zone = Some(format!("{} ({})", name, zone_id));
+        self.client = client;
+        self.invalidate_list_cache();
+        self.refresh_current()
+    }
+
     pub async fn execute_command(&mut self) -> Result<bool> {
         let command_text = if self.command_text.is_empty() {
             self.command_preview.clone().unwrap_or_default()
         } else if let Some(preview) = &self.command_preview {
-            if preview.contains(&self.command_text) {
+            let typed = self.command_text.to_lowercase();
+            if !BUILTIN_COMMANDS.contains(&typed.as_str()) && fuzzy_match(&typed, preview).is_some()
+            {
                 preview.clone()
             } else {
                 self.command_text.clone()
@@ -608,25 +2836,83 @@ impl App {
         if parts.is_empty() {
             return Ok(false);
         }
+        self.push_history(&parts.join(" "));
 
         let cmd = parts[0];
 
         match cmd {
             "q" | "quit" => return Ok(true),
             "back" => {
-                self.navigate_back().await?;
+                self.navigate_back()?;
+            }
+            "capacity" => {
+                self.navigate_to_resource("one-capacity")?;
+            }
+            "log" => {
+                self.enter_log_mode();
+            }
+            "col" => {
+                self.column_command(&parts[1..]);
+            }
+            "login" => {
+                self.login().await?;
             }
+            "readonly" => {
+                self.readonly_command(&parts[1..]);
+            }
+            "export" => {
+                self.export_command(&parts[1..]);
+            }
+            // Bare `:zone` still opens the zone list
+            "zone" if parts.len() > 1 => {
+                self.switch_zone(parts[1]).await?;
+            }
+            "refresh-interval" => {
+                self.refresh_interval_command(&parts[1..]);
+            }
+            "pipe" => {
+                let pipe_command = command_text
+                    .trim_start()
+                    .strip_prefix("pipe")
+                    .unwrap_or_default()
+                    .trim()
+                    .to_string();
+                self.pipe_describe(&pipe_command).await;
+            }
+            // `:42` describes item 42 of the current view
+            _ if parts.len() == 1 && is_item_id(cmd) => match cmd.parse() {
+                Ok(id) => {
+                    let resource_key = self.current_resource_key.clone();
+                    self.jump_to_id(&resource_key, id).await?;
+                }
+                Err(_) => self.error_message = Some(format!("Invalid ID: {}", cmd)),
+            },
+            // `:vm 42` / `:one-vms 42` switch resource first
+            _ if parts.len() == 2
+                && is_item_id(parts[1])
+                && resource_for_command(cmd).is_some() =>
+            {
+                let resource_key = resource_for_command(cmd).unwrap_or_default();
+                match parts[1].parse() {
+                    Ok(id) => self.jump_to_id(&resource_key, id).await?,
+                    Err(_) => self.error_message = Some(format!("Invalid ID: {}", parts[1])),
+                }
+            }
+            _ if parts.len() > 1 => match parse_command_filters(cmd, &parts[1..]) {
+                Ok(filters) => self.navigate_with_filters(cmd, filters)?,
+                Err(e) => self.error_message = Some(e),
+            },
             _ => {
                 if get_resource(cmd).is_some() {
                     if let Some(resource) = self.current_resource() {
                         let is_sub = resource.sub_resources.iter().any(|s| s.resource_key == cmd);
                         if is_sub && self.selected_item().is_some() {
-                            self.navigate_to_sub_resource(cmd).await?;
+                            self.navigate_to_sub_resource(cmd)?;
                         } else {
-                            self.navigate_to_resource(cmd).await?;
+                            self.navigate_to_resource(cmd)?;
                         }
                     } else {
-                        self.navigate_to_resource(cmd).await?;
+                        self.navigate_to_resource(cmd)?;
                     }
                 } else {
                     self.error_message = Some(format!("Unknown command: {}", cmd));
@@ -637,3 +2923,709 @@ impl App {
         Ok(false)
     }
 }
+
+/// One call of an action and its outcome (the error message on failure)
+struct ActionCall {
+    resource_id: String,
+    /// `service.method on id`, for the action log
+    text: String,
+    result: std::result::Result<(), String>,
+}
+
+/// An action run by `run_action`, with secrets already wiped
+struct ActionDone {
+    pending: PendingAction,
+    batch: bool,
+    calls: Vec<ActionCall>,
+    /// The parent item refetched after a successful action, by resource key and ID
+    parent: Option<((String, i64), Value)>,
+}
+
+/// Invoke an action on its item, or on every marked item for a batch, then
+/// refetch the parent item it may have changed
+async fn run_action(
+    client: OneClient,
+    mut pending: PendingAction,
+    parent: Option<(String, i64)>,
+) -> ActionDone {
+    let batch = !pending.batch.is_empty();
+    let targets = if batch {
+        std::mem::take(&mut pending.batch)
+    } else {
+        vec![(pending.resource_id.clone(), serde_json::Map::new())]
+    };
+    let mut calls = Vec::new();
+    for (id, item_params) in targets {
+        let mut single = pending.clone();
+        single.resource_id = id;
+        single.extra_params.extend(item_params);
+        let text = format!(
+            "{}.{} on {}",
+            single.service, single.sdk_method, single.resource_id
+        );
+        let result = match single.params() {
+            Ok(mut params) => {
+                let result =
+                    invoke_sdk_method(&single.service, &single.sdk_method, &client, &params)
+                        .await
+                        .map(|_| ())
+                        .map_err(|e| crate::one::client::format_one_error(&e));
+                if let Value::Object(map) = &mut params {
+                    wipe_secret_params(map, &single.secrets);
+                }
+                result
+            }
+            Err(e) => Err(e.to_string()),
+        };
+        calls.push(ActionCall {
+            resource_id: single.resource_id.clone(),
+            text,
+            result,
+        });
+    }
+    pending.wipe_secrets();
+
+    let mut refetched = None;
+    let resource = parent
+        .as_ref()
+        .and_then(|(key, id)| Some((get_resource(key)?, *id)));
+    if let Some((resource, id)) = resource.filter(|_| calls.iter().any(|c| c.result.is_ok())) {
+        match fetch_detail(&client, resource, id).await {
+            Ok(item) => refetched = item,
+            Err(e) => tracing::warn!("Failed to refresh the parent item: {}", e),
+        }
+    }
+    ActionDone {
+        pending,
+        batch,
+        calls,
+        parent: parent.zip(refetched),
+    }
+}
+
+/// Full object from the resource's detail method (e.g. `one.vm.info`),
+/// which carries fields that list responses leave out
+async fn fetch_detail(
+    client: &OneClient,
+    resource: &ResourceDef,
+    id: i64,
+) -> Result<Option<Value>> {
+    let method = resource.detail_sdk_method.as_deref().unwrap_or("get");
+    let mut params = resource
+        .detail_sdk_method_params
+        .as_object()
+        .cloned()
+        .unwrap_or_default();
+    params.insert("id".to_string(), id.into());
+    let response =
+        invoke_sdk_method(&resource.service, method, client, &Value::Object(params)).await?;
+    // Detail responses wrap the item in its type, e.g. {"VM": {...}}
+    Ok(response
+        .as_object()
+        .and_then(|m| m.values().next())
+        .cloned())
+}
+
+/// Fill a `connect_command` template for `vm`
+///
+/// Placeholders: `{id}`, `{name}`, `{ip}` (first NIC address), `{host}` (current
+/// host name) and `{port}` (VNC/SPICE port). Errors name a placeholder the VM
+/// can't provide.
+pub fn connect_command(template: &str, vm: &Value) -> std::result::Result<String, String> {
+    let placeholders: [(&str, &str, Option<String>); 5] = [
+        ("{id}", "an ID", extract_json_values(vm, "ID").pop()),
+        ("{name}", "a name", extract_json_values(vm, "NAME").pop()),
+        (
+            "{ip}",
+            "a NIC with an IP",
+            nic_ips(get_json_path(vm, "TEMPLATE.NIC"))
+                .into_iter()
+                .next(),
+        ),
+        ("{host}", "a host", current_host_name(vm)),
+        (
+            "{port}",
+            "a graphics port",
+            extract_json_values(vm, "TEMPLATE.GRAPHICS.PORT").pop(),
+        ),
+    ];
+
+    let mut command = template.to_string();
+    for (placeholder, what, value) in placeholders {
+        if !command.contains(placeholder) {
+            continue;
+        }
+        let value = value.ok_or_else(|| format!("VM has no {} to connect to", what))?;
+        command = command.replace(placeholder, &shell_quote(&value));
+    }
+    Ok(command)
+}
+
+/// Quote `value` for the shell unless it's plain (IPs, host names, ports)
+fn shell_quote(value: &str) -> String {
+    if !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "._-:@/".contains(c))
+    {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+}
+
+/// Items as CSV: a header row, then each column as displayed in the table
+fn list_to_csv(items: &[Value], columns: &[ColumnDef]) -> String {
+    let mut rows = vec![columns
+        .iter()
+        .map(|col| csv_field(&col.header))
+        .collect::<Vec<_>>()
+        .join(",")];
+    rows.extend(items.iter().map(|item| {
+        columns
+            .iter()
+            .map(|col| csv_field(&format_display_value(item, col)))
+            .collect::<Vec<_>>()
+            .join(",")
+    }));
+    rows.join("\n") + "\n"
+}
+
+/// Quote a CSV field holding a separator, a quote or a line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Last line of a regex error, e.g. "unclosed group" (the full message spans
+/// several lines with a caret under the pattern)
+fn regex_error_reason(error: &regex::Error) -> String {
+    let message = error.to_string();
+    let reason = message.lines().last().unwrap_or_default();
+    reason.strip_prefix("error: ").unwrap_or(reason).to_string()
+}
+
+/// Screen rows a describe line takes when wrapped at `width` chars
+pub fn wrapped_rows(line: &str, width: usize) -> usize {
+    line.chars().count().div_ceil(width.max(1)).max(1)
+}
+
+/// Whether a command word is an item ID (`42`)
+fn is_item_id(word: &str) -> bool {
+    !word.is_empty() && word.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Resource a command names: a resource key (`one-vms`) or its short form (`vm`, `host`)
+fn resource_for_command(cmd: &str) -> Option<String> {
+    [cmd.to_string(), format!("one-{}s", cmd)]
+        .into_iter()
+        .find(|key| get_resource(key).is_some())
+}
+
+/// Match `pattern` as a case-insensitive subsequence of `candidate`
+///
+/// Returns a score (higher is better) and the char indices of the matched
+/// characters. Plain substring matches score above scattered ones; matches at
+/// the start of a `-` separated word and runs of consecutive characters add
+/// to the score.
+pub fn fuzzy_match(pattern: &str, candidate: &str) -> Option<(i64, Vec<usize>)> {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let chars: Vec<char> = candidate.to_lowercase().chars().collect();
+    if pattern.is_empty() {
+        return Some((0, Vec::new()));
+    }
+
+    let word_start = |i: usize| i == 0 || chars[i - 1] == '-';
+    let substring = (0..=chars.len().saturating_sub(pattern.len()))
+        .filter(|&start| chars[start..].starts_with(&pattern))
+        .max_by_key(|&start| (word_start(start), std::cmp::Reverse(start)));
+    let positions: Vec<usize> = match substring {
+        Some(start) => (start..start + pattern.len()).collect(),
+        None => {
+            let mut positions = Vec::with_capacity(pattern.len());
+            let mut next = 0;
+            for c in &pattern {
+                let found = (next..chars.len()).find(|&i| chars[i] == *c)?;
+                positions.push(found);
+                next = found + 1;
+            }
+            positions
+        }
+    };
+
+    let mut score = if substring.is_some() { 10 } else { 0 };
+    for (n, &i) in positions.iter().enumerate() {
+        score += 1;
+        if word_start(i) {
+            score += 3;
+        }
+        if n > 0 && positions[n - 1] + 1 == i {
+            score += 2;
+        }
+    }
+    // Shorter candidates are closer matches
+    score -= chars.len() as i64 / 8;
+    Some((score, positions))
+}
+
+/// Parse `key=value` arguments of a resource command into server-side filters
+///
+/// Keys must be parameters of the resource's list method; `state` on VMs also
+/// takes a state name (e.g. `state=running`).
+fn parse_command_filters(
+    resource_key: &str,
+    args: &[&str],
+) -> std::result::Result<Vec<ResourceFilter>, String> {
+    let resource =
+        get_resource(resource_key).ok_or_else(|| format!("Unknown command: {}", resource_key))?;
+    args.iter()
+        .map(|arg| {
+            let (key, value) = arg
+                .split_once('=')
+                .ok_or_else(|| format!("Expected key=value, got '{}'", arg))?;
+            if resource.sdk_method_params.get(key).is_none() {
+                return Err(format!("Unknown parameter '{}' for {}", key, resource_key));
+            }
+            let value = if resource.service == "vm" && key == "state" {
+                vm_state_code(value)
+                    .ok_or_else(|| format!("Unknown VM state '{}'", value))?
+                    .to_string()
+            } else {
+                value.to_string()
+            };
+            Ok(ResourceFilter::new(key, vec![value]))
+        })
+        .collect()
+}
+
+/// Parameters an action reads from the item it runs on (`item_params`)
+fn item_params(
+    action: &crate::resource::ActionDef,
+    item: &Value,
+) -> serde_json::Map<String, Value> {
+    action
+        .item_params
+        .iter()
+        .map(|(param, path)| {
+            let value = extract_json_value(item, path);
+            let value = match value.parse::<i64>() {
+                Ok(n) => Value::from(n),
+                Err(_) => Value::String(value),
+            };
+            (param.clone(), value)
+        })
+        .collect()
+}
+
+/// Line numbers of `text` containing `query`, ignoring case; none for an empty query
+fn find_matching_lines(text: &str, query: &str) -> Vec<usize> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let query = query.to_lowercase();
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| line.to_lowercase().contains(&query))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Index of the item whose `id_field` equals `id`
+fn position_by_id(items: &[Value], id_field: &str, id: &str) -> Option<usize> {
+    items
+        .iter()
+        .position(|item| extract_json_value(item, id_field) == id)
+}
+
+/// What to type to confirm a destructive action on marked items
+const BATCH_CONFIRM_TEXT: &str = "yes";
+
+/// Entries kept in the action log; older ones are dropped
+const ACTION_LOG_CAPACITY: usize = 200;
+
+/// How long a status message stays in the crumb bar
+const STATUS_MESSAGE_DURATION: std::time::Duration = std::time::Duration::from_secs(4);
+
+/// How long a fetched list is reused when navigating back to it
+const LIST_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Table page size assumed until the table is first drawn
+const DEFAULT_TABLE_VIEWPORT: usize = 10;
+
+/// Describe viewport height assumed until the view is first drawn
+const DEFAULT_DESCRIBE_VIEWPORT: usize = 20;
+
+/// Width (percent) of columns added with `:col add`
+const CUSTOM_COLUMN_WIDTH: u16 = 15;
+
+/// How often the header's connection status is re-checked
+const HEALTH_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Maximum time an external `:pipe` command may run
+const PIPE_TIMEOUT_SECS: u64 = 30;
+
+/// Run `command` through the system shell with `input` on stdin
+async fn run_pipe_command(command: &str, input: &str) -> Result<PipeOutput> {
+    use tokio::io::AsyncWriteExt;
+    use tokio::process::Command;
+
+    #[cfg(unix)]
+    let mut cmd = {
+        let mut c = Command::new("sh");
+        c.arg("-c").arg(command);
+        c
+    };
+    #[cfg(not(unix))]
+    let mut cmd = {
+        let mut c = Command::new("cmd");
+        c.arg("/C").arg(command);
+        c
+    };
+
+    let mut child = cmd
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()?;
+
+    let stdin = child.stdin.take();
+    let write_input = async move {
+        if let Some(mut stdin) = stdin {
+            // A command that ignores stdin (e.g. `head -1`) may close it early
+            let _ = stdin.write_all(input.as_bytes()).await;
+        }
+        // Dropping stdin here sends EOF
+    };
+    // Feed stdin while draining the output: a command may fill its output pipe
+    // before reading all of its input, or never read it at all
+    let output = tokio::time::timeout(std::time::Duration::from_secs(PIPE_TIMEOUT_SECS), async {
+        let ((), output) = tokio::join!(write_input, child.wait_with_output());
+        output
+    })
+    .await
+    .map_err(|_| anyhow::anyhow!("command timed out after {}s", PIPE_TIMEOUT_SECS))??;
+
+    let success = output.status.success();
+    let text = if success {
+        String::from_utf8_lossy(&output.stdout).to_string()
+    } else {
+        format!(
+            "{}\n{}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr)
+        )
+    };
+
+    Ok(PipeOutput {
+        command: command.to_string(),
+        text,
+        success,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(param: &str, text: &str) -> InputField {
+        InputField {
+            prompt: String::new(),
+            buffer: text.to_string(),
+            cursor: text.chars().count(),
+            param: param.to_string(),
+            toggle: None,
+            masked: false,
+        }
+    }
+
+    fn input(fields: Vec<InputField>) -> InputState {
+        InputState {
+            fields,
+            focused: 0,
+            confirm: false,
+            next: None,
+            pending: PendingAction {
+                service: "vm".to_string(),
+                sdk_method: "rename".to_string(),
+                action_key: "rename".to_string(),
+                resource_id: "1".to_string(),
+                summary: String::new(),
+                message: String::new(),
+                default_no: true,
+                destructive: false,
+                selected_yes: false,
+                warning: None,
+                preview: None,
+                extra_params: serde_json::Map::new(),
+                navigate_to: None,
+                confirm_text: None,
+                batch: Vec::new(),
+                secrets: Vec::new(),
+            },
+        }
+    }
+
+    /// App on a dry-run client, so no call reaches a server
+    fn test_app(items: Vec<Value>) -> App {
+        let options = crate::one::client::HttpOptions {
+            timeout: std::time::Duration::from_secs(1),
+            ca_cert: None,
+            insecure: false,
+        };
+        let credentials = crate::one::auth::OneCredentials::from_login("u".into(), "p".into());
+        let client = OneClient::from_credentials(credentials, &options).unwrap();
+        App::from_initialized(client.dry_run(), items, Config::default(), false)
+    }
+
+    /// Land a finished detail fetch returning `detail`
+    async fn land_detail(app: &mut App, detail: Value) {
+        app.cancel_detail_fetch();
+        let task = tokio::spawn(async move { Ok(Some(detail)) });
+        while !task.is_finished() {
+            tokio::task::yield_now().await;
+        }
+        app.detail_task = Some(task);
+        app.poll_detail_fetch().await;
+    }
+
+    #[tokio::test]
+    async fn test_describe_scroll_restored_after_detail_lands() {
+        let item = serde_json::json!({"ID": "7", "NAME": "web"});
+        let detail = serde_json::json!({
+            "ID": "7",
+            "NAME": "web",
+            "TEMPLATE": {"CPU": "1", "MEMORY": "512", "DISK": {"SIZE": "1024"}}
+        });
+        let mut app = test_app(vec![item]);
+        app.current_resource_key = "one-vms".to_string();
+        app.items_resource_key = "one-vms".to_string();
+
+        app.enter_describe_mode();
+        land_detail(&mut app, detail.clone()).await;
+        app.describe_scroll = 3;
+        app.exit_mode();
+
+        // The list item shows first, with fewer lines than the detail
+        app.enter_describe_mode();
+        assert_eq!(app.describe_scroll, 0);
+        land_detail(&mut app, detail).await;
+        assert_eq!(app.describe_scroll, 3);
+    }
+
+    #[tokio::test]
+    async fn test_jump_to_overflowing_id_is_rejected() {
+        let mut app = test_app(Vec::new());
+        app.command_text = "99999999999999999999".to_string();
+        app.execute_command().await.unwrap();
+        assert_eq!(
+            app.error_message.as_deref(),
+            Some("Invalid ID: 99999999999999999999")
+        );
+        assert_ne!(app.mode, Mode::Describe);
+    }
+
+    #[tokio::test]
+    async fn test_typed_confirm_times_out() {
+        let mut app = test_app(Vec::new());
+        app.config.confirm_timeout_secs = Some(30);
+        let mut pending = input(Vec::new()).pending;
+        pending.confirm_text = Some("web".to_string());
+        app.pending_action = Some(pending);
+        app.enter_typed_confirm();
+        assert_eq!(app.mode, Mode::Input);
+
+        app.check_confirm_timeout();
+        assert_eq!(app.mode, Mode::Input);
+        let idle = std::time::Instant::now() - std::time::Duration::from_secs(31);
+        app.confirm_last_activity = Some(idle);
+        app.touch_confirm();
+        app.check_confirm_timeout();
+        assert_eq!(app.mode, Mode::Input);
+
+        app.confirm_last_activity = Some(idle);
+        app.check_confirm_timeout();
+        assert_eq!(app.mode, Mode::Normal);
+        assert!(app.input.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_action_runs_in_background() {
+        let mut app = test_app(Vec::new());
+        let mut pending = input(Vec::new()).pending;
+        pending.summary = "Rename web".to_string();
+        pending
+            .extra_params
+            .insert("name".to_string(), Value::from("web-2"));
+        app.start_action(pending);
+        assert!(app.is_loading());
+        assert!(app.last_action.is_none());
+
+        while !app.action_task.as_ref().unwrap().is_finished() {
+            tokio::task::yield_now().await;
+        }
+        app.poll_action().await;
+        assert!(app.action_task.is_none());
+        let (status, _) = app.status_message.clone().unwrap();
+        assert_eq!(status, "Rename web: done");
+        assert_eq!(
+            app.action_log.back().unwrap().text,
+            "invoked vm.rename on 1 → ok"
+        );
+        assert!(app.last_action.is_some());
+    }
+
+    #[test]
+    fn test_input_editing_multibyte() {
+        let mut field = field("name", "café");
+        field.backspace();
+        assert_eq!(field.buffer, "caf");
+        field.insert('é');
+        field.move_home();
+        field.delete();
+        field.insert('C');
+        assert_eq!(field.buffer, "Café");
+        field.move_left();
+        field.move_right();
+        field.insert('!');
+        assert_eq!(field.buffer, "C!afé");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_pipe_command_larger_than_pipe_buffer() {
+        // cat blocks on its full stdout until the output is read
+        let input = "x".repeat(1 << 20);
+        let output = run_pipe_command("cat", &input).await.unwrap();
+        assert!(output.success);
+        assert_eq!(output.text.len(), input.len());
+    }
+
+    #[test]
+    fn test_wipe_secrets_keeps_other_params() {
+        let mut pending = input(Vec::new()).pending;
+        pending
+            .extra_params
+            .insert("name".to_string(), Value::from("alice"));
+        pending
+            .extra_params
+            .insert("password".to_string(), Value::from(" s3cret "));
+        pending.secrets = vec!["password".to_string()];
+        pending.wipe_secrets();
+        assert!(!pending.extra_params.contains_key("password"));
+        assert_eq!(pending.extra_params["name"], "alice");
+    }
+
+    #[test]
+    fn test_jump_command_parsing() {
+        assert!(is_item_id("42"));
+        assert!(!is_item_id("4x2"));
+        assert!(!is_item_id(""));
+        assert_eq!(resource_for_command("vm").as_deref(), Some("one-vms"));
+        assert_eq!(
+            resource_for_command("one-hosts").as_deref(),
+            Some("one-hosts")
+        );
+        assert_eq!(resource_for_command("nothing"), None);
+    }
+
+    #[test]
+    fn test_find_matching_lines_ignores_case() {
+        let text = "{\n  \"NAME\": \"web\",\n  \"STATE\": \"3\",\n  \"web_port\": 80\n}";
+        assert_eq!(find_matching_lines(text, "WEB"), vec![1, 3]);
+        assert!(find_matching_lines(text, "").is_empty());
+    }
+
+    #[test]
+    fn test_position_by_id_after_reorder() {
+        let items = vec![
+            serde_json::json!({"ID": "12"}),
+            serde_json::json!({"ID": "7"}),
+            serde_json::json!({"ID": "3"}),
+        ];
+        assert_eq!(position_by_id(&items, "ID", "7"), Some(1));
+        assert_eq!(position_by_id(&items, "ID", "99"), None);
+    }
+
+    #[test]
+    fn test_wrapped_rows() {
+        assert_eq!(wrapped_rows("", 10), 1);
+        assert_eq!(wrapped_rows("0123456789", 10), 1);
+        assert_eq!(wrapped_rows("0123456789a", 10), 2);
+        assert_eq!(wrapped_rows("ééééé", 2), 3);
+    }
+
+    #[test]
+    fn test_fuzzy_match() {
+        let (_, positions) = fuzzy_match("ovms", "one-vms").unwrap();
+        assert_eq!(positions, vec![0, 4, 5, 6]);
+        assert!(fuzzy_match("xyz", "one-vms").is_none());
+        assert!(fuzzy_match("smv", "one-vms").is_none());
+
+        // A substring beats a scattered match, and word starts beat mid-word hits
+        let score = |pattern, candidate| fuzzy_match(pattern, candidate).unwrap().0;
+        assert!(score("temp", "one-templates") > score("tmpl", "one-templates"));
+        assert!(score("host", "one-hosts") > score("host", "one-vhosts"));
+        assert_eq!(fuzzy_match("VM", "one-vms").unwrap().1, vec![4, 5]);
+    }
+
+    #[test]
+    fn test_connect_command() {
+        let vm = serde_json::json!({
+            "ID": "7",
+            "NAME": "web 1",
+            "TEMPLATE": {
+                "NIC": [{"IP": "10.0.0.5"}, {"IP": "10.0.1.5"}],
+                "GRAPHICS": {"TYPE": "VNC", "PORT": "5907"}
+            },
+            "HISTORY_RECORDS": {"HISTORY": {"HOSTNAME": "node1"}}
+        });
+        assert_eq!(
+            connect_command("ssh root@{ip}", &vm).as_deref(),
+            Ok("ssh root@10.0.0.5")
+        );
+        assert_eq!(
+            connect_command("vncviewer {host}:{port} # {name}", &vm).as_deref(),
+            Ok("vncviewer node1:5907 # 'web 1'")
+        );
+        assert!(connect_command("ssh {ip}", &serde_json::json!({"ID": "7"})).is_err());
+    }
+
+    #[test]
+    fn test_list_to_csv() {
+        let vms = get_resource("one-vms").unwrap();
+        let columns: Vec<ColumnDef> = vms.columns.iter().take(2).cloned().collect();
+        let items = vec![
+            serde_json::json!({"ID": "1", "NAME": "web"}),
+            serde_json::json!({"ID": "2", "NAME": "db, \"primary\""}),
+        ];
+        assert_eq!(
+            list_to_csv(&items, &columns),
+            "ID,NAME\n1,web\n2,\"db, \"\"primary\"\"\"\n"
+        );
+    }
+
+    #[test]
+    fn test_regex_error_reason() {
+        let pattern = String::from("vm-(prod");
+        let error = regex::Regex::new(&pattern).unwrap_err();
+        assert_eq!(regex_error_reason(&error), "unclosed group");
+    }
+
+    #[test]
+    fn test_input_focus_wraps() {
+        let mut state = input(vec![
+            field("cpu", "1"),
+            field("vcpu", ""),
+            field("memory", "512"),
+        ]);
+        state.focus_previous();
+        assert_eq!(state.focused, 2);
+        state.focus_next();
+        state.focus_next();
+        assert_eq!(state.current_mut().map(|f| f.param.as_str()), Some("vcpu"));
+    }
+}
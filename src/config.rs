@@ -0,0 +1,436 @@
+//! Configuration
+//!
+//! User settings persisted as JSON in the tone config directory
+//! (`~/.config/tone/config.json` on Linux). Missing or invalid files fall
+//! back to defaults so a broken config never prevents startup; an invalid
+//! file is left as is rather than overwritten with those defaults.
+
+use crate::resource::get_resource;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Config file name inside the config directory
+const CONFIG_FILE: &str = "config.json";
+
+/// Command history file name inside the config directory
+const HISTORY_FILE: &str = "history";
+
+/// Number of `:` commands kept in the history file
+pub const HISTORY_LIMIT: usize = 100;
+
+/// Request timeout when `request_timeout_secs` is unset
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Session token lifetime when `session_token_secs` is unset
+const DEFAULT_SESSION_TOKEN: Duration = Duration::from_secs(10 * 60 * 60);
+
+/// Resource shown on startup when none is configured
+const DEFAULT_RESOURCE: &str = "one-vms";
+
+/// Shortest auto-refresh interval, so a typo like `1ms` can't flood the server
+pub const MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Console command when `connect_command` is unset
+const DEFAULT_CONNECT_COMMAND: &str = "ssh {ip}";
+
+/// User configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Auto-cancel an open confirmation dialog after this many seconds of
+    /// inactivity (disabled when unset)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirm_timeout_secs: Option<u64>,
+
+    /// Give up on an OpenNebula request after this many seconds (default 30)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_timeout_secs: Option<u64>,
+
+    /// Lifetime of the session token requested at login; `0` keeps sending
+    /// the password with every call (default 10 hours)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_token_secs: Option<u64>,
+
+    /// PEM root certificate to trust for the endpoint (`--ca-cert` overrides it)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ca_cert: Option<PathBuf>,
+
+    /// Resource shown on startup when there's no last resource (defaults to `one-vms`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_resource: Option<String>,
+
+    /// Resource viewed when tone last exited, reopened on startup
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_resource: Option<String>,
+
+    /// `/` filter active when tone last exited, restored with `last_resource`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_filter: Option<String>,
+
+    /// Tint whole table rows by their state color instead of only the state cell
+    pub row_state_colors: bool,
+
+    /// Treat every `/` filter as a regex, not only those starting with `~`
+    pub regex_filter: bool,
+
+    /// Reload the current view this often, e.g. `"10s"` (disabled when unset)
+    #[serde(skip_serializing_if = "Option::is_none", with = "optional_duration")]
+    pub refresh_interval: Option<Duration>,
+
+    /// XML-RPC endpoint of the last successful connection, used when neither
+    /// `--endpoint` nor ONE_XMLRPC is given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+
+    /// User of the last successful connection (informational)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+
+    /// Normal-mode key overrides by action, e.g. `{"quit": "x"}`
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub keybindings: HashMap<String, String>,
+
+    /// Command run by `c` on a VM, e.g. `"vncviewer {host}:{port}"` or
+    /// `"ssh root@{ip}"` (defaults to `"ssh {ip}"`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connect_command: Option<String>,
+
+    /// Color overrides; unset entries keep the built-in look
+    #[serde(skip_serializing_if = "ThemeConfig::is_empty")]
+    pub theme: ThemeConfig,
+
+    /// The file exists but couldn't be read or parsed: saving these defaults
+    /// over it would lose the user's settings
+    #[serde(skip)]
+    unreadable: bool,
+}
+
+/// `theme` section of the config: colors as names (`"light-blue"`), hex
+/// codes (`"#1e90ff"`) or ANSI indexes (`"33"`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThemeConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub header: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accent: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub border: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selection_bg: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selection_fg: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub highlight: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub success: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub muted: Option<String>,
+}
+
+impl ThemeConfig {
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl Config {
+    /// Load the config file, falling back to defaults
+    pub fn load() -> Self {
+        let path = Self::path();
+        if !path.exists() {
+            return Self::default();
+        }
+
+        match std::fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                tracing::warn!("Invalid config file {:?}: {}", path, e);
+                Self::unreadable()
+            }),
+            Err(e) => {
+                tracing::warn!("Failed to read config file {:?}: {}", path, e);
+                Self::unreadable()
+            }
+        }
+    }
+
+    /// Defaults standing in for a broken config file, which `save` leaves alone
+    fn unreadable() -> Self {
+        Self {
+            unreadable: true,
+            ..Self::default()
+        }
+    }
+
+    /// Write the config file, creating the config directory if needed.
+    /// Refuses to overwrite a file that failed to load.
+    pub fn save(&self) -> anyhow::Result<()> {
+        let path = Self::path();
+        if self.unreadable {
+            anyhow::bail!("{:?} is invalid; fix it to save settings again", path);
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Endpoint to connect to: `--endpoint`, else the saved one unless ONE_XMLRPC
+    /// is set (None lets the client read ONE_XMLRPC or use its default)
+    pub fn effective_endpoint(&self, cli_endpoint: Option<&str>) -> Option<String> {
+        if let Some(endpoint) = cli_endpoint {
+            return Some(endpoint.to_string());
+        }
+        if std::env::var_os("ONE_XMLRPC").is_some() {
+            return None;
+        }
+        self.endpoint.clone()
+    }
+
+    /// Timeout for each OpenNebula request
+    pub fn request_timeout(&self) -> Duration {
+        self.request_timeout_secs
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_REQUEST_TIMEOUT)
+    }
+
+    /// Lifetime of the login session token, None when token auth is disabled
+    pub fn session_token_lifetime(&self) -> Option<Duration> {
+        match self.session_token_secs {
+            Some(0) => None,
+            Some(secs) => Some(Duration::from_secs(secs)),
+            None => Some(DEFAULT_SESSION_TOKEN),
+        }
+    }
+
+    /// Template of the command connecting to a VM's console
+    pub fn connect_command(&self) -> &str {
+        self.connect_command
+            .as_deref()
+            .unwrap_or(DEFAULT_CONNECT_COMMAND)
+    }
+
+    /// Record the endpoint and user of a successful connection; true if they changed
+    pub fn remember_connection(&mut self, endpoint: &str, username: &str) -> bool {
+        let changed = self.endpoint.as_deref() != Some(endpoint)
+            || self.username.as_deref() != Some(username);
+        self.endpoint = Some(endpoint.to_string());
+        self.username = Some(username.to_string());
+        changed
+    }
+
+    /// Resource to open on startup: the last one viewed, else the configured
+    /// default, ignoring unknown keys and sub-resources (they need a parent item)
+    pub fn initial_resource(&self) -> &str {
+        if let Some(key) = self.last_resource.as_deref() {
+            if is_top_level(key) {
+                return key;
+            }
+        }
+        match self.default_resource.as_deref() {
+            Some(key) if is_top_level(key) => key,
+            Some(key) => {
+                tracing::warn!("Unknown default_resource '{}' in config", key);
+                DEFAULT_RESOURCE
+            }
+            None => DEFAULT_RESOURCE,
+        }
+    }
+
+    /// Remember the view to reopen on the next start; sub-resources aren't kept
+    pub fn remember_session(&mut self, resource_key: &str, filter: Option<String>) {
+        if is_top_level(resource_key) {
+            self.last_resource = Some(resource_key.to_string());
+            self.last_filter = filter;
+        } else {
+            self.last_resource = None;
+            self.last_filter = None;
+        }
+    }
+
+    /// Path of the config file
+    pub fn path() -> PathBuf {
+        config_dir().join(CONFIG_FILE)
+    }
+}
+
+/// Parse a duration like `500ms`, `5s`, `2m` or `1h`; a bare number is seconds
+pub fn parse_duration(text: &str) -> Option<Duration> {
+    let text = text.trim();
+    let split = text
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let number: u64 = number.parse().ok()?;
+    match unit {
+        "ms" => Some(Duration::from_millis(number)),
+        "" | "s" => Some(Duration::from_secs(number)),
+        "m" => number.checked_mul(60).map(Duration::from_secs),
+        "h" => number.checked_mul(3600).map(Duration::from_secs),
+        _ => None,
+    }
+}
+
+/// Parse an auto-refresh interval: a `parse_duration` value no shorter than
+/// `MIN_REFRESH_INTERVAL`
+pub fn parse_refresh_interval(text: &str) -> Result<Duration, String> {
+    match parse_duration(text) {
+        Some(interval) if interval >= MIN_REFRESH_INTERVAL => Ok(interval),
+        Some(_) => Err(format!(
+            "'{}' is shorter than the {} minimum",
+            text.trim(),
+            format_duration(MIN_REFRESH_INTERVAL)
+        )),
+        None => Err(format!(
+            "'{}' is not a duration (use e.g. 5s, 1m)",
+            text.trim()
+        )),
+    }
+}
+
+/// Whether a resource can be opened on its own, without a parent item
+fn is_top_level(resource_key: &str) -> bool {
+    get_resource(resource_key).is_some_and(|resource| resource.is_global)
+}
+
+/// Format a duration the way `parse_duration` reads it
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if duration.subsec_millis() != 0 || secs == 0 {
+        format!("{}ms", duration.as_millis())
+    } else if secs.is_multiple_of(3600) {
+        format!("{}h", secs / 3600)
+    } else if secs.is_multiple_of(60) {
+        format!("{}m", secs / 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+/// (De)serialize an optional duration as a `"10s"`-style string
+mod optional_duration {
+    use super::{format_duration, parse_refresh_interval};
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(value: &Option<Duration>, s: S) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(duration) => s.serialize_str(&format_duration(*duration)),
+            None => s.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Option<Duration>, D::Error> {
+        let text = Option::<String>::deserialize(d)?;
+        text.map(|text| parse_refresh_interval(&text).map_err(serde::de::Error::custom))
+            .transpose()
+    }
+}
+
+/// Previously executed `:` commands, oldest first (empty when unreadable)
+pub fn load_history() -> Vec<String> {
+    match std::fs::read_to_string(config_dir().join(HISTORY_FILE)) {
+        Ok(content) => content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(str::to_string)
+            .collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Write the last `HISTORY_LIMIT` commands to the history file
+pub fn save_history(history: &[String]) -> anyhow::Result<()> {
+    let dir = config_dir();
+    std::fs::create_dir_all(&dir)?;
+    let start = history.len().saturating_sub(HISTORY_LIMIT);
+    let mut content = history[start..].join("\n");
+    content.push('\n');
+    std::fs::write(dir.join(HISTORY_FILE), content)?;
+    Ok(())
+}
+
+/// Directory holding tone's config, logs and other local state
+pub fn config_dir() -> PathBuf {
+    if let Some(config_dir) = dirs::config_dir() {
+        return config_dir.join("tone");
+    }
+    if let Some(home) = dirs::home_dir() {
+        return home.join(".tone");
+    }
+    PathBuf::from(".")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cli_endpoint_wins() {
+        let config = Config {
+            endpoint: Some("https://saved:2633/RPC2".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            config.effective_endpoint(Some("https://cli:2633/RPC2")),
+            Some("https://cli:2633/RPC2".to_string())
+        );
+    }
+
+    #[test]
+    fn test_sub_resources_are_not_restored() {
+        let mut config = Config {
+            last_resource: Some("one-vm-nics".to_string()),
+            ..Config::default()
+        };
+        assert_eq!(config.initial_resource(), DEFAULT_RESOURCE);
+        config.remember_session("one-vm-nics", Some("eth0".to_string()));
+        assert_eq!(config.last_resource, None);
+        config.remember_session("one-hosts", Some("kvm".to_string()));
+        assert_eq!(config.initial_resource(), "one-hosts");
+        assert_eq!(config.last_filter.as_deref(), Some("kvm"));
+    }
+
+    #[test]
+    fn test_unreadable_config_is_not_overwritten() {
+        let mut config = Config::unreadable();
+        config.last_resource = Some("one-hosts".to_string());
+        assert!(config.save().is_err());
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("5s"), Some(Duration::from_secs(5)));
+        assert_eq!(parse_duration("500ms"), Some(Duration::from_millis(500)));
+        assert_eq!(parse_duration("2m"), Some(Duration::from_secs(120)));
+        assert_eq!(parse_duration("10"), Some(Duration::from_secs(10)));
+        assert_eq!(parse_duration("fast"), None);
+        assert_eq!(parse_duration("999999999999999999h"), None);
+        assert!(parse_refresh_interval("1ms").is_err());
+        assert!(parse_refresh_interval("999999999999999999h").is_err());
+        assert_eq!(parse_refresh_interval("1s"), Ok(MIN_REFRESH_INTERVAL));
+        assert_eq!(format_duration(Duration::from_secs(120)), "2m");
+    }
+
+    #[test]
+    fn test_refresh_interval_round_trip() {
+        let config: Config = serde_json::from_str(r#"{"refresh_interval": "30s"}"#).unwrap();
+        assert_eq!(config.refresh_interval, Some(Duration::from_secs(30)));
+        let json = serde_json::to_string(&config).unwrap();
+        assert!(json.contains(r#""refresh_interval":"30s""#));
+    }
+
+    #[test]
+    fn test_remember_connection() {
+        let mut config = Config::default();
+        assert!(config.remember_connection("https://one:2633/RPC2", "oneadmin"));
+        assert!(!config.remember_connection("https://one:2633/RPC2", "oneadmin"));
+        assert!(config.remember_connection("https://one:2633/RPC2", "alice"));
+        assert_eq!(config.username.as_deref(), Some("alice"));
+    }
+}
@@ -2,35 +2,96 @@
 //!
 //! Handles keyboard input and user events.
 
-use crate::app::{App, Mode};
-use crate::resource::{extract_json_value, invoke_sdk_method};
+use crate::app::{App, Mode, PendingAction, READ_WRITE_SERVICE};
+use crate::keymap::KeyAction;
+use crate::resource::{current_host_id, extract_json_value, ActionDef};
 use anyhow::Result;
-use crossterm::event::{poll, read, Event, KeyCode, KeyModifiers};
+use crossterm::event::{
+    poll, read, Event, KeyCode, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
 use std::time::Duration;
 
+/// Resource ID given to actions that create a resource; the SDK call ignores it
+const NEW_RESOURCE_ID: &str = "-1";
+
 /// Handle events and return true if the application should quit
 pub async fn handle_events(app: &mut App) -> Result<bool> {
     if poll(Duration::from_millis(100))? {
-        if let Event::Key(key) = read()? {
-            return handle_key(app, key.code, key.modifiers).await;
+        match read()? {
+            Event::Key(key) => return handle_key(app, key.code, key.modifiers).await,
+            Event::Mouse(mouse) => handle_mouse(app, mouse).await,
+            _ => {}
         }
     }
     Ok(false)
 }
 
+/// Lines the describe view moves per wheel notch
+const WHEEL_SCROLL_LINES: isize = 3;
+
+/// Two clicks on the same row within this window describe it
+const DOUBLE_CLICK_WINDOW: Duration = Duration::from_millis(400);
+
+/// Wheel scrolling and row clicks; ignored while a dialog or prompt is open
+async fn handle_mouse(app: &mut App, mouse: MouseEvent) {
+    match (&app.mode, mouse.kind) {
+        (Mode::Normal, MouseEventKind::ScrollDown) => app.next(),
+        (Mode::Normal, MouseEventKind::ScrollUp) => app.previous(),
+        (Mode::Normal, MouseEventKind::Down(MouseButton::Left)) => {
+            let Some(index) = app.item_at(mouse.column, mouse.row) else {
+                app.last_click = None;
+                return;
+            };
+            app.selected = index;
+
+            let now = std::time::Instant::now();
+            if let Some((prev_index, prev_time)) = app.last_click {
+                if prev_index == index && now.duration_since(prev_time) < DOUBLE_CLICK_WINDOW {
+                    app.last_click = None;
+                    app.enter_describe_mode();
+                    return;
+                }
+            }
+            app.last_click = Some((index, now));
+        }
+        (Mode::Describe | Mode::Log, MouseEventKind::ScrollDown) => {
+            app.scroll_describe(WHEEL_SCROLL_LINES)
+        }
+        (Mode::Describe | Mode::Log, MouseEventKind::ScrollUp) => {
+            app.scroll_describe(-WHEEL_SCROLL_LINES)
+        }
+        _ => {}
+    }
+}
+
 async fn handle_key(app: &mut App, code: KeyCode, modifiers: KeyModifiers) -> Result<bool> {
     // Handle Ctrl+C globally
     if code == KeyCode::Char('c') && modifiers.contains(KeyModifiers::CONTROL) {
         return Ok(true);
     }
 
+    // Esc in the list cancels a running fetch before anything else
+    if code == KeyCode::Esc && app.mode == Mode::Normal && app.fetch_in_flight() {
+        app.cancel_fetch();
+        return Ok(false);
+    }
+
     match app.mode {
         Mode::Normal => handle_normal_mode(app, code, modifiers).await,
         Mode::Command => handle_command_mode(app, code, modifiers).await,
         Mode::Help => handle_help_mode(app, code),
-        Mode::Confirm => handle_confirm_mode(app, code, modifiers).await,
+        Mode::Confirm => {
+            app.touch_confirm();
+            handle_confirm_mode(app, code, modifiers).await
+        }
         Mode::Warning => handle_warning_mode(app, code),
-        Mode::Describe => handle_describe_mode(app, code, modifiers),
+        Mode::Describe => handle_describe_mode(app, code, modifiers).await,
+        Mode::Select => handle_select_mode(app, code).await,
+        Mode::Input => {
+            app.touch_confirm();
+            handle_input_mode(app, code, modifiers).await
+        }
+        Mode::Log => handle_log_mode(app, code, modifiers),
     }
 }
 
@@ -52,73 +113,103 @@ async fn handle_normal_mode(app: &mut App, code: KeyCode, modifiers: KeyModifier
     // Reset key tracking for other keys
     app.last_key_press = None;
 
-    match code {
-        // Quit
-        KeyCode::Char('q') => return Ok(true),
+    // Rebindable core actions
+    if let Some(action) = app.keymap.action_for(code, modifiers) {
+        match action {
+            KeyAction::Quit => return Ok(true),
+            KeyAction::Filter => app.filter_active = true,
+            KeyAction::Describe => app.enter_describe_mode(),
+            KeyAction::Command => app.enter_command_mode(),
+            KeyAction::Refresh => app.refresh_current()?,
+            KeyAction::Back => app.navigate_back()?,
+        }
+        handle_filter_input(app, code);
+        return Ok(false);
+    }
 
+    match code {
         // Navigation
         KeyCode::Char('j') | KeyCode::Down => app.next(),
         KeyCode::Char('k') | KeyCode::Up => app.previous(),
         KeyCode::Char('G') => app.go_to_bottom(),
         KeyCode::PageDown | KeyCode::Char('f') if modifiers.contains(KeyModifiers::CONTROL) => {
-            app.page_down(10);
+            app.page_down();
         }
         KeyCode::PageUp | KeyCode::Char('b') if modifiers.contains(KeyModifiers::CONTROL) => {
-            app.page_up(10);
+            app.page_up();
         }
 
         // Filter
-        KeyCode::Char('/') => {
-            app.filter_active = true;
-        }
         KeyCode::Esc if app.filter_active => {
             app.clear_filter();
         }
+        KeyCode::Esc if !app.selected_set.is_empty() => {
+            app.selected_set.clear();
+        }
 
         // Handle Ctrl+D for destructive actions (must come before 'd' for describe)
         KeyCode::Char('d') if modifiers.contains(KeyModifiers::CONTROL) => {
             if let Some(resource) = app.current_resource() {
                 for action in &resource.actions {
                     if action.shortcut.as_deref() == Some("ctrl+d") {
-                        if app.readonly {
-                            app.show_warning("Read-only mode: actions are disabled");
-                            return Ok(false);
-                        }
-                        if let Some(item) = app.selected_item() {
-                            let resource_id = extract_json_value(item, &resource.id_field);
-                            if let Some(pending) = app.create_pending_action(action, &resource_id) {
-                                app.enter_confirm_mode(pending);
-                            }
-                        }
+                        trigger_action(app, action).await;
                         return Ok(false);
                     }
                 }
             }
         }
 
-        // Describe / Details
-        KeyCode::Enter | KeyCode::Char('d') => {
-            app.enter_describe_mode().await;
+        // Help
+        KeyCode::Char('?') => {
+            app.enter_help_mode();
         }
 
-        // Command mode
-        KeyCode::Char(':') => {
-            app.enter_command_mode();
+        // Action log
+        KeyCode::Char('E') => {
+            app.enter_log_mode();
         }
 
-        // Help
-        KeyCode::Char('?') => {
-            app.enter_help_mode();
+        // Mark for batch actions
+        KeyCode::Char(' ') if !app.filter_active => {
+            app.toggle_selected_mark();
         }
 
-        // Refresh
-        KeyCode::Char('R') => {
-            app.refresh_current().await?;
+        // Horizontal scrolling
+        KeyCode::Right => app.scroll_columns_right(),
+        KeyCode::Left => app.scroll_columns_left(),
+
+        // Sorting
+        KeyCode::Char('>') => app.cycle_sort_column(),
+        KeyCode::Char('<') => app.toggle_sort_direction(),
+
+        // Repeat last action
+        KeyCode::Char('.') if !app.filter_active => {
+            if app.readonly {
+                app.show_warning("Read-only mode: actions are disabled");
+                return Ok(false);
+            }
+            if let Some((pending, reused_choices)) = app.create_repeat_action().await {
+                // Only a plain repeat of a harmless action runs without asking
+                if pending.destructive || pending.warning.is_some() || reused_choices {
+                    app.enter_confirm_mode(pending);
+                } else {
+                    app.pending_action = Some(pending);
+                    execute_pending_action(app);
+                }
+            }
         }
 
-        // Back navigation
-        KeyCode::Char('b') | KeyCode::Backspace => {
-            app.navigate_back().await?;
+        // Open a console on the selected VM
+        KeyCode::Char('c') if app.current_resource_key == "one-vms" && !app.filter_active => {
+            app.connect_selected();
+        }
+
+        // Pagination
+        KeyCode::Char(']') => {
+            app.next_page()?;
+        }
+        KeyCode::Char('[') => {
+            app.prev_page()?;
         }
 
         // Handle sub-resource shortcuts
@@ -126,7 +217,7 @@ async fn handle_normal_mode(app: &mut App, code: KeyCode, modifiers: KeyModifier
             if let Some(resource) = app.current_resource() {
                 for sub in &resource.sub_resources {
                     if sub.shortcut == c.to_string() && app.selected_item().is_some() {
-                        app.navigate_to_sub_resource(&sub.resource_key).await?;
+                        app.navigate_to_sub_resource(&sub.resource_key)?;
                         return Ok(false);
                     }
                 }
@@ -134,16 +225,7 @@ async fn handle_normal_mode(app: &mut App, code: KeyCode, modifiers: KeyModifier
                 // Handle action shortcuts
                 for action in &resource.actions {
                     if action.shortcut.as_deref() == Some(&c.to_string()) {
-                        if app.readonly && action.sdk_method != "get" {
-                            app.show_warning("Read-only mode: actions are disabled");
-                            return Ok(false);
-                        }
-                        if let Some(item) = app.selected_item() {
-                            let resource_id = extract_json_value(item, &resource.id_field);
-                            if let Some(pending) = app.create_pending_action(action, &resource_id) {
-                                app.enter_confirm_mode(pending);
-                            }
-                        }
+                        trigger_action(app, action).await;
                         return Ok(false);
                     }
                 }
@@ -153,39 +235,121 @@ async fn handle_normal_mode(app: &mut App, code: KeyCode, modifiers: KeyModifier
         _ => {}
     }
 
-    // Handle filter input
-    if app.filter_active {
-        match code {
-            KeyCode::Char(c) => {
-                app.filter_text.push(c);
-                app.apply_filter();
-            }
-            KeyCode::Backspace => {
-                app.filter_text.pop();
-                app.apply_filter();
-            }
-            KeyCode::Enter => {
-                app.filter_active = false;
-            }
-            _ => {}
+    handle_filter_input(app, code);
+    Ok(false)
+}
+
+/// Edit the `/` filter while it's being typed
+fn handle_filter_input(app: &mut App, code: KeyCode) {
+    if !app.filter_active {
+        return;
+    }
+    match code {
+        KeyCode::Char(c) => {
+            app.filter_text.push(c);
+            app.apply_filter();
+        }
+        KeyCode::Backspace => {
+            app.filter_text.pop();
+            app.apply_filter();
         }
+        KeyCode::Enter => {
+            app.filter_active = false;
+        }
+        _ => {}
     }
+}
 
-    Ok(false)
+/// Start a registry action on the selected item (state checks, pickers, confirm)
+async fn trigger_action(app: &mut App, action: &'static ActionDef) {
+    if app.readonly && action.sdk_method != "get" {
+        app.show_warning("Read-only mode: actions are disabled");
+        return;
+    }
+    if action.creates {
+        // Nothing to select for a new resource: it is described by the typed values
+        if let Some(pending) = app.create_pending_action(action, NEW_RESOURCE_ID).await {
+            open_action(app, action, pending, None, Vec::new()).await;
+        }
+        return;
+    }
+    let Some(resource) = app.current_resource() else {
+        return;
+    };
+    let Some(item) = app.selected_item() else {
+        return;
+    };
+    // With marked items, blocked states are filtered per item instead
+    if app.selected_set.is_empty() {
+        if let Some(warning) = action.check_state(item) {
+            app.show_warning(&warning);
+            return;
+        }
+    }
+
+    let resource_id = extract_json_value(item, &resource.id_field);
+    let exclude = match &action.select {
+        Some(select) if select.exclude_current_host => current_host_id(item),
+        _ => None,
+    };
+    let initial: Vec<String> = action
+        .input
+        .iter()
+        .flat_map(|input| &input.fields)
+        .map(|field| {
+            field
+                .initial_field
+                .as_ref()
+                .map(|path| extract_json_value(item, path))
+                .filter(|value| value != "-")
+                .unwrap_or_default()
+        })
+        .collect();
+    let Some(pending) = app.create_pending_action(action, &resource_id).await else {
+        return;
+    };
+    open_action(app, action, pending, exclude, initial).await;
+}
+
+/// Show the picker, prompt or confirm dialog that comes first for an action
+async fn open_action(
+    app: &mut App,
+    action: &ActionDef,
+    pending: PendingAction,
+    exclude: Option<String>,
+    initial: Vec<String>,
+) {
+    if let Some(ref select) = action.select {
+        app.enter_select(pending, select, exclude).await;
+    } else if let Some(ref input) = action.input {
+        app.enter_input(pending, input, initial);
+    } else {
+        app.enter_confirm_mode(pending);
+    }
 }
 
 async fn handle_command_mode(
     app: &mut App,
     code: KeyCode,
-    _modifiers: KeyModifiers,
+    modifiers: KeyModifiers,
 ) -> Result<bool> {
+    if modifiers.contains(KeyModifiers::CONTROL) {
+        match code {
+            KeyCode::Char('p') => app.history_prev(),
+            KeyCode::Char('n') => app.history_next(),
+            _ => {}
+        }
+        return Ok(false);
+    }
     match code {
         KeyCode::Esc => {
-            app.exit_mode();
+            app.exit_command_mode();
         }
         KeyCode::Enter => {
             let should_quit = app.execute_command().await?;
-            app.exit_mode();
+            if app.mode == Mode::Command {
+                app.exit_command_mode();
+            }
             return Ok(should_quit);
         }
         KeyCode::Char(c) => {
@@ -235,8 +399,7 @@ async fn handle_confirm_mode(
             }
         }
         KeyCode::Char('y') | KeyCode::Char('Y') => {
-            execute_pending_action(app).await?;
-            app.exit_mode();
+            confirm_pending_action(app).await?;
         }
         KeyCode::Enter => {
             if app
@@ -245,15 +408,31 @@ async fn handle_confirm_mode(
                 .map(|p| p.selected_yes)
                 .unwrap_or(false)
             {
-                execute_pending_action(app).await?;
+                confirm_pending_action(app).await?;
+            } else {
+                app.exit_mode();
             }
-            app.exit_mode();
         }
         _ => {}
     }
     Ok(false)
 }
 
+/// Run the confirmed action, or ask for the typed confirmation first
+async fn confirm_pending_action(app: &mut App) -> Result<()> {
+    if app
+        .pending_action
+        .as_ref()
+        .is_some_and(|p| p.confirm_text.is_some())
+    {
+        app.enter_typed_confirm();
+        return Ok(());
+    }
+    execute_pending_action(app);
+    app.exit_mode();
+    Ok(())
+}
+
 fn handle_warning_mode(app: &mut App, code: KeyCode) -> Result<bool> {
     match code {
         KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => {
@@ -265,55 +444,208 @@ fn handle_warning_mode(app: &mut App, code: KeyCode) -> Result<bool> {
     Ok(false)
 }
 
-fn handle_describe_mode(app: &mut App, code: KeyCode, modifiers: KeyModifiers) -> Result<bool> {
+async fn handle_describe_mode(
+    app: &mut App,
+    code: KeyCode,
+    modifiers: KeyModifiers,
+) -> Result<bool> {
+    if app.describe_search_active {
+        match code {
+            KeyCode::Char(c) => {
+                app.describe_search.push(c);
+                app.update_describe_matches();
+            }
+            KeyCode::Backspace => {
+                app.describe_search.pop();
+                app.update_describe_matches();
+            }
+            KeyCode::Enter => {
+                app.describe_search_active = false;
+            }
+            KeyCode::Esc => {
+                app.clear_describe_search();
+            }
+            _ => {}
+        }
+        return Ok(false);
+    }
+
     match code {
+        // Leave pipe output first, then the describe view
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('d') if app.pipe_output.is_some() => {
+            app.pipe_output = None;
+            app.describe_scroll = 0;
+            app.update_describe_matches();
+        }
         KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('d') => {
             app.exit_mode();
         }
+        KeyCode::Char(':') => {
+            app.enter_command_mode();
+        }
+        KeyCode::Char('|') => {
+            app.enter_pipe_command();
+        }
+        KeyCode::Char('/') => {
+            app.start_describe_search();
+        }
+        KeyCode::Char('n') => {
+            app.describe_next_match();
+        }
+        KeyCode::Char('N') => {
+            app.describe_previous_match();
+        }
+        KeyCode::Char('y') if app.pipe_output.is_none() => {
+            app.toggle_describe_format();
+        }
+        KeyCode::Char('x') if app.pipe_output.is_none() => {
+            app.toggle_describe_xml().await;
+        }
+        KeyCode::Char('w') => {
+            app.toggle_describe_wrap();
+        }
         KeyCode::Char('j') | KeyCode::Down => {
-            app.describe_scroll = app.describe_scroll.saturating_add(1);
+            app.scroll_describe(1);
         }
         KeyCode::Char('k') | KeyCode::Up => {
-            app.describe_scroll = app.describe_scroll.saturating_sub(1);
+            app.scroll_describe(-1);
         }
         KeyCode::Char('g') => {
             app.describe_scroll = 0;
         }
         KeyCode::Char('G') => {
-            app.describe_scroll_to_bottom(20);
+            app.describe_scroll_to_bottom();
         }
         KeyCode::PageDown | KeyCode::Char('f') if modifiers.contains(KeyModifiers::CONTROL) => {
-            app.describe_scroll = app.describe_scroll.saturating_add(20);
+            app.page_describe(1);
         }
         KeyCode::PageUp | KeyCode::Char('b') if modifiers.contains(KeyModifiers::CONTROL) => {
-            app.describe_scroll = app.describe_scroll.saturating_sub(20);
+            app.page_describe(-1);
         }
         _ => {}
     }
     Ok(false)
 }
 
-async fn execute_pending_action(app: &mut App) -> Result<()> {
-    let Some(pending) = app.pending_action.take() else {
-        return Ok(());
-    };
-
-    app.loading = true;
+/// Action log: scrolls like the describe view
+fn handle_log_mode(app: &mut App, code: KeyCode, modifiers: KeyModifiers) -> Result<bool> {
+    match code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('E') => {
+            app.exit_mode();
+        }
+        KeyCode::Char('j') | KeyCode::Down => {
+            app.scroll_describe(1);
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            app.scroll_describe(-1);
+        }
+        KeyCode::Char('g') => {
+            app.describe_scroll = 0;
+        }
+        KeyCode::Char('G') => {
+            app.describe_scroll_to_bottom();
+        }
+        KeyCode::PageDown | KeyCode::Char('f') if modifiers.contains(KeyModifiers::CONTROL) => {
+            app.page_describe(1);
+        }
+        KeyCode::PageUp | KeyCode::Char('b') if modifiers.contains(KeyModifiers::CONTROL) => {
+            app.page_describe(-1);
+        }
+        _ => {}
+    }
+    Ok(false)
+}
 
-    let params = serde_json::json!({
-        "id": pending.resource_id.parse::<i32>().unwrap_or(0)
-    });
+async fn handle_select_mode(app: &mut App, code: KeyCode) -> Result<bool> {
+    match code {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            app.exit_mode();
+        }
+        KeyCode::Char('j') | KeyCode::Down => {
+            app.select_next();
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            app.select_previous();
+        }
+        KeyCode::Char('g') | KeyCode::Home => {
+            app.select_first();
+        }
+        KeyCode::Char('G') | KeyCode::End => {
+            app.select_last();
+        }
+        KeyCode::Enter => {
+            app.confirm_selection().await;
+        }
+        _ => {}
+    }
+    Ok(false)
+}
 
-    match invoke_sdk_method(&pending.service, &pending.sdk_method, &app.client, &params).await {
-        Ok(_) => {
-            // Refresh after action
-            let _ = app.refresh_current().await;
+async fn handle_input_mode(app: &mut App, code: KeyCode, modifiers: KeyModifiers) -> Result<bool> {
+    if code == KeyCode::Enter {
+        let confirm = app.input.as_ref().is_some_and(|input| input.confirm);
+        let next = app.input.as_ref().and_then(|input| input.next.clone());
+        if let Some(pending) = app.take_input_action() {
+            if let Some(next) = next {
+                app.enter_select(pending, &next, None).await;
+            } else if confirm {
+                app.confirm_input(pending).await;
+            } else {
+                app.pending_action = Some(pending);
+                execute_pending_action(app);
+                app.exit_mode();
+            }
         }
-        Err(e) => {
-            app.error_message = Some(crate::one::client::format_one_error(&e));
+        return Ok(false);
+    }
+    if code == KeyCode::Esc {
+        app.exit_mode();
+        return Ok(false);
+    }
+
+    let Some(state) = app.input.as_mut() else {
+        return Ok(false);
+    };
+    if matches!(code, KeyCode::Tab | KeyCode::Down) {
+        state.focus_next();
+        return Ok(false);
+    }
+    if matches!(code, KeyCode::BackTab | KeyCode::Up) {
+        state.focus_previous();
+        return Ok(false);
+    }
+    let Some(input) = state.current_mut() else {
+        return Ok(false);
+    };
+    if input.toggle.is_some() {
+        if code == KeyCode::Char(' ') {
+            input.flip();
         }
+        return Ok(false);
+    }
+    match code {
+        KeyCode::Char('u') if modifiers.contains(KeyModifiers::CONTROL) => input.clear(),
+        KeyCode::Char(c) => input.insert(c),
+        KeyCode::Backspace => input.backspace(),
+        KeyCode::Delete => input.delete(),
+        KeyCode::Left => input.move_left(),
+        KeyCode::Right => input.move_right(),
+        KeyCode::Home => input.move_home(),
+        KeyCode::End => input.move_end(),
+        _ => {}
     }
+    Ok(false)
+}
 
-    app.loading = false;
-    Ok(())
+/// Start the pending action in the background; its outcome shows once it finishes
+fn execute_pending_action(app: &mut App) {
+    let Some(pending) = app.pending_action.take() else {
+        return;
+    };
+    if pending.service == READ_WRITE_SERVICE {
+        app.readonly = false;
+        app.set_status("Read-write mode: actions are enabled".to_string());
+        return;
+    }
+    app.start_action(pending);
 }
@@ -0,0 +1,341 @@
+//! Key bindings
+//!
+//! Core normal-mode actions and the keys that trigger them. The
+//! `keybindings` section of the config rebinds them, e.g.
+//! `{"quit": "ctrl+q", "back": "backspace"}`; anything invalid keeps the default.
+
+use crate::resource::{get_all_resource_keys, get_resource};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::collections::HashMap;
+
+/// Normal-mode actions that can be rebound
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyAction {
+    Quit,
+    Filter,
+    Describe,
+    Refresh,
+    Back,
+    Command,
+}
+
+impl KeyAction {
+    const ALL: [KeyAction; 6] = [
+        KeyAction::Quit,
+        KeyAction::Filter,
+        KeyAction::Describe,
+        KeyAction::Refresh,
+        KeyAction::Back,
+        KeyAction::Command,
+    ];
+
+    /// Name used in the config file
+    fn name(self) -> &'static str {
+        match self {
+            KeyAction::Quit => "quit",
+            KeyAction::Filter => "filter",
+            KeyAction::Describe => "describe",
+            KeyAction::Refresh => "refresh",
+            KeyAction::Back => "back",
+            KeyAction::Command => "command",
+        }
+    }
+
+    fn default_keys(self) -> &'static [&'static str] {
+        match self {
+            KeyAction::Quit => &["q"],
+            KeyAction::Filter => &["/"],
+            KeyAction::Describe => &["enter", "d"],
+            KeyAction::Refresh => &["R"],
+            KeyAction::Back => &["b", "backspace"],
+            KeyAction::Command => &[":"],
+        }
+    }
+}
+
+/// Keys with a fixed meaning that only their default action may use: global
+/// keys and the hard-coded normal-mode keys (navigation, marks, sorting,
+/// repeat, console, pages, action log)
+const RESERVED_KEYS: &[&str] = &[
+    ":", "?", "esc", "ctrl+c", "ctrl+d", "ctrl+f", "ctrl+b", "j", "k", "g", "G", "space", ".", "c",
+    "[", "]", ">", "<", "E",
+];
+
+/// Shortcuts of resource actions and sub-resources, which a rebind would shadow
+fn registry_shortcuts() -> impl Iterator<Item = &'static str> {
+    get_all_resource_keys()
+        .into_iter()
+        .filter_map(get_resource)
+        .flat_map(|resource| {
+            let actions = resource
+                .actions
+                .iter()
+                .filter_map(|a| a.shortcut.as_deref());
+            let subs = resource.sub_resources.iter().map(|s| s.shortcut.as_str());
+            actions.chain(subs)
+        })
+}
+
+/// A key with an optional Ctrl modifier
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct KeyBinding {
+    code: KeyCode,
+    ctrl: bool,
+}
+
+impl KeyBinding {
+    /// Parse `"x"`, `"ctrl+x"`, `"enter"`, `"backspace"`, `"tab"`, `"esc"` or `"space"`
+    fn parse(s: &str) -> Option<Self> {
+        let (ctrl, key) = match s.strip_prefix("ctrl+") {
+            Some(key) => (true, key),
+            None => (false, s),
+        };
+        let code = match key {
+            "enter" => KeyCode::Enter,
+            "backspace" => KeyCode::Backspace,
+            "tab" => KeyCode::Tab,
+            "esc" => KeyCode::Esc,
+            "space" => KeyCode::Char(' '),
+            _ => {
+                let mut chars = key.chars();
+                let c = chars.next()?;
+                if chars.next().is_some() {
+                    return None;
+                }
+                KeyCode::Char(c)
+            }
+        };
+        Some(Self { code, ctrl })
+    }
+}
+
+/// Resolved bindings for the rebindable actions
+#[derive(Debug, Clone)]
+pub struct KeyMap {
+    bindings: Vec<(KeyBinding, KeyAction)>,
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        Self::from_config(&HashMap::new())
+    }
+}
+
+impl KeyMap {
+    /// Apply `action name -> key` overrides. A rebound action loses its default
+    /// keys; unknown actions, unparsable keys, reserved keys, keys of resource
+    /// actions and sub-resources, and keys already taken by another action are
+    /// logged and ignored.
+    pub fn from_config(overrides: &HashMap<String, String>) -> Self {
+        let defaults = |action: KeyAction| -> Vec<KeyBinding> {
+            action
+                .default_keys()
+                .iter()
+                .filter_map(|key| KeyBinding::parse(key))
+                .collect()
+        };
+        let mut keys: HashMap<KeyAction, Vec<KeyBinding>> = KeyAction::ALL
+            .iter()
+            .map(|&action| (action, defaults(action)))
+            .collect();
+
+        let mut unknown: Vec<&String> = overrides
+            .keys()
+            .filter(|name| !KeyAction::ALL.iter().any(|a| a.name() == *name))
+            .collect();
+        unknown.sort();
+        for name in unknown {
+            tracing::warn!("Unknown action '{}' in keybindings", name);
+        }
+
+        let reserved: Vec<KeyBinding> = RESERVED_KEYS
+            .iter()
+            .copied()
+            .chain(registry_shortcuts())
+            .filter_map(KeyBinding::parse)
+            .collect();
+
+        // Overrides apply in `KeyAction::ALL` order, so conflicts resolve the same way every run
+        let mut rebound = Vec::new();
+        for action in KeyAction::ALL {
+            let Some(key) = overrides.get(action.name()) else {
+                continue;
+            };
+            let Some(binding) = KeyBinding::parse(key) else {
+                tracing::warn!("Invalid key '{}' for {} in keybindings", key, action.name());
+                continue;
+            };
+            if reserved.contains(&binding) && !defaults(action).contains(&binding) {
+                tracing::warn!(
+                    "Key '{}' is reserved; not binding it to {}",
+                    key,
+                    action.name()
+                );
+                continue;
+            }
+            keys.insert(action, vec![binding]);
+            rebound.push((action, key));
+        }
+
+        // An override that lands on another action's key is dropped, latest
+        // action first, until no two actions share a key
+        loop {
+            let clash = rebound.iter().rev().find_map(|&(action, key)| {
+                let other = KeyAction::ALL.iter().copied().find(|&other| {
+                    other != action && keys[&other].iter().any(|b| keys[&action].contains(b))
+                })?;
+                Some((action, key, other))
+            });
+            let Some((action, key, other)) = clash else {
+                break;
+            };
+            tracing::warn!(
+                "Key '{}' is already bound to {}; not binding it to {}",
+                key,
+                other.name(),
+                action.name()
+            );
+            keys.insert(action, defaults(action));
+            rebound.retain(|&(a, _)| a != action);
+        }
+
+        let bindings = KeyAction::ALL
+            .iter()
+            .flat_map(|&action| keys[&action].iter().map(move |&binding| (binding, action)))
+            .collect();
+        Self { bindings }
+    }
+
+    /// Action bound to this key press, if any
+    pub fn action_for(&self, code: KeyCode, modifiers: KeyModifiers) -> Option<KeyAction> {
+        let pressed = KeyBinding {
+            code,
+            ctrl: modifiers.contains(KeyModifiers::CONTROL),
+        };
+        self.bindings
+            .iter()
+            .find(|(binding, _)| *binding == pressed)
+            .map(|(_, action)| *action)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keymap(overrides: &[(&str, &str)]) -> KeyMap {
+        let overrides = overrides
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        KeyMap::from_config(&overrides)
+    }
+
+    #[test]
+    fn test_default_bindings() {
+        let map = KeyMap::default();
+        let none = KeyModifiers::NONE;
+        assert_eq!(
+            map.action_for(KeyCode::Char('q'), none),
+            Some(KeyAction::Quit)
+        );
+        assert_eq!(
+            map.action_for(KeyCode::Enter, none),
+            Some(KeyAction::Describe)
+        );
+        assert_eq!(
+            map.action_for(KeyCode::Backspace, none),
+            Some(KeyAction::Back)
+        );
+        assert_eq!(
+            map.action_for(KeyCode::Char('d'), KeyModifiers::CONTROL),
+            None
+        );
+    }
+
+    #[test]
+    fn test_rebind_replaces_default() {
+        let map = keymap(&[("quit", "ctrl+q"), ("refresh", "ctrl+r")]);
+        assert_eq!(
+            map.action_for(KeyCode::Char('q'), KeyModifiers::CONTROL),
+            Some(KeyAction::Quit)
+        );
+        assert_eq!(map.action_for(KeyCode::Char('q'), KeyModifiers::NONE), None);
+        assert_eq!(
+            map.action_for(KeyCode::Char('r'), KeyModifiers::CONTROL),
+            Some(KeyAction::Refresh)
+        );
+    }
+
+    #[test]
+    fn test_rebind_rejects_resource_shortcuts() {
+        // `x` is Disable on hosts and images, `r` is VM resume
+        let map = keymap(&[("quit", "x"), ("back", "r")]);
+        let none = KeyModifiers::NONE;
+        assert_eq!(map.action_for(KeyCode::Char('x'), none), None);
+        assert_eq!(map.action_for(KeyCode::Char('r'), none), None);
+        assert_eq!(
+            map.action_for(KeyCode::Char('q'), none),
+            Some(KeyAction::Quit)
+        );
+    }
+
+    #[test]
+    fn test_rebind_conflict_keeps_earlier_action() {
+        for _ in 0..20 {
+            let map = keymap(&[("quit", "t"), ("refresh", "t"), ("filter", "y")]);
+            let none = KeyModifiers::NONE;
+            assert_eq!(
+                map.action_for(KeyCode::Char('t'), none),
+                Some(KeyAction::Quit)
+            );
+            assert_eq!(
+                map.action_for(KeyCode::Char('R'), none),
+                Some(KeyAction::Refresh)
+            );
+            assert_eq!(
+                map.action_for(KeyCode::Char('y'), none),
+                Some(KeyAction::Filter)
+            );
+        }
+        // A swap is not a conflict
+        let map = keymap(&[("quit", "/"), ("filter", "q")]);
+        assert_eq!(
+            map.action_for(KeyCode::Char('/'), KeyModifiers::NONE),
+            Some(KeyAction::Quit)
+        );
+    }
+
+    #[test]
+    fn test_rebind_rejects_reserved_and_taken_keys() {
+        let map = keymap(&[
+            ("quit", ":"),
+            ("back", "d"),
+            ("filter", "j"),
+            ("refresh", " "),
+        ]);
+        assert_eq!(
+            map.action_for(KeyCode::Char('q'), KeyModifiers::NONE),
+            Some(KeyAction::Quit)
+        );
+        assert_eq!(
+            map.action_for(KeyCode::Char(':'), KeyModifiers::NONE),
+            Some(KeyAction::Command)
+        );
+        assert_eq!(
+            map.action_for(KeyCode::Char('d'), KeyModifiers::NONE),
+            Some(KeyAction::Describe)
+        );
+        assert_eq!(
+            map.action_for(KeyCode::Char('b'), KeyModifiers::NONE),
+            Some(KeyAction::Back)
+        );
+        // Hard-coded normal-mode keys stay with navigation and marking
+        assert_eq!(map.action_for(KeyCode::Char('j'), KeyModifiers::NONE), None);
+        assert_eq!(map.action_for(KeyCode::Char(' '), KeyModifiers::NONE), None);
+        assert_eq!(
+            map.action_for(KeyCode::Char('/'), KeyModifiers::NONE),
+            Some(KeyAction::Filter)
+        );
+    }
+}
@@ -4,7 +4,9 @@
 //! OpenNebula cloud resources.
 
 mod app;
+mod config;
 mod event;
+mod keymap;
 mod one;
 mod resource;
 mod ui;
@@ -24,13 +26,14 @@ use crossterm::{
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
+use one::auth::OneCredentials;
 use ratatui::prelude::*;
 use std::io;
 use std::path::PathBuf;
 use std::time::Duration;
 use tracing::Level;
 use tracing_subscriber::fmt::writer::MakeWriterExt;
-use ui::splash::{render as render_splash, SplashState};
+use ui::splash::{render as render_splash, CredentialPrompt, SplashState};
 
 /// Terminal UI for OpenNebula
 #[derive(Parser, Debug)]
@@ -44,9 +47,21 @@ struct Args {
     #[arg(long, value_enum, default_value = "off")]
     log_level: LogLevel,
 
+    /// PEM root certificate to trust, e.g. for a self-signed endpoint
+    #[arg(long, value_name = "PATH")]
+    ca_cert: Option<PathBuf>,
+
+    /// Skip TLS certificate verification (unsafe; lab setups only)
+    #[arg(long)]
+    insecure: bool,
+
     /// Run in read-only mode (block all write operations)
     #[arg(long)]
     readonly: bool,
+
+    /// Auto-refresh the current view at this interval (e.g. 5s, 1m)
+    #[arg(long, value_parser = config::parse_refresh_interval)]
+    refresh: Option<Duration>,
 }
 
 #[derive(Debug, Clone, Copy, ValueEnum)]
@@ -106,13 +121,7 @@ fn setup_logging(level: LogLevel) -> Option<tracing_appender::non_blocking::Work
 }
 
 fn get_log_path() -> PathBuf {
-    if let Some(config_dir) = dirs::config_dir() {
-        return config_dir.join("tone").join("tone.log");
-    }
-    if let Some(home) = dirs::home_dir() {
-        return home.join(".tone").join("tone.log");
-    }
-    PathBuf::from("tone.log")
+    config::config_dir().join("tone.log")
 }
 
 #[tokio::main]
@@ -134,6 +143,7 @@ async fn main() -> Result<()> {
     match result {
         Ok(Some(mut app)) => {
             let run_result = run_app(&mut terminal, &mut app).await;
+            app.save_session();
             cleanup_terminal(&mut terminal)?;
 
             if let Err(err) = run_result {
@@ -166,6 +176,36 @@ where
     Ok(())
 }
 
+/// Run `command` through the shell on the real terminal, leaving raw mode and
+/// the alternate screen until it exits
+fn run_suspended<B: Backend>(
+    terminal: &mut Terminal<B>,
+    command: &str,
+) -> Result<std::process::ExitStatus>
+where
+    B::Error: Send + Sync + 'static,
+{
+    disable_raw_mode()?;
+    execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture)?;
+
+    #[cfg(unix)]
+    let status = std::process::Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .status();
+    #[cfg(not(unix))]
+    let status = std::process::Command::new("cmd")
+        .arg("/C")
+        .arg(command)
+        .status();
+
+    // Restore the TUI even if the command couldn't be started
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture)?;
+    terminal.clear()?;
+    Ok(status?)
+}
+
 async fn initialize_with_splash<B: Backend>(
     terminal: &mut Terminal<B>,
     args: &Args,
@@ -186,11 +226,37 @@ where
     splash.set_message("Connecting to OpenNebula...");
     terminal.draw(|f| render_splash(f, &splash))?;
 
-    let client = if let Some(ref endpoint) = args.endpoint {
-        one::OneClient::with_endpoint(endpoint).await?
+    let mut config = config::Config::load();
+    let http_options = one::HttpOptions {
+        timeout: config.request_timeout(),
+        ca_cert: args.ca_cert.clone().or_else(|| config.ca_cert.clone()),
+        insecure: args.insecure,
+    };
+    let endpoint = config.effective_endpoint(args.endpoint.as_deref());
+    let mut save_credentials = false;
+    let mut client = if OneCredentials::stored_credentials_exist() {
+        match endpoint {
+            Some(endpoint) => one::OneClient::with_endpoint(&endpoint, &http_options).await?,
+            None => one::OneClient::new(&http_options).await?,
+        }
     } else {
-        one::OneClient::new().await?
+        // First run: ask instead of failing on missing ONE_AUTH
+        let Some((mut credentials, save)) = prompt_credentials(terminal, &mut splash)? else {
+            return Ok(None);
+        };
+        if let Some(endpoint) = endpoint {
+            credentials.set_endpoint(endpoint);
+        }
+        save_credentials = save;
+        splash.set_message("Connecting to OpenNebula...");
+        terminal.draw(|f| render_splash(f, &splash))?;
+        one::OneClient::from_credentials(credentials, &http_options)?
     };
+    if let Some(lifetime) = config.session_token_lifetime() {
+        if let Err(e) = client.login(lifetime).await {
+            tracing::warn!("Token login unavailable, using password auth: {}", e);
+        }
+    }
 
     tracing::info!(
         "Connected to OpenNebula at {} as {}",
@@ -204,16 +270,34 @@ where
         return Ok(None);
     }
 
-    // Step 3: Fetch initial data (VMs)
-    splash.set_message("Fetching virtual machines...");
+    // Step 2: Fetch initial data (last viewed resource, else VMs unless configured otherwise)
+    let initial_resource = config.initial_resource().to_string();
+    let display_name = resource::get_resource(&initial_resource)
+        .map(|r| r.display_name.to_lowercase())
+        .unwrap_or_default();
+    splash.set_message(&format!("Fetching {}...", display_name));
     terminal.draw(|f| render_splash(f, &splash))?;
 
-    let (vms, initial_error) = {
-        match resource::fetch_resources("one-vms", &client, &[]).await {
-            Ok(items) => (items, None),
+    let (items, next_token, initial_error) = {
+        match resource::fetch_resources_paginated(&initial_resource, &client, &[], None).await {
+            Ok(page) => {
+                // The connection works: remember it for the next launch
+                if save_credentials {
+                    match client.save_credentials() {
+                        Ok(path) => tracing::info!("Saved credentials to {:?}", path),
+                        Err(e) => tracing::warn!("Failed to save credentials: {}", e),
+                    }
+                }
+                if config.remember_connection(client.endpoint(), client.username()) {
+                    if let Err(e) = config.save() {
+                        tracing::warn!("Failed to save config: {}", e);
+                    }
+                }
+                (page.items, page.next_token, None)
+            }
             Err(e) => {
                 let error_msg = one::client::format_one_error(&e);
-                (Vec::new(), Some(error_msg))
+                (Vec::new(), None, Some(error_msg))
             }
         }
     };
@@ -224,15 +308,69 @@ where
 
     tokio::time::sleep(Duration::from_millis(200)).await;
 
-    let mut app = App::from_initialized(client, vms, args.readonly);
+    let mut app = App::from_initialized(client, items, config, args.readonly);
+    app.set_initial_page(next_token);
+    if args.refresh.is_some() {
+        app.refresh_interval = args.refresh;
+    }
 
+    match app.client.get_version().await {
+        Ok(version) => app.set_server_version(&version),
+        Err(e) => tracing::warn!("Failed to get OpenNebula version: {}", e),
+    }
     if let Some(err) = initial_error {
+        app.connected = false;
         app.error_message = Some(err);
     }
 
     Ok(Some(app))
 }
 
+/// Ask for a username and password on the splash screen; None if the user quits
+fn prompt_credentials<B: Backend>(
+    terminal: &mut Terminal<B>,
+    splash: &mut SplashState,
+) -> Result<Option<(OneCredentials, bool)>>
+where
+    B::Error: Send + Sync + 'static,
+{
+    splash.prompt = Some(CredentialPrompt::new());
+    loop {
+        terminal.draw(|f| render_splash(f, splash))?;
+        let Event::Key(key) = read()? else {
+            continue;
+        };
+        let Some(prompt) = splash.prompt.as_mut() else {
+            return Ok(None);
+        };
+        match key.code {
+            KeyCode::Esc => {
+                splash.prompt = None;
+                return Ok(None);
+            }
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                splash.prompt = None;
+                return Ok(None);
+            }
+            KeyCode::Tab | KeyCode::Down => prompt.focus_next(),
+            KeyCode::BackTab | KeyCode::Up => prompt.focus_previous(),
+            KeyCode::Char(' ') if prompt.focused == 2 => prompt.save = !prompt.save,
+            KeyCode::Char(c) => prompt.push(c),
+            KeyCode::Backspace => prompt.pop(),
+            KeyCode::Enter if prompt.focused == 0 => prompt.focus_next(),
+            KeyCode::Enter if !prompt.username.is_empty() => {
+                let username = prompt.username.clone();
+                // Move the password out; the emptied prompt is zeroized on drop
+                let password = std::mem::take(&mut prompt.password);
+                let save = prompt.save;
+                splash.prompt = None;
+                return Ok(Some((OneCredentials::from_login(username, password), save)));
+            }
+            _ => {}
+        }
+    }
+}
+
 fn check_abort() -> Result<bool> {
     if poll(Duration::from_millis(50))? {
         if let Event::Key(key) = read()? {
@@ -255,9 +393,26 @@ where
             return Ok(());
         }
 
-        // Auto-refresh (disabled by default)
+        if let Some(command) = app.pending_connect.take() {
+            match run_suspended(terminal, &command) {
+                Ok(status) if status.success() => {}
+                Ok(status) => app.error_message = Some(format!("connect: {}", status)),
+                Err(e) => app.error_message = Some(format!("connect: {}", e)),
+            }
+        }
+
+        app.check_confirm_timeout();
+        app.check_status_timeout();
+        app.tick_spinner();
+        app.poll_fetch();
+        app.poll_detail_fetch().await;
+        app.poll_connect().await;
+        app.poll_action().await;
+        app.poll_health_check().await;
+
+        // Auto-refresh (off unless --refresh or refresh_interval is set)
         if app.needs_refresh() {
-            let _ = app.refresh_current().await;
+            let _ = app.refresh_current();
         }
     }
 }
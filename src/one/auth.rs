@@ -12,6 +12,7 @@
 
 use anyhow::{Context, Result};
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
 /// OpenNebula credentials holder
@@ -27,14 +28,22 @@ pub struct OneCredentials {
     password: String,
     #[zeroize(skip)] // Endpoint is not sensitive
     endpoint: String,
+    /// Session token from `one.user.login`, used instead of the password
+    token: Option<String>,
+    #[zeroize(skip)]
+    token_expires: Option<Instant>,
 }
 
+/// Stop using a session token this long before the server expires it
+const TOKEN_EXPIRY_MARGIN: Duration = Duration::from_secs(60);
+
 // Custom Debug implementation that redacts sensitive data
 impl std::fmt::Debug for OneCredentials {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("OneCredentials")
             .field("username", &self.username)
             .field("password", &"[REDACTED]")
+            .field("token", &self.token.as_ref().map(|_| "[REDACTED]"))
             .field("endpoint", &self.endpoint)
             .finish()
     }
@@ -44,7 +53,7 @@ impl OneCredentials {
     /// Create credentials from environment or config file
     pub fn new() -> Result<Self> {
         let auth_string = Self::get_auth_string()?;
-        let endpoint = Self::get_endpoint();
+        let endpoint = normalize_endpoint(&Self::get_endpoint());
 
         let (username, password) = Self::parse_auth_string(&auth_string)?;
 
@@ -55,9 +64,56 @@ impl OneCredentials {
             username,
             password,
             endpoint,
+            token: None,
+            token_expires: None,
         })
     }
 
+    /// Create credentials from a username and password typed by the user
+    ///
+    /// The password is moved in, so the only copy is zeroized on drop.
+    pub fn from_login(username: String, password: String) -> Self {
+        let endpoint = normalize_endpoint(&Self::get_endpoint());
+        Self::warn_insecure_endpoint(&endpoint);
+        Self {
+            username,
+            password,
+            endpoint,
+            token: None,
+            token_expires: None,
+        }
+    }
+
+    /// Whether ONE_AUTH or `~/.one/one_auth` can provide credentials
+    pub fn stored_credentials_exist() -> bool {
+        std::env::var_os("ONE_AUTH").is_some() || Self::get_default_auth_file().exists()
+    }
+
+    /// Write `username:password` to `~/.one/one_auth`, readable only by the owner
+    pub fn save_to_auth_file(&self) -> Result<PathBuf> {
+        let path = Self::get_default_auth_file();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).context("Failed to create ~/.one")?;
+        }
+
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options
+            .open(&path)
+            .with_context(|| format!("Failed to create {:?}", path))?;
+
+        let mut content = format!("{}:{}\n", self.username, self.password);
+        let written = std::io::Write::write_all(&mut file, content.as_bytes());
+        content.zeroize();
+        written.with_context(|| format!("Failed to write {:?}", path))?;
+        Ok(path)
+    }
+
     /// Get the username (read-only access)
     pub fn username(&self) -> &str {
         &self.username
@@ -70,21 +126,19 @@ impl OneCredentials {
 
     /// Set a custom endpoint
     pub fn set_endpoint(&mut self, endpoint: String) {
+        let endpoint = normalize_endpoint(&endpoint);
         Self::warn_insecure_endpoint(&endpoint);
         self.endpoint = endpoint;
     }
 
     /// Warn if using insecure HTTP for remote endpoints
     fn warn_insecure_endpoint(endpoint: &str) {
-        if endpoint.starts_with("http://") {
-            // Allow HTTP only for localhost/127.0.0.1
-            let is_localhost = endpoint.contains("localhost") || endpoint.contains("127.0.0.1");
-            if !is_localhost {
-                tracing::warn!(
-                    "Using insecure HTTP connection to remote host. \
-                     Consider using HTTPS for production environments."
-                );
-            }
+        if endpoint.starts_with("http://") && !is_loopback_endpoint(endpoint) {
+            // Allow HTTP only for loopback hosts
+            tracing::warn!(
+                "Using insecure HTTP connection to remote host. \
+                 Consider using HTTPS for production environments."
+            );
         }
     }
 
@@ -175,11 +229,58 @@ impl OneCredentials {
         Ok((parts[0].to_string(), parts[1].to_string()))
     }
 
-    /// Get the auth string for XML-RPC calls
+    /// Use a session token valid for `lifetime` instead of the password
+    pub fn set_token(&mut self, token: String, lifetime: Duration) {
+        if let Some(ref mut old) = self.token {
+            old.zeroize();
+        }
+        self.token = Some(token);
+        self.token_expires = Some(Instant::now() + lifetime.saturating_sub(TOKEN_EXPIRY_MARGIN));
+    }
+
+    /// Get the auth string for XML-RPC calls: `username:token` while a session
+    /// token is valid, `username:password` otherwise
     /// Note: The returned string contains sensitive data and should be
     /// zeroized after use if stored in a variable
     pub fn auth_string(&self) -> String {
-        format!("{}:{}", self.username, self.password)
+        match (&self.token, self.token_expires) {
+            (Some(token), Some(expires)) if Instant::now() < expires => {
+                format!("{}:{}", self.username, token)
+            }
+            _ => format!("{}:{}", self.username, self.password),
+        }
+    }
+}
+
+/// Normalize an endpoint URL
+///
+/// Trims whitespace and appends the default `/RPC2` path when none is given.
+/// Bracketed IPv6 hosts and custom ports are preserved. Unparseable input is
+/// returned as-is so the HTTP client can report the error.
+pub fn normalize_endpoint(endpoint: &str) -> String {
+    let trimmed = endpoint.trim();
+    match url::Url::parse(trimmed) {
+        Ok(mut url) => {
+            if url.path().is_empty() || url.path() == "/" {
+                url.set_path("/RPC2");
+            }
+            url.to_string()
+        }
+        Err(_) => trimmed.to_string(),
+    }
+}
+
+/// Check whether an endpoint points at the local machine
+/// (`localhost`, `127.0.0.0/8` or `[::1]`)
+pub fn is_loopback_endpoint(endpoint: &str) -> bool {
+    let Ok(url) = url::Url::parse(endpoint) else {
+        return false;
+    };
+    match url.host() {
+        Some(url::Host::Domain(domain)) => domain.eq_ignore_ascii_case("localhost"),
+        Some(url::Host::Ipv4(ip)) => ip.is_loopback(),
+        Some(url::Host::Ipv6(ip)) => ip.is_loopback(),
+        None => false,
     }
 }
 
@@ -204,14 +305,65 @@ mod tests {
     #[test]
     fn test_debug_redacts_password() {
         // This test verifies that Debug output doesn't contain the actual password
-        let creds = OneCredentials {
+        let mut creds = OneCredentials {
             username: "testuser".to_string(),
             password: "supersecret".to_string(),
             endpoint: "https://localhost:2633/RPC2".to_string(),
+            token: None,
+            token_expires: None,
         };
+        creds.set_token("sessiontoken".to_string(), Duration::from_secs(3600));
         let debug_output = format!("{:?}", creds);
         assert!(!debug_output.contains("supersecret"));
+        assert!(!debug_output.contains("sessiontoken"));
         assert!(debug_output.contains("[REDACTED]"));
         assert!(debug_output.contains("testuser"));
     }
+
+    #[test]
+    fn test_auth_string_prefers_valid_token() {
+        let mut creds = OneCredentials {
+            username: "testuser".to_string(),
+            password: "supersecret".to_string(),
+            endpoint: "https://localhost:2633/RPC2".to_string(),
+            token: None,
+            token_expires: None,
+        };
+        assert_eq!(creds.auth_string(), "testuser:supersecret");
+
+        creds.set_token("abc123".to_string(), Duration::from_secs(3600));
+        assert_eq!(creds.auth_string(), "testuser:abc123");
+
+        // Inside the expiry margin the password is used again
+        creds.set_token("abc123".to_string(), Duration::from_secs(30));
+        assert_eq!(creds.auth_string(), "testuser:supersecret");
+    }
+
+    #[test]
+    fn test_loopback_endpoint_detection() {
+        assert!(is_loopback_endpoint("http://localhost:2633/RPC2"));
+        assert!(is_loopback_endpoint("http://127.0.0.1:2633/RPC2"));
+        assert!(is_loopback_endpoint("http://[::1]:2633/RPC2"));
+        assert!(!is_loopback_endpoint("http://[2001:db8::1]:2633/RPC2"));
+        assert!(!is_loopback_endpoint(
+            "http://localhost.example.com:2633/RPC2"
+        ));
+        assert!(!is_loopback_endpoint("http://10.0.0.127:2633/RPC2"));
+    }
+
+    #[test]
+    fn test_normalize_ipv6_endpoint() {
+        assert_eq!(
+            normalize_endpoint("https://[2001:db8::1]:2633/RPC2"),
+            "https://[2001:db8::1]:2633/RPC2"
+        );
+        assert_eq!(
+            normalize_endpoint(" https://[2001:db8::1]:8443 "),
+            "https://[2001:db8::1]:8443/RPC2"
+        );
+        assert_eq!(
+            normalize_endpoint("http://one.example.com:2633/custom/RPC2"),
+            "http://one.example.com:2633/custom/RPC2"
+        );
+    }
 }
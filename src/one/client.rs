@@ -8,52 +8,76 @@
 //! - Uses secure credential handling from auth module
 
 use super::auth::OneCredentials;
-use super::xmlrpc::{
-    build_method_call, parse_one_xml_to_json, parse_response, XmlRpcResponse, XmlRpcValue,
-};
+use super::xmlrpc::{build_method_call, parse_one_xml_to_json, parse_response, XmlRpcValue};
 use anyhow::{Context, Result};
-use reqwest::Client;
+use reqwest::{Certificate, Client, StatusCode};
 use serde_json::Value;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
-/// Default timeout for HTTP requests (30 seconds)
-const DEFAULT_TIMEOUT_SECS: u64 = 30;
+/// Retries of a read-only call after a transient network failure
+const MAX_RETRIES: u32 = 2;
+
+/// Delay before the first retry, doubled for each further one
+const RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
+/// HTTP settings for the connection to OpenNebula
+#[derive(Debug, Clone)]
+pub struct HttpOptions {
+    /// Give up on a request after this long
+    pub timeout: Duration,
+    /// PEM root certificate trusted in addition to the built-in roots
+    pub ca_cert: Option<PathBuf>,
+    /// Accept any server certificate (self-signed labs only)
+    pub insecure: bool,
+}
 
 /// Main OpenNebula client
 #[derive(Clone)]
 pub struct OneClient {
     credentials: OneCredentials,
     http: Client,
+    /// When set, calls are recorded here instead of being sent (dry-run)
+    recorder: Option<Arc<Mutex<Vec<String>>>>,
 }
 
 impl OneClient {
     /// Create a new OpenNebula client
-    pub async fn new() -> Result<Self> {
+    pub async fn new(options: &HttpOptions) -> Result<Self> {
         let credentials = OneCredentials::new()?;
 
-        let http = Client::builder()
-            .user_agent("tone/0.1.0")
-            .timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECS))
-            .connect_timeout(Duration::from_secs(10))
-            .build()
-            .context("Failed to create HTTP client")?;
+        Ok(Self {
+            credentials,
+            http: build_http_client(options)?,
+            recorder: None,
+        })
+    }
 
-        Ok(Self { credentials, http })
+    /// Create a client from credentials built elsewhere (e.g. typed at startup)
+    pub fn from_credentials(credentials: OneCredentials, options: &HttpOptions) -> Result<Self> {
+        Ok(Self {
+            credentials,
+            http: build_http_client(options)?,
+            recorder: None,
+        })
     }
 
     /// Create a new client with custom endpoint
-    pub async fn with_endpoint(endpoint: &str) -> Result<Self> {
+    pub async fn with_endpoint(endpoint: &str, options: &HttpOptions) -> Result<Self> {
         let mut credentials = OneCredentials::new()?;
         credentials.set_endpoint(endpoint.to_string());
 
-        let http = Client::builder()
-            .user_agent("tone/0.1.0")
-            .timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECS))
-            .connect_timeout(Duration::from_secs(10))
-            .build()
-            .context("Failed to create HTTP client")?;
+        Ok(Self {
+            credentials,
+            http: build_http_client(options)?,
+            recorder: None,
+        })
+    }
 
-        Ok(Self { credentials, http })
+    /// Store the client's username and password in `~/.one/one_auth`
+    pub fn save_credentials(&self) -> Result<PathBuf> {
+        self.credentials.save_to_auth_file()
     }
 
     /// Get the endpoint URL (for display purposes)
@@ -66,8 +90,114 @@ impl OneClient {
         self.credentials.username()
     }
 
+    /// Point the client at another endpoint (e.g. a federation zone), keeping
+    /// the login
+    pub fn set_endpoint(&mut self, endpoint: &str) {
+        self.credentials.set_endpoint(endpoint.to_string());
+    }
+
+    /// Re-read credentials (ONE_AUTH or ~/.one/one_auth), keeping the current endpoint
+    ///
+    /// The previous credentials are zeroized when dropped.
+    pub fn reload_credentials(&mut self) -> Result<()> {
+        let mut credentials = OneCredentials::new()?;
+        credentials.set_endpoint(self.endpoint().to_string());
+        self.credentials = credentials;
+        Ok(())
+    }
+
+    /// Log in with the password and switch to a session token valid for
+    /// `expiration` (one.user.login)
+    ///
+    /// On error (e.g. the auth driver doesn't allow tokens) the client keeps
+    /// using password authentication.
+    pub async fn login(&mut self, expiration: Duration) -> Result<()> {
+        let result = self
+            .call(
+                "one.user.login",
+                vec![
+                    XmlRpcValue::String(self.username().to_string()),
+                    XmlRpcValue::String(String::new()), // empty: generate a token
+                    XmlRpcValue::Int(expiration.as_secs().min(i32::MAX as u64) as i64),
+                    XmlRpcValue::Int(-1), // keep the user's effective group
+                ],
+            )
+            .await?;
+        let Value::String(token) = result else {
+            anyhow::bail!("one.user.login returned no token");
+        };
+        self.credentials.set_token(token, expiration);
+        Ok(())
+    }
+
+    /// Create a dry-run copy of this client that records calls instead of sending them
+    pub fn dry_run(&self) -> Self {
+        Self {
+            credentials: self.credentials.clone(),
+            http: self.http.clone(),
+            recorder: Some(Arc::new(Mutex::new(Vec::new()))),
+        }
+    }
+
+    /// Calls recorded by a dry-run client, formatted as `method(arg, ...)`
+    pub fn recorded_calls(&self) -> Vec<String> {
+        self.recorder
+            .as_ref()
+            .and_then(|r| r.lock().ok().map(|calls| calls.clone()))
+            .unwrap_or_default()
+    }
+
     /// Make an XML-RPC call to OpenNebula
     pub async fn call(&self, method: &str, params: Vec<XmlRpcValue>) -> Result<Value> {
+        if self.record(method, &params) {
+            return Ok(Value::Null);
+        }
+
+        match self.send(method, params).await? {
+            // OpenNebula returns an array [success, data, error_code]
+            XmlRpcValue::Array(arr) if arr.len() >= 2 => match one_result(&arr)? {
+                // The data is usually an XML document
+                XmlRpcValue::String(xml_data) => parse_one_xml_to_json(xml_data),
+                XmlRpcValue::Int(i) => Ok(Value::Number((*i).into())),
+                other => Ok(super::xmlrpc::xmlrpc_to_json(other)),
+            },
+            XmlRpcValue::Array(arr) => Ok(Value::Array(
+                arr.iter().map(super::xmlrpc::xmlrpc_to_json).collect(),
+            )),
+            value => Ok(super::xmlrpc::xmlrpc_to_json(&value)),
+        }
+    }
+
+    /// Make an XML-RPC call and return OpenNebula's XML document unparsed,
+    /// e.g. for a raw view of `one.vm.info`
+    pub async fn call_raw(&self, method: &str, params: Vec<XmlRpcValue>) -> Result<String> {
+        if self.record(method, &params) {
+            return Ok(String::new());
+        }
+
+        match self.send(method, params).await? {
+            XmlRpcValue::Array(arr) if arr.len() >= 2 => match one_result(&arr)? {
+                XmlRpcValue::String(xml_data) => Ok(xml_data.clone()),
+                _ => Err(anyhow::anyhow!("{} did not return an XML document", method)),
+            },
+            _ => Err(anyhow::anyhow!("Unexpected response to {}", method)),
+        }
+    }
+
+    /// Dry-run: record the call (without credentials) so the request is skipped
+    fn record(&self, method: &str, params: &[XmlRpcValue]) -> bool {
+        let Some(ref recorder) = self.recorder else {
+            return false;
+        };
+        let args: Vec<String> = params.iter().map(|p| p.to_string()).collect();
+        if let Ok(mut calls) = recorder.lock() {
+            calls.push(format!("{}({})", method, args.join(", ")));
+        }
+        true
+    }
+
+    /// Send an XML-RPC request and return the response value
+    async fn send(&self, method: &str, params: Vec<XmlRpcValue>) -> Result<XmlRpcValue> {
         // Prepend auth string to params
         let mut full_params = vec![XmlRpcValue::String(self.credentials.auth_string())];
         full_params.extend(params);
@@ -85,20 +215,7 @@ impl OneClient {
             xml_request.len()
         );
 
-        let response = self
-            .http
-            .post(self.credentials.endpoint())
-            .header("Content-Type", "text/xml")
-            .body(xml_request)
-            .send()
-            .await
-            .context("Failed to send XML-RPC request")?;
-
-        let status = response.status();
-        let body = response
-            .text()
-            .await
-            .context("Failed to read response body")?;
+        let (status, body) = self.post_with_retry(method, xml_request).await?;
 
         if !status.is_success() {
             // SECURITY: Don't log full response body as it may contain sensitive data
@@ -109,53 +226,48 @@ impl OneClient {
         // SECURITY: Only log response size, not content
         tracing::trace!("Response XML: {} bytes received", body.len());
 
-        let parsed = parse_response(&body)?;
-
-        match parsed {
-            XmlRpcResponse::Success(value) => {
-                // OpenNebula returns an array [success, data, error_code]
-                if let XmlRpcValue::Array(arr) = value {
-                    if arr.len() >= 2 {
-                        // First element is boolean success
-                        let success = match &arr[0] {
-                            XmlRpcValue::Boolean(b) => *b,
-                            _ => true,
-                        };
-
-                        if !success {
-                            // Second element contains error message
-                            let error_msg = match &arr[1] {
-                                XmlRpcValue::String(s) => s.clone(),
-                                _ => "Unknown error".to_string(),
-                            };
-                            return Err(anyhow::anyhow!("OpenNebula API error: {}", error_msg));
-                        }
-
-                        // Second element contains the data (usually XML string)
-                        match &arr[1] {
-                            XmlRpcValue::String(xml_data) => {
-                                // Parse the XML data to JSON
-                                parse_one_xml_to_json(xml_data)
-                            }
-                            XmlRpcValue::Int(i) => Ok(Value::Number((*i).into())),
-                            other => Ok(super::xmlrpc::xmlrpc_to_json(other)),
-                        }
-                    } else {
-                        Ok(Value::Array(
-                            arr.iter().map(super::xmlrpc::xmlrpc_to_json).collect(),
-                        ))
-                    }
-                } else {
-                    Ok(super::xmlrpc::xmlrpc_to_json(&value))
+        parse_response(&body)?.into_value()
+    }
+
+    /// POST a request, retrying transient network failures of read-only calls
+    ///
+    /// Write calls are sent once: a timed-out action may still have been applied.
+    async fn post_with_retry(&self, method: &str, body: String) -> Result<(StatusCode, String)> {
+        let retries = if is_read_only(method) { MAX_RETRIES } else { 0 };
+        let mut delay = RETRY_BACKOFF;
+        let mut attempt = 0;
+        loop {
+            match self.post(body.clone()).await {
+                Err(e) if attempt < retries && is_transient(&e) => {
+                    attempt += 1;
+                    tracing::warn!(
+                        "{} failed ({}), retry {}/{} in {:?}",
+                        method,
+                        e,
+                        attempt,
+                        retries,
+                        delay
+                    );
+                    tokio::time::sleep(delay).await;
+                    delay *= 2;
                 }
-            }
-            XmlRpcResponse::Fault(fault) => {
-                let msg = format!("XML-RPC fault: {:?}", fault);
-                Err(anyhow::anyhow!(msg))
+                result => return result.context("Failed to send XML-RPC request"),
             }
         }
     }
 
+    async fn post(&self, body: String) -> reqwest::Result<(StatusCode, String)> {
+        let response = self
+            .http
+            .post(self.credentials.endpoint())
+            .header("Content-Type", "text/xml")
+            .body(body)
+            .send()
+            .await?;
+        let status = response.status();
+        Ok((status, response.text().await?))
+    }
+
     // =========================================================================
     // VM Pool API
     // =========================================================================
@@ -164,7 +276,7 @@ impl OneClient {
     /// filter: -2 = all, -1 = mine, >= 0 = specific user
     /// start/end: -1 = all
     /// state: -1 = all, or specific state filter
-    pub async fn list_vms(&self, filter: i32, start: i32, end: i32, state: i32) -> Result<Value> {
+    pub async fn list_vms(&self, filter: i64, start: i64, end: i64, state: i64) -> Result<Value> {
         self.call(
             "one.vmpool.info",
             vec![
@@ -177,14 +289,40 @@ impl OneClient {
         .await
     }
 
+    /// VM usage history records between two timestamps (one.vmpool.accounting)
+    /// filter: -2 = all, -1 = mine, >= 0 = specific user
+    /// start_time/end_time: unix seconds, -1 = unbounded
+    pub async fn vmpool_accounting(
+        &self,
+        filter: i64,
+        start_time: i64,
+        end_time: i64,
+    ) -> Result<Value> {
+        self.call(
+            "one.vmpool.accounting",
+            vec![
+                XmlRpcValue::Int(filter),
+                XmlRpcValue::Int(start_time),
+                XmlRpcValue::Int(end_time),
+            ],
+        )
+        .await
+    }
+
+    /// Unparsed XML of one object (one.<object>.info), e.g. `info_xml("vm", 7)`
+    pub async fn info_xml(&self, object: &str, id: i64) -> Result<String> {
+        let method = format!("one.{}.info", object);
+        self.call_raw(&method, vec![XmlRpcValue::Int(id)]).await
+    }
+
     /// Get VM info (one.vm.info)
-    pub async fn get_vm(&self, vm_id: i32) -> Result<Value> {
+    pub async fn get_vm(&self, vm_id: i64) -> Result<Value> {
         self.call("one.vm.info", vec![XmlRpcValue::Int(vm_id)])
             .await
     }
 
     /// Perform VM action (one.vm.action)
-    pub async fn vm_action(&self, action: &str, vm_id: i32) -> Result<Value> {
+    pub async fn vm_action(&self, action: &str, vm_id: i64) -> Result<Value> {
         self.call(
             "one.vm.action",
             vec![
@@ -195,6 +333,208 @@ impl OneClient {
         .await
     }
 
+    /// Deploy a VM to a host (one.vm.deploy)
+    /// enforce: honor host capacity limits; ds_id: -1 = default system datastore
+    pub async fn vm_deploy(
+        &self,
+        vm_id: i64,
+        host_id: i64,
+        enforce: bool,
+        ds_id: i64,
+    ) -> Result<Value> {
+        self.call(
+            "one.vm.deploy",
+            vec![
+                XmlRpcValue::Int(vm_id),
+                XmlRpcValue::Int(host_id),
+                XmlRpcValue::Boolean(enforce),
+                XmlRpcValue::Int(ds_id),
+            ],
+        )
+        .await
+    }
+
+    /// Rename any object supporting it (one.<object>.rename, e.g. `image`, `vn`)
+    pub async fn rename(&self, object: &str, id: i64, name: &str) -> Result<Value> {
+        self.call(
+            &format!("one.{}.rename", object),
+            vec![XmlRpcValue::Int(id), XmlRpcValue::from(name)],
+        )
+        .await
+    }
+
+    /// Change the owner and/or group of an object (one.<object>.chown)
+    /// user_id / group_id: -1 leaves that field unchanged
+    pub async fn chown(&self, object: &str, id: i64, user_id: i64, group_id: i64) -> Result<Value> {
+        self.call(
+            &format!("one.{}.chown", object),
+            vec![
+                XmlRpcValue::Int(id),
+                XmlRpcValue::Int(user_id),
+                XmlRpcValue::Int(group_id),
+            ],
+        )
+        .await
+    }
+
+    /// Lock an object against actions (one.<object>.lock)
+    /// level: 1 = USE, 2 = MANAGE, 3 = ADMIN, 4 = ALL
+    pub async fn lock(&self, object: &str, id: i64, level: i64) -> Result<Value> {
+        self.call(
+            &format!("one.{}.lock", object),
+            vec![XmlRpcValue::Int(id), XmlRpcValue::Int(level)],
+        )
+        .await
+    }
+
+    /// Remove an object's lock (one.<object>.unlock)
+    pub async fn unlock(&self, object: &str, id: i64) -> Result<Value> {
+        self.call(
+            &format!("one.{}.unlock", object),
+            vec![XmlRpcValue::Int(id)],
+        )
+        .await
+    }
+
+    /// Lock a VM (one.vm.lock)
+    pub async fn vm_lock(&self, vm_id: i64, level: i64) -> Result<Value> {
+        self.lock("vm", vm_id, level).await
+    }
+
+    /// Unlock a VM (one.vm.unlock)
+    pub async fn vm_unlock(&self, vm_id: i64) -> Result<Value> {
+        self.unlock("vm", vm_id).await
+    }
+
+    /// Rename a VM (one.vm.rename)
+    pub async fn vm_rename(&self, vm_id: i64, name: &str) -> Result<Value> {
+        self.rename("vm", vm_id, name).await
+    }
+
+    /// Save a powered-off VM as a new template, copying its disks to images (one.vm.save)
+    /// persistent: make the copied images persistent
+    pub async fn vm_save_as_template(
+        &self,
+        vm_id: i64,
+        name: &str,
+        persistent: bool,
+    ) -> Result<Value> {
+        self.call(
+            "one.vm.save",
+            vec![
+                XmlRpcValue::Int(vm_id),
+                XmlRpcValue::String(name.to_string()),
+                XmlRpcValue::Boolean(persistent),
+            ],
+        )
+        .await
+    }
+
+    /// Resize a VM's capacity (one.vm.resize)
+    /// template: CPU/VCPU/MEMORY attributes, see `resize_template`
+    pub async fn vm_resize(&self, vm_id: i64, template: &str, enforce: bool) -> Result<Value> {
+        self.call(
+            "one.vm.resize",
+            vec![
+                XmlRpcValue::Int(vm_id),
+                XmlRpcValue::String(template.to_string()),
+                XmlRpcValue::Boolean(enforce),
+            ],
+        )
+        .await
+    }
+
+    /// Attach a NIC to a VM (one.vm.attachnic)
+    /// nic_template: e.g. `NIC = [ NETWORK_ID = "0" ]`
+    pub async fn vm_attach_nic(&self, vm_id: i64, nic_template: &str) -> Result<Value> {
+        self.call(
+            "one.vm.attachnic",
+            vec![
+                XmlRpcValue::Int(vm_id),
+                XmlRpcValue::String(nic_template.to_string()),
+            ],
+        )
+        .await
+    }
+
+    /// Detach a NIC from a VM (one.vm.detachnic)
+    pub async fn vm_detach_nic(&self, vm_id: i64, nic_id: i64) -> Result<Value> {
+        self.call(
+            "one.vm.detachnic",
+            vec![XmlRpcValue::Int(vm_id), XmlRpcValue::Int(nic_id)],
+        )
+        .await
+    }
+
+    /// Migrate a VM to another host (one.vm.migrate)
+    /// live: migrate without stopping the VM; ds_id: -1 = keep the system datastore
+    pub async fn vm_migrate(
+        &self,
+        vm_id: i64,
+        host_id: i64,
+        live: bool,
+        enforce: bool,
+        ds_id: i64,
+    ) -> Result<Value> {
+        self.call(
+            "one.vm.migrate",
+            vec![
+                XmlRpcValue::Int(vm_id),
+                XmlRpcValue::Int(host_id),
+                XmlRpcValue::Boolean(live),
+                XmlRpcValue::Boolean(enforce),
+                XmlRpcValue::Int(ds_id),
+            ],
+        )
+        .await
+    }
+
+    /// Recover a VM stuck in a failure state (one.vm.recover)
+    /// operation: 0 = failure, 1 = success, 2 = retry, 3 = delete, 4 = delete-recreate
+    pub async fn vm_recover(&self, vm_id: i64, operation: i64) -> Result<Value> {
+        self.call(
+            "one.vm.recover",
+            vec![XmlRpcValue::Int(vm_id), XmlRpcValue::Int(operation)],
+        )
+        .await
+    }
+
+    /// Back up a VM's disks to a backup datastore (one.vm.backup)
+    pub async fn vm_backup(&self, vm_id: i64, datastore_id: i64) -> Result<Value> {
+        self.call(
+            "one.vm.backup",
+            vec![
+                XmlRpcValue::Int(vm_id),
+                XmlRpcValue::Int(datastore_id),
+                // Keep the incremental backup chain
+                XmlRpcValue::Boolean(false),
+            ],
+        )
+        .await
+    }
+
+    /// Schedule an action on a VM (one.vm.schedadd)
+    /// template: e.g. `SCHED_ACTION = [ ACTION = "poweroff", TIME = "1760000000" ]`
+    pub async fn vm_sched_add(&self, vm_id: i64, template: &str) -> Result<Value> {
+        self.call(
+            "one.vm.schedadd",
+            vec![
+                XmlRpcValue::Int(vm_id),
+                XmlRpcValue::String(template.to_string()),
+            ],
+        )
+        .await
+    }
+
+    /// Delete a scheduled action of a VM (one.vm.scheddelete)
+    pub async fn vm_sched_delete(&self, vm_id: i64, sched_id: i64) -> Result<Value> {
+        self.call(
+            "one.vm.scheddelete",
+            vec![XmlRpcValue::Int(vm_id), XmlRpcValue::Int(sched_id)],
+        )
+        .await
+    }
+
     // =========================================================================
     // Host Pool API
     // =========================================================================
@@ -205,11 +545,27 @@ impl OneClient {
     }
 
     /// Get host info (one.host.info)
-    pub async fn get_host(&self, host_id: i32) -> Result<Value> {
+    pub async fn get_host(&self, host_id: i64) -> Result<Value> {
         self.call("one.host.info", vec![XmlRpcValue::Int(host_id)])
             .await
     }
 
+    /// Set a host's status (one.host.status)
+    /// status: 0 = enabled, 1 = disabled, 2 = offline
+    pub async fn host_status(&self, host_id: i64, status: i64) -> Result<Value> {
+        self.call(
+            "one.host.status",
+            vec![XmlRpcValue::Int(host_id), XmlRpcValue::Int(status)],
+        )
+        .await
+    }
+
+    /// Disable a host and move its VMs elsewhere (one.host.flush)
+    pub async fn host_flush(&self, host_id: i64) -> Result<Value> {
+        self.call("one.host.flush", vec![XmlRpcValue::Int(host_id)])
+            .await
+    }
+
     // =========================================================================
     // Datastore Pool API
     // =========================================================================
@@ -220,18 +576,27 @@ impl OneClient {
     }
 
     /// Get datastore info (one.datastore.info)
-    pub async fn get_datastore(&self, ds_id: i32) -> Result<Value> {
+    pub async fn get_datastore(&self, ds_id: i64) -> Result<Value> {
         self.call("one.datastore.info", vec![XmlRpcValue::Int(ds_id)])
             .await
     }
 
+    /// Enable or disable a datastore (one.datastore.enable)
+    pub async fn datastore_enable(&self, ds_id: i64, enable: bool) -> Result<Value> {
+        self.call(
+            "one.datastore.enable",
+            vec![XmlRpcValue::Int(ds_id), XmlRpcValue::Boolean(enable)],
+        )
+        .await
+    }
+
     // =========================================================================
     // Virtual Network Pool API
     // =========================================================================
 
     /// List all virtual networks (one.vnpool.info)
     /// filter: -2 = all, -1 = mine, >= 0 = specific user
-    pub async fn list_vnets(&self, filter: i32, start: i32, end: i32) -> Result<Value> {
+    pub async fn list_vnets(&self, filter: i64, start: i64, end: i64) -> Result<Value> {
         self.call(
             "one.vnpool.info",
             vec![
@@ -244,18 +609,56 @@ impl OneClient {
     }
 
     /// Get virtual network info (one.vn.info)
-    pub async fn get_vnet(&self, vnet_id: i32) -> Result<Value> {
+    pub async fn get_vnet(&self, vnet_id: i64) -> Result<Value> {
         self.call("one.vn.info", vec![XmlRpcValue::Int(vnet_id)])
             .await
     }
 
+    /// Hold a lease so it isn't handed out (one.vn.hold)
+    /// lease_template: e.g. `LEASES = [ IP = "10.0.0.5" ]`
+    pub async fn vn_hold(&self, vnet_id: i64, lease_template: &str) -> Result<Value> {
+        self.call(
+            "one.vn.hold",
+            vec![
+                XmlRpcValue::Int(vnet_id),
+                XmlRpcValue::String(lease_template.to_string()),
+            ],
+        )
+        .await
+    }
+
+    /// Release a held lease (one.vn.release)
+    pub async fn vn_release(&self, vnet_id: i64, lease_template: &str) -> Result<Value> {
+        self.call(
+            "one.vn.release",
+            vec![
+                XmlRpcValue::Int(vnet_id),
+                XmlRpcValue::String(lease_template.to_string()),
+            ],
+        )
+        .await
+    }
+
+    /// Reserve addresses into a new network (one.vn.reserve), returning its ID
+    /// template: e.g. `SIZE = "4" NAME = "web-reservation"`
+    pub async fn vn_reserve(&self, vnet_id: i64, template: &str) -> Result<Value> {
+        self.call(
+            "one.vn.reserve",
+            vec![
+                XmlRpcValue::Int(vnet_id),
+                XmlRpcValue::String(template.to_string()),
+            ],
+        )
+        .await
+    }
+
     // =========================================================================
     // Image Pool API
     // =========================================================================
 
     /// List all images (one.imagepool.info)
     /// filter: -2 = all, -1 = mine, >= 0 = specific user
-    pub async fn list_images(&self, filter: i32, start: i32, end: i32) -> Result<Value> {
+    pub async fn list_images(&self, filter: i64, start: i64, end: i64) -> Result<Value> {
         self.call(
             "one.imagepool.info",
             vec![
@@ -268,18 +671,56 @@ impl OneClient {
     }
 
     /// Get image info (one.image.info)
-    pub async fn get_image(&self, image_id: i32) -> Result<Value> {
+    pub async fn get_image(&self, image_id: i64) -> Result<Value> {
         self.call("one.image.info", vec![XmlRpcValue::Int(image_id)])
             .await
     }
 
+    /// Enable or disable an image (one.image.enable)
+    pub async fn image_enable(&self, image_id: i64, enable: bool) -> Result<Value> {
+        self.call(
+            "one.image.enable",
+            vec![XmlRpcValue::Int(image_id), XmlRpcValue::Boolean(enable)],
+        )
+        .await
+    }
+
+    /// Make an image persistent or non-persistent (one.image.persistent)
+    pub async fn image_persistent(&self, image_id: i64, persistent: bool) -> Result<Value> {
+        self.call(
+            "one.image.persistent",
+            vec![XmlRpcValue::Int(image_id), XmlRpcValue::Boolean(persistent)],
+        )
+        .await
+    }
+
+    /// Copy an image (one.image.clone), returning the new image's ID
+    /// target_ds: datastore of the copy, -1 = the source image's datastore
+    pub async fn image_clone(&self, image_id: i64, name: &str, target_ds: i64) -> Result<Value> {
+        self.call(
+            "one.image.clone",
+            vec![
+                XmlRpcValue::Int(image_id),
+                XmlRpcValue::String(name.to_string()),
+                XmlRpcValue::Int(target_ds),
+            ],
+        )
+        .await
+    }
+
+    /// Delete an image (one.image.delete)
+    pub async fn image_delete(&self, image_id: i64) -> Result<Value> {
+        self.call("one.image.delete", vec![XmlRpcValue::Int(image_id)])
+            .await
+    }
+
     // =========================================================================
     // Template Pool API
     // =========================================================================
 
     /// List all templates (one.templatepool.info)
     /// filter: -2 = all, -1 = mine, >= 0 = specific user
-    pub async fn list_templates(&self, filter: i32, start: i32, end: i32) -> Result<Value> {
+    pub async fn list_templates(&self, filter: i64, start: i64, end: i64) -> Result<Value> {
         self.call(
             "one.templatepool.info",
             vec![
@@ -292,11 +733,171 @@ impl OneClient {
     }
 
     /// Get template info (one.template.info)
-    pub async fn get_template(&self, template_id: i32) -> Result<Value> {
+    pub async fn get_template(&self, template_id: i64) -> Result<Value> {
         self.call("one.template.info", vec![XmlRpcValue::Int(template_id)])
             .await
     }
 
+    /// Create a VM from a template (one.template.instantiate)
+    /// name: empty lets OpenNebula pick one; hold: create the VM on hold instead of pending
+    pub async fn template_instantiate(
+        &self,
+        template_id: i64,
+        name: &str,
+        hold: bool,
+        extra_template: &str,
+    ) -> Result<Value> {
+        self.call(
+            "one.template.instantiate",
+            vec![
+                XmlRpcValue::Int(template_id),
+                XmlRpcValue::String(name.to_string()),
+                XmlRpcValue::Boolean(hold),
+                XmlRpcValue::String(extra_template.to_string()),
+            ],
+        )
+        .await
+    }
+
+    /// Copy a template (one.template.clone)
+    /// recursive: also clone the images it uses
+    pub async fn template_clone(
+        &self,
+        template_id: i64,
+        name: &str,
+        recursive: bool,
+    ) -> Result<Value> {
+        self.call(
+            "one.template.clone",
+            vec![
+                XmlRpcValue::Int(template_id),
+                XmlRpcValue::String(name.to_string()),
+                XmlRpcValue::Boolean(recursive),
+            ],
+        )
+        .await
+    }
+
+    /// Delete a template (one.template.delete)
+    /// recursive: also delete the images it uses
+    pub async fn template_delete(&self, template_id: i64, recursive: bool) -> Result<Value> {
+        self.call(
+            "one.template.delete",
+            vec![
+                XmlRpcValue::Int(template_id),
+                XmlRpcValue::Boolean(recursive),
+            ],
+        )
+        .await
+    }
+
+    // =========================================================================
+    // VM Group Pool API
+    // =========================================================================
+
+    /// List VM groups (one.vmgrouppool.info)
+    /// filter: -2 = all, -1 = mine, >= 0 = specific user
+    pub async fn list_vmgroups(&self, filter: i64, start: i64, end: i64) -> Result<Value> {
+        self.call(
+            "one.vmgrouppool.info",
+            vec![
+                XmlRpcValue::Int(filter),
+                XmlRpcValue::Int(start),
+                XmlRpcValue::Int(end),
+            ],
+        )
+        .await
+    }
+
+    /// Get VM group info, including its roles (one.vmgroup.info)
+    pub async fn get_vmgroup(&self, vmgroup_id: i64) -> Result<Value> {
+        self.call("one.vmgroup.info", vec![XmlRpcValue::Int(vmgroup_id)])
+            .await
+    }
+
+    // =========================================================================
+    // Security Group Pool API
+    // =========================================================================
+
+    /// List security groups (one.secgrouppool.info)
+    /// filter: -2 = all, -1 = mine, >= 0 = specific user
+    pub async fn list_security_groups(&self, filter: i64, start: i64, end: i64) -> Result<Value> {
+        self.call(
+            "one.secgrouppool.info",
+            vec![
+                XmlRpcValue::Int(filter),
+                XmlRpcValue::Int(start),
+                XmlRpcValue::Int(end),
+            ],
+        )
+        .await
+    }
+
+    /// Get security group info, including its rules (one.secgroup.info)
+    pub async fn get_security_group(&self, secgroup_id: i64) -> Result<Value> {
+        self.call("one.secgroup.info", vec![XmlRpcValue::Int(secgroup_id)])
+            .await
+    }
+
+    // =========================================================================
+    // Virtual Router Pool API
+    // =========================================================================
+
+    /// List virtual routers (one.vrouterpool.info)
+    /// filter: -2 = all, -1 = mine, >= 0 = specific user
+    pub async fn list_vrouters(&self, filter: i64, start: i64, end: i64) -> Result<Value> {
+        self.call(
+            "one.vrouterpool.info",
+            vec![
+                XmlRpcValue::Int(filter),
+                XmlRpcValue::Int(start),
+                XmlRpcValue::Int(end),
+            ],
+        )
+        .await
+    }
+
+    /// Get virtual router info, including its NICs (one.vrouter.info)
+    pub async fn get_vrouter(&self, vrouter_id: i64) -> Result<Value> {
+        self.call("one.vrouter.info", vec![XmlRpcValue::Int(vrouter_id)])
+            .await
+    }
+
+    // =========================================================================
+    // Marketplace API
+    // =========================================================================
+
+    /// List all marketplaces (one.marketpool.info)
+    pub async fn list_marketplaces(&self) -> Result<Value> {
+        self.call("one.marketpool.info", vec![]).await
+    }
+
+    /// Get marketplace info (one.market.info)
+    pub async fn get_marketplace(&self, market_id: i64) -> Result<Value> {
+        self.call("one.market.info", vec![XmlRpcValue::Int(market_id)])
+            .await
+    }
+
+    /// List marketplace apps (one.marketapppool.info)
+    /// filter: -2 = all, -1 = mine, >= 0 = specific user
+    pub async fn list_market_apps(&self, filter: i64, start: i64, end: i64) -> Result<Value> {
+        self.call(
+            "one.marketapppool.info",
+            vec![
+                XmlRpcValue::Int(filter),
+                XmlRpcValue::Int(start),
+                XmlRpcValue::Int(end),
+            ],
+        )
+        .await
+    }
+
+    /// Get marketplace app info (one.marketapp.info)
+    pub async fn get_market_app(&self, app_id: i64) -> Result<Value> {
+        self.call("one.marketapp.info", vec![XmlRpcValue::Int(app_id)])
+            .await
+    }
+
     // =========================================================================
     // Cluster Pool API
     // =========================================================================
@@ -307,7 +908,7 @@ impl OneClient {
     }
 
     /// Get cluster info (one.cluster.info)
-    pub async fn get_cluster(&self, cluster_id: i32) -> Result<Value> {
+    pub async fn get_cluster(&self, cluster_id: i64) -> Result<Value> {
         self.call("one.cluster.info", vec![XmlRpcValue::Int(cluster_id)])
             .await
     }
@@ -322,11 +923,56 @@ impl OneClient {
     }
 
     /// Get user info (one.user.info)
-    pub async fn get_user(&self, user_id: i32) -> Result<Value> {
+    pub async fn get_user(&self, user_id: i64) -> Result<Value> {
         self.call("one.user.info", vec![XmlRpcValue::Int(user_id)])
             .await
     }
 
+    /// Create a user in its default group (one.user.allocate), returning its ID
+    /// driver: authentication driver, e.g. `core`
+    pub async fn user_create(&self, name: &str, password: &str, driver: &str) -> Result<Value> {
+        self.call(
+            "one.user.allocate",
+            vec![
+                XmlRpcValue::String(name.to_string()),
+                XmlRpcValue::String(password.to_string()),
+                XmlRpcValue::String(driver.to_string()),
+                XmlRpcValue::Array(Vec::new()),
+            ],
+        )
+        .await
+    }
+
+    /// Add a user to a secondary group (one.user.addgroup)
+    pub async fn user_addgroup(&self, user_id: i64, group_id: i64) -> Result<Value> {
+        self.call(
+            "one.user.addgroup",
+            vec![XmlRpcValue::Int(user_id), XmlRpcValue::Int(group_id)],
+        )
+        .await
+    }
+
+    /// Remove a user from a secondary group (one.user.delgroup)
+    pub async fn user_delgroup(&self, user_id: i64, group_id: i64) -> Result<Value> {
+        self.call(
+            "one.user.delgroup",
+            vec![XmlRpcValue::Int(user_id), XmlRpcValue::Int(group_id)],
+        )
+        .await
+    }
+
+    /// Change a user's password (one.user.passwd)
+    pub async fn user_passwd(&self, user_id: i64, password: &str) -> Result<Value> {
+        self.call(
+            "one.user.passwd",
+            vec![
+                XmlRpcValue::Int(user_id),
+                XmlRpcValue::String(password.to_string()),
+            ],
+        )
+        .await
+    }
+
     // =========================================================================
     // Group Pool API
     // =========================================================================
@@ -336,6 +982,30 @@ impl OneClient {
         self.call("one.grouppool.info", vec![]).await
     }
 
+    /// Get group info, including its quotas (one.group.info)
+    pub async fn get_group(&self, group_id: i64) -> Result<Value> {
+        self.call("one.group.info", vec![XmlRpcValue::Int(group_id)])
+            .await
+    }
+
+    /// Create a group (one.group.allocate), returning its ID
+    pub async fn group_create(&self, name: &str) -> Result<Value> {
+        self.call(
+            "one.group.allocate",
+            vec![XmlRpcValue::String(name.to_string())],
+        )
+        .await
+    }
+
+    // =========================================================================
+    // ACL API
+    // =========================================================================
+
+    /// List all ACL rules (one.acl.info)
+    pub async fn list_acls(&self) -> Result<Value> {
+        self.call("one.acl.info", vec![]).await
+    }
+
     // =========================================================================
     // Zone API
     // =========================================================================
@@ -345,6 +1015,12 @@ impl OneClient {
         self.call("one.zonepool.info", vec![]).await
     }
 
+    /// Get zone info (one.zone.info)
+    pub async fn get_zone(&self, zone_id: i64) -> Result<Value> {
+        self.call("one.zone.info", vec![XmlRpcValue::Int(zone_id)])
+            .await
+    }
+
     // =========================================================================
     // System API
     // =========================================================================
@@ -360,13 +1036,48 @@ impl OneClient {
     }
 }
 
+/// Build the template for one.vm.resize; attributes left as None keep their current value
+pub fn resize_template(cpu: Option<f64>, vcpu: Option<u32>, memory: Option<u64>) -> String {
+    let mut lines = Vec::new();
+    if let Some(cpu) = cpu {
+        lines.push(format!("CPU = \"{}\"", cpu));
+    }
+    if let Some(vcpu) = vcpu {
+        lines.push(format!("VCPU = \"{}\"", vcpu));
+    }
+    if let Some(memory) = memory {
+        lines.push(format!("MEMORY = \"{}\"", memory));
+    }
+    lines.join("\n")
+}
+
+/// Longest OpenNebula error message shown before it is cut off
+const MAX_ERROR_CHARS: usize = 100;
+
 /// Format an OpenNebula API error for display
 /// This function sanitizes error messages to prevent information disclosure
 pub fn format_one_error(error: &anyhow::Error) -> String {
-    let error_str = error.to_string();
+    let timed_out = error
+        .chain()
+        .filter_map(|cause| cause.downcast_ref::<reqwest::Error>())
+        .any(|e| e.is_timeout());
+    if timed_out {
+        return "Request timed out. Server may be unreachable.".to_string();
+    }
+
+    // Include the causes: the top-level message is only our context
+    let error_str = format!("{:#}", error);
+
+    // OpenNebula reports these as faults or failed results, not HTTP errors
+    if error_str.contains("Not authorized to perform") {
+        return "Permission denied: you lack rights for this action".to_string();
+    }
 
     // Clean up common error patterns with safe messages
-    if error_str.contains("401") || error_str.contains("Authentication") {
+    if error_str.contains("401")
+        || error_str.contains("Authentication")
+        || error_str.contains("couldn't be authenticated")
+    {
         return "Authentication failed. Check ONE_AUTH credentials.".to_string();
     }
     if error_str.contains("Connection refused") {
@@ -379,12 +1090,16 @@ pub fn format_one_error(error: &anyhow::Error) -> String {
         return "TLS/SSL error. Check certificate configuration.".to_string();
     }
 
-    // For OpenNebula API errors, extract just the message
-    if let Some(start) = error_str.find("OpenNebula API error:") {
+    // For OpenNebula API errors and XML-RPC faults, extract just the message
+    if let Some(start) = error_str
+        .find("OpenNebula API error:")
+        .or_else(|| error_str.find("XML-RPC fault:"))
+    {
         let msg = &error_str[start..];
-        // Truncate long error messages
-        if msg.len() > 100 {
-            return format!("{}...", &msg[..100]);
+        // Truncate long error messages on a char boundary (names may be non-ASCII)
+        if msg.chars().count() > MAX_ERROR_CHARS {
+            let truncated: String = msg.chars().take(MAX_ERROR_CHARS).collect();
+            return format!("{}...", truncated);
         }
         return msg.to_string();
     }
@@ -392,3 +1107,103 @@ pub fn format_one_error(error: &anyhow::Error) -> String {
     // Generic fallback - don't expose internal details
     "An error occurred. Check logs for details.".to_string()
 }
+
+fn build_http_client(options: &HttpOptions) -> Result<Client> {
+    let mut builder = Client::builder()
+        .user_agent("tone/0.1.0")
+        .timeout(options.timeout)
+        .connect_timeout(Duration::from_secs(10));
+
+    if let Some(ref path) = options.ca_cert {
+        let pem = std::fs::read(path)
+            .with_context(|| format!("Failed to read CA certificate {}", path.display()))?;
+        let cert = Certificate::from_pem(&pem)
+            .with_context(|| format!("Invalid CA certificate {}", path.display()))?;
+        builder = builder.add_root_certificate(cert);
+    }
+    if options.insecure {
+        tracing::warn!("TLS certificate verification is DISABLED (--insecure)");
+        builder = builder.danger_accept_invalid_certs(true);
+    }
+
+    builder.build().context("Failed to create HTTP client")
+}
+
+/// Data of an OpenNebula `[success, data, error_code]` reply, or its error message
+fn one_result(arr: &[XmlRpcValue]) -> Result<&XmlRpcValue> {
+    let success = match &arr[0] {
+        XmlRpcValue::Boolean(b) => *b,
+        _ => true,
+    };
+    if !success {
+        let error_msg = match &arr[1] {
+            XmlRpcValue::String(s) => s.clone(),
+            _ => "Unknown error".to_string(),
+        };
+        return Err(anyhow::anyhow!("OpenNebula API error: {}", error_msg));
+    }
+    Ok(&arr[1])
+}
+
+/// Info and system queries, safe to send again
+fn is_read_only(method: &str) -> bool {
+    method.ends_with(".info") || matches!(method, "one.system.version" | "one.system.config")
+}
+
+/// Failures worth retrying: timeouts, refused or reset connections
+fn is_transient(error: &reqwest::Error) -> bool {
+    error.is_timeout() || error.is_connect() || error.is_request()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_read_only_methods_are_retried() {
+        assert!(is_read_only("one.vmpool.info"));
+        assert!(is_read_only("one.system.version"));
+        assert!(!is_read_only("one.vm.action"));
+        assert!(!is_read_only("one.template.instantiate"));
+    }
+
+    #[test]
+    fn test_error_truncation_keeps_multibyte_chars() {
+        // "é" is two bytes, so byte 100 falls inside a character
+        let name = "é".repeat(60);
+        let error = anyhow::anyhow!("OpenNebula API error: [one.vm.info] VM {} not found", name);
+        let message = format_one_error(&error);
+        assert!(message.ends_with("..."));
+        assert_eq!(message.chars().count(), MAX_ERROR_CHARS + 3);
+        assert!(message.starts_with("OpenNebula API error:"));
+    }
+
+    #[test]
+    fn test_permission_errors() {
+        let error = anyhow::anyhow!(
+            "OpenNebula API error: [one.vm.action] User [3] : Not authorized to perform MANAGE VM [12]."
+        );
+        assert_eq!(
+            format_one_error(&error),
+            "Permission denied: you lack rights for this action"
+        );
+        let error = anyhow::anyhow!(
+            "OpenNebula API error: [one.vm.action] User couldn't be authenticated, aborting call."
+        );
+        assert_eq!(
+            format_one_error(&error),
+            "Authentication failed. Check ONE_AUTH credentials."
+        );
+    }
+
+    #[test]
+    fn test_fault_shown_in_error() {
+        let error =
+            anyhow::anyhow!("XML-RPC fault: [-32601] RPC method one.vm.frobnicate not defined")
+                .context("one.vm.frobnicate failed");
+        assert_eq!(
+            format_one_error(&error),
+            "XML-RPC fault: [-32601] RPC method one.vm.frobnicate not defined"
+        );
+    }
+}
@@ -6,4 +6,4 @@ pub mod auth;
 pub mod client;
 pub mod xmlrpc;
 
-pub use client::OneClient;
+pub use client::{HttpOptions, OneClient};
@@ -67,13 +67,36 @@ pub fn build_method_call(method: &str, params: &[XmlRpcValue]) -> Result<String>
 #[derive(Debug, Clone)]
 pub enum XmlRpcValue {
     String(String),
-    Int(i32),
+    /// Integer; sent as `<int>` when it fits in 32 bits, `<i8>` otherwise
+    Int(i64),
     Boolean(bool),
     Double(f64),
     Array(Vec<XmlRpcValue>),
     Struct(Vec<(String, XmlRpcValue)>),
 }
 
+impl std::fmt::Display for XmlRpcValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            XmlRpcValue::String(s) => write!(f, "{}", s),
+            XmlRpcValue::Int(i) => write!(f, "{}", i),
+            XmlRpcValue::Boolean(b) => write!(f, "{}", b),
+            XmlRpcValue::Double(d) => write!(f, "{}", d),
+            XmlRpcValue::Array(arr) => {
+                let items: Vec<String> = arr.iter().map(|v| v.to_string()).collect();
+                write!(f, "[{}]", items.join(", "))
+            }
+            XmlRpcValue::Struct(members) => {
+                let items: Vec<String> = members
+                    .iter()
+                    .map(|(k, v)| format!("{}: {}", k, v))
+                    .collect();
+                write!(f, "{{{}}}", items.join(", "))
+            }
+        }
+    }
+}
+
 impl From<&str> for XmlRpcValue {
     fn from(s: &str) -> Self {
         XmlRpcValue::String(s.to_string())
@@ -88,6 +111,12 @@ impl From<String> for XmlRpcValue {
 
 impl From<i32> for XmlRpcValue {
     fn from(i: i32) -> Self {
+        XmlRpcValue::Int(i.into())
+    }
+}
+
+impl From<i64> for XmlRpcValue {
+    fn from(i: i64) -> Self {
         XmlRpcValue::Int(i)
     }
 }
@@ -116,14 +145,19 @@ fn write_value<W: std::io::Write>(writer: &mut Writer<W>, value: &XmlRpcValue) -
                 .context("Failed to write string end")?;
         }
         XmlRpcValue::Int(i) => {
+            let tag = if i32::try_from(*i).is_ok() {
+                "int"
+            } else {
+                "i8"
+            };
             writer
-                .write_event(Event::Start(BytesStart::new("int")))
+                .write_event(Event::Start(BytesStart::new(tag)))
                 .context("Failed to write int start")?;
             writer
                 .write_event(Event::Text(BytesText::new(&i.to_string())))
                 .context("Failed to write int value")?;
             writer
-                .write_event(Event::End(BytesEnd::new("int")))
+                .write_event(Event::End(BytesEnd::new(tag)))
                 .context("Failed to write int end")?;
         }
         XmlRpcValue::Boolean(b) => {
@@ -267,11 +301,11 @@ fn parse_value_content(reader: &mut quick_xml::Reader<&[u8]>) -> Result<XmlRpcVa
                         }
                     }
                     "name" => in_name = true,
-                    "string" | "int" | "i4" | "boolean" | "double" | "array" | "struct"
-                    | "data" | "member" => {
-                        if current_type.is_none() {
-                            current_type = Some(tag);
-                        }
+                    "string" | "int" | "i4" | "i8" | "boolean" | "double" | "array" | "struct"
+                    | "data" | "member"
+                        if current_type.is_none() =>
+                    {
+                        current_type = Some(tag);
                     }
                     _ => {}
                 }
@@ -285,8 +319,8 @@ fn parse_value_content(reader: &mut quick_xml::Reader<&[u8]>) -> Result<XmlRpcVa
                             // Return based on type
                             return match current_type.as_deref() {
                                 Some("string") | None => Ok(XmlRpcValue::String(text_content)),
-                                Some("int") | Some("i4") => {
-                                    let i: i32 = text_content.parse().unwrap_or(0);
+                                Some("int") | Some("i4") | Some("i8") => {
+                                    let i: i64 = text_content.parse().unwrap_or(0);
                                     Ok(XmlRpcValue::Int(i))
                                 }
                                 Some("boolean") => {
@@ -316,6 +350,15 @@ fn parse_value_content(reader: &mut quick_xml::Reader<&[u8]>) -> Result<XmlRpcVa
                     text_content.push_str(&text);
                 }
             }
+            Ok(Event::CData(e)) => {
+                // CDATA is literal text: no entity unescaping
+                let text = String::from_utf8_lossy(&e);
+                if in_name {
+                    member_name.push_str(&text);
+                } else {
+                    text_content.push_str(&text);
+                }
+            }
             Ok(Event::Eof) => break,
             Err(e) => return Err(anyhow::anyhow!("XML parsing error: {}", e)),
             _ => {}
@@ -333,6 +376,38 @@ pub enum XmlRpcResponse {
     Fault(XmlRpcValue),
 }
 
+impl XmlRpcResponse {
+    /// The returned value, or an error reading `XML-RPC fault: [code] message`
+    pub fn into_value(self) -> Result<XmlRpcValue> {
+        match self {
+            XmlRpcResponse::Success(value) => Ok(value),
+            XmlRpcResponse::Fault(fault) => {
+                Err(anyhow::anyhow!("XML-RPC fault: {}", fault_message(&fault)))
+            }
+        }
+    }
+}
+
+/// Format a standard fault struct (`faultCode`, `faultString`) as `[code] message`
+fn fault_message(fault: &XmlRpcValue) -> String {
+    let XmlRpcValue::Struct(members) = fault else {
+        return fault.to_string();
+    };
+    let member = |name: &str| {
+        members
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value)
+    };
+    match (member("faultCode"), member("faultString")) {
+        (Some(XmlRpcValue::Int(code)), Some(XmlRpcValue::String(message))) => {
+            format!("[{}] {}", code, message)
+        }
+        (None, Some(XmlRpcValue::String(message))) => message.clone(),
+        _ => fault.to_string(),
+    }
+}
+
 /// Convert XmlRpcValue to serde_json::Value
 pub fn xmlrpc_to_json(value: &XmlRpcValue) -> Value {
     match value {
@@ -364,6 +439,22 @@ pub fn parse_one_xml_to_json(xml: &str) -> Result<Value> {
     parse_xml_element(&mut reader)
 }
 
+/// Re-indent an OpenNebula XML document with one element per line
+pub fn pretty_print_xml(xml: &str) -> Result<String> {
+    let mut reader = quick_xml::Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+    let mut writer = Writer::new_with_indent(Vec::new(), b' ', 2);
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Eof) => break,
+            Ok(event) => writer.write_event(event)?,
+            Err(e) => return Err(anyhow::anyhow!("XML parsing error: {}", e)),
+        }
+    }
+    String::from_utf8(writer.into_inner()).context("XML is not valid UTF-8")
+}
+
 fn parse_xml_element(reader: &mut quick_xml::Reader<&[u8]>) -> Result<Value> {
     let mut buf = Vec::new();
     let mut result: Map<String, Value> = Map::new();
@@ -400,6 +491,9 @@ fn parse_xml_element(reader: &mut quick_xml::Reader<&[u8]>) -> Result<Value> {
             Ok(Event::Text(e)) => {
                 text_content.push_str(&e.unescape().unwrap_or_default());
             }
+            Ok(Event::CData(e)) => {
+                text_content.push_str(&String::from_utf8_lossy(&e));
+            }
             Ok(Event::Empty(ref e)) => {
                 let tag = String::from_utf8_lossy(e.name().as_ref()).to_string();
                 result.insert(tag, Value::Null);
@@ -421,6 +515,37 @@ fn parse_xml_element(reader: &mut quick_xml::Reader<&[u8]>) -> Result<Value> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_pretty_print_xml() {
+        let xml = "<VM><ID>7</ID><TEMPLATE><CONTEXT><START_SCRIPT><![CDATA[echo hi]]></START_SCRIPT></CONTEXT><NIC/></TEMPLATE></VM>";
+        let pretty = pretty_print_xml(xml).unwrap();
+        assert_eq!(
+            pretty,
+            "<VM>\n  <ID>7</ID>\n  <TEMPLATE>\n    <CONTEXT>\n      <START_SCRIPT><![CDATA[echo hi]]></START_SCRIPT>\n    </CONTEXT>\n    <NIC/>\n  </TEMPLATE>\n</VM>"
+        );
+        assert!(pretty_print_xml("<VM><ID>7</VM>").is_err());
+    }
+
+    #[test]
+    fn test_fault_response_message() {
+        let body = r#"<?xml version="1.0"?>
+<methodResponse>
+  <fault>
+    <value>
+      <struct>
+        <member><name>faultCode</name><value><int>-32601</int></value></member>
+        <member><name>faultString</name><value><string>RPC method one.vm.frobnicate not defined</string></value></member>
+      </struct>
+    </value>
+  </fault>
+</methodResponse>"#;
+        let error = parse_response(body).unwrap().into_value().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "XML-RPC fault: [-32601] RPC method one.vm.frobnicate not defined"
+        );
+    }
+
     #[test]
     fn test_build_method_call() {
         let params = vec![
@@ -434,6 +559,60 @@ mod tests {
         assert!(xml.contains("user:password"));
     }
 
+    #[test]
+    fn test_large_id_round_trip() {
+        let id = 123_456_789_012_i64;
+        let xml = build_method_call("one.vm.info", &[XmlRpcValue::Int(id)]).unwrap();
+        assert!(xml.contains("<i8>123456789012</i8>"));
+
+        let params_start = xml.find("<params>").unwrap();
+        let params_end = xml.find("</params>").unwrap() + "</params>".len();
+        let response = format!(
+            "<methodResponse>{}</methodResponse>",
+            &xml[params_start..params_end]
+        );
+        match parse_response(&response).unwrap() {
+            XmlRpcResponse::Success(XmlRpcValue::Int(parsed)) => assert_eq!(parsed, id),
+            other => panic!("unexpected response: {:?}", other),
+        }
+
+        let small = build_method_call("one.vm.info", &[XmlRpcValue::Int(42)]).unwrap();
+        assert!(small.contains("<int>42</int>"));
+    }
+
+    #[test]
+    fn test_display_value() {
+        let value = XmlRpcValue::Array(vec![
+            XmlRpcValue::String("terminate-hard".to_string()),
+            XmlRpcValue::Int(4213),
+        ]);
+        assert_eq!(value.to_string(), "[terminate-hard, 4213]");
+    }
+
+    #[test]
+    fn test_parse_one_xml_cdata() {
+        let xml = r#"<CONTEXT><START_SCRIPT><![CDATA[echo hi]]></START_SCRIPT></CONTEXT>"#;
+        let json = parse_one_xml_to_json(xml).unwrap();
+        assert_eq!(json["CONTEXT"]["START_SCRIPT"], "echo hi");
+
+        let xml = r#"<VM><DESCRIPTION><![CDATA[a < b & "c"]]></DESCRIPTION></VM>"#;
+        let json = parse_one_xml_to_json(xml).unwrap();
+        assert_eq!(json["VM"]["DESCRIPTION"], r#"a < b & "c""#);
+    }
+
+    #[test]
+    fn test_parse_response_cdata() {
+        let xml = "<methodResponse><params><param><value><string>\
+                   <![CDATA[<VM><ID>1</ID></VM>]]></string></value></param></params>\
+                   </methodResponse>";
+        match parse_response(xml).unwrap() {
+            XmlRpcResponse::Success(XmlRpcValue::String(s)) => {
+                assert_eq!(s, "<VM><ID>1</ID></VM>")
+            }
+            other => panic!("unexpected response: {:?}", other),
+        }
+    }
+
     #[test]
     fn test_parse_one_xml() {
         let xml = r#"<VM><ID>123</ID><NAME>test-vm</NAME></VM>"#;
@@ -9,18 +9,19 @@ use anyhow::Result;
 use serde_json::Value;
 
 /// Paginated result with next page token
+#[derive(Clone)]
 pub struct PaginatedResult {
     pub items: Vec<Value>,
     pub next_token: Option<String>,
 }
 
-/// Fetch resources based on resource key
+/// Fetch every item of a resource, across all pages (e.g. for select pickers)
 pub async fn fetch_resources(
     resource_key: &str,
     client: &OneClient,
     filters: &[ResourceFilter],
 ) -> Result<Vec<Value>> {
-    let result = fetch_resources_paginated(resource_key, client, filters, None).await?;
+    let result = fetch(resource_key, client, filters, false, None).await?;
     Ok(result.items)
 }
 
@@ -30,18 +31,61 @@ pub async fn fetch_resources_paginated(
     client: &OneClient,
     filters: &[ResourceFilter],
     page_token: Option<&str>,
+) -> Result<PaginatedResult> {
+    fetch(
+        resource_key,
+        client,
+        filters,
+        pages_on_server(filters),
+        page_token,
+    )
+    .await
+}
+
+/// Whether a list can be fetched a page at a time. Client-side filters need the
+/// whole pool: filtering a single page would miss matches on other pages and
+/// page through unrelated items (e.g. a virtual router's VMs).
+fn pages_on_server(filters: &[ResourceFilter]) -> bool {
+    filters.iter().all(|f| f.field.is_none())
+}
+
+/// Fetch one page starting at `page_token`, or the whole pool unless `paged`
+async fn fetch(
+    resource_key: &str,
+    client: &OneClient,
+    filters: &[ResourceFilter],
+    paged: bool,
+    page_token: Option<&str>,
 ) -> Result<PaginatedResult> {
     let resource = get_resource(resource_key)
         .ok_or_else(|| anyhow::anyhow!("Unknown resource: {}", resource_key))?;
 
     // Build parameters from filters
     let mut params = resource.sdk_method_params.clone();
+    let mut page = None;
     if let Value::Object(ref mut map) = params {
-        for filter in filters {
-            map.insert(filter.name.clone(), Value::String(filter.values.join(",")));
+        for filter in filters.iter().filter(|f| f.field.is_none()) {
+            // A single numeric value goes as a number, as pool methods expect ints
+            let value = match filter.values.as_slice() {
+                [single] => single
+                    .parse::<i64>()
+                    .map(Value::from)
+                    .unwrap_or_else(|_| Value::String(single.clone())),
+                values => Value::String(values.join(",")),
+            };
+            map.insert(filter.name.clone(), value);
         }
-        if let Some(token) = page_token {
-            map.insert("page_token".to_string(), Value::String(token.to_string()));
+
+        // Pool methods page with start = offset and end = -page_size;
+        // the defaults (start = end = -1) return the whole pool
+        if let Some(size) = resource
+            .page_size
+            .filter(|_| paged && map.contains_key("start"))
+        {
+            let offset = page_token.and_then(|t| t.parse::<u64>().ok()).unwrap_or(0);
+            map.insert("start".to_string(), Value::from(offset));
+            map.insert("end".to_string(), Value::from(-i64::from(size)));
+            page = Some((offset, size));
         }
     }
 
@@ -50,13 +94,16 @@ pub async fn fetch_resources_paginated(
         invoke_sdk_method(&resource.service, &resource.sdk_method, client, &params).await?;
 
     // Extract items from response using response_path
-    let items = extract_items(&response, &resource.response_path)?;
+    let mut items = extract_items(&response, &resource.response_path)?;
+
+    // A full page means there may be more; the token is the next offset
+    let next_token = page
+        .filter(|(_, size)| items.len() >= *size as usize)
+        .map(|(offset, size)| (offset + u64::from(size)).to_string());
+
+    items.retain(|item| filters.iter().all(|f| f.matches(item)));
 
-    // OpenNebula doesn't have built-in pagination tokens, so we return None
-    Ok(PaginatedResult {
-        items,
-        next_token: None,
-    })
+    Ok(PaginatedResult { items, next_token })
 }
 
 /// Extract items from response using a path like "VM_POOL.VM" or "HOST_POOL.HOST"
@@ -80,3 +127,36 @@ fn extract_items(response: &Value, path: &str) -> Result<Vec<Value>> {
         _ => Ok(Vec::new()),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_side_filters_fetch_whole_pool() {
+        assert!(pages_on_server(&[]));
+        assert!(pages_on_server(&[ResourceFilter::new(
+            "state",
+            vec!["3".into()]
+        )]));
+        // A virtual router's VMs, matched by ID among all VMs
+        let router_vms =
+            ResourceFilter::client_side("vm_ids", "ID", vec!["7".into(), "120".into()]);
+        assert!(!pages_on_server(&[router_vms]));
+    }
+
+    #[test]
+    fn test_vm_group_members_fetch_whole_pool() {
+        let groups = get_resource("one-vmgroups").unwrap();
+        let members = groups
+            .sub_resources
+            .iter()
+            .find(|sub| sub.resource_key == "one-vms")
+            .unwrap();
+        let field = members.filter_field.as_deref().unwrap();
+        // Role members are listed as "3,5" and matched by ID among all VMs
+        let filter = ResourceFilter::client_side(&members.filter_param, field, vec!["3".into()]);
+        assert!(filter.matches(&serde_json::json!({"ID": "3"})));
+        assert!(!pages_on_server(&[filter]));
+    }
+}
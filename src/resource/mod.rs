@@ -6,41 +6,89 @@ mod fetcher;
 mod registry;
 mod sdk_dispatch;
 
-pub use fetcher::{fetch_resources, fetch_resources_paginated};
+pub use fetcher::{fetch_resources, fetch_resources_paginated, PaginatedResult};
 pub use registry::{
-    get_all_resource_keys, get_color_for_value, get_resource, ActionDef, ColumnDef, ResourceDef,
-    ResourceFilter,
+    get_all_resource_keys, get_color_for_value, get_resource, ActionDef, ColumnDef, ColumnFormat,
+    InputConfig, ResourceDef, ResourceFilter, SelectConfig,
 };
-pub use sdk_dispatch::invoke_sdk_method;
+pub use sdk_dispatch::{fetch_raw_xml, invoke_sdk_method};
 
-/// Extract a value from JSON using a dot-notation path
-pub fn extract_json_value(item: &serde_json::Value, path: &str) -> String {
-    let parts: Vec<&str> = path.split('.').collect();
+/// Resolve a dot-notation path (with optional `KEY[idx]` indexing) to a JSON node
+pub fn get_json_path<'a>(item: &'a serde_json::Value, path: &str) -> Option<&'a serde_json::Value> {
     let mut current = item;
 
-    for part in parts {
+    for part in path.split('.') {
         // Handle array indexing like "DISK[0]"
         if let Some(bracket_pos) = part.find('[') {
             let key = &part[..bracket_pos];
             let idx_str = &part[bracket_pos + 1..part.len() - 1];
             if let Ok(idx) = idx_str.parse::<usize>() {
-                current = match current.get(key) {
-                    Some(arr) => match arr.get(idx) {
-                        Some(v) => v,
-                        None => return "-".to_string(),
-                    },
-                    None => return "-".to_string(),
-                };
+                current = current.get(key)?.get(idx)?;
                 continue;
             }
         }
 
-        current = match current.get(part) {
-            Some(v) => v,
-            None => return "-".to_string(),
+        current = current.get(part)?;
+    }
+
+    Some(current)
+}
+
+/// Resolve a path that may contain `KEY[*]` wildcards to every matching node
+///
+/// A wildcard over a single object (OpenNebula's encoding of a one-element
+/// list) yields that object, so `TEMPLATE.NIC[*].IP` works for any NIC count.
+pub fn collect_json_path<'a>(
+    item: &'a serde_json::Value,
+    path: &str,
+) -> Vec<&'a serde_json::Value> {
+    let Some((head, rest)) = path.split_once("[*]") else {
+        return get_json_path(item, path).into_iter().collect();
+    };
+
+    let base = if head.is_empty() {
+        Some(item)
+    } else {
+        get_json_path(item, head)
+    };
+    let elements: Vec<&serde_json::Value> = match base {
+        Some(serde_json::Value::Array(arr)) => arr.iter().collect(),
+        Some(serde_json::Value::Null) | None => Vec::new(),
+        Some(value) => vec![value],
+    };
+
+    let rest = rest.strip_prefix('.').unwrap_or(rest);
+    if rest.is_empty() {
+        return elements;
+    }
+    elements
+        .into_iter()
+        .flat_map(|element| collect_json_path(element, rest))
+        .collect()
+}
+
+/// Extract a value from JSON using a dot-notation path
+///
+/// Wildcard paths (`NIC[*].IP`) join all matches with ", ".
+pub fn extract_json_value(item: &serde_json::Value, path: &str) -> String {
+    if path.contains("[*]") {
+        let joined = extract_json_values(item, path).join(", ");
+        return if joined.is_empty() {
+            "-".to_string()
+        } else {
+            joined
         };
     }
 
+    let current = if path.is_empty() {
+        item
+    } else {
+        match get_json_path(item, path) {
+            Some(v) => v,
+            None => return "-".to_string(),
+        }
+    };
+
     match current {
         serde_json::Value::String(s) => s.clone(),
         serde_json::Value::Number(n) => n.to_string(),
@@ -60,6 +108,38 @@ pub fn extract_json_value(item: &serde_json::Value, path: &str) -> String {
     }
 }
 
+/// Format a numeric state code using a state format (e.g. `vm_state`)
+pub fn format_state(format: ColumnFormat, value: &str) -> Option<String> {
+    let code = value.parse::<i32>().ok()?;
+    match format {
+        ColumnFormat::VmState => Some(format_vm_state(code)),
+        ColumnFormat::LcmState => Some(format_lcm_state(code)),
+        ColumnFormat::HostState => Some(format_host_state(code)),
+        ColumnFormat::ImageState => Some(format_image_state(code)),
+        ColumnFormat::DatastoreState => Some(format_datastore_state(code)),
+        ColumnFormat::MarketState => Some(format_market_state(code)),
+        ColumnFormat::MarketappState => Some(format_marketapp_state(code)),
+        ColumnFormat::LockLevel => Some(format_lock_level(code)),
+        _ => None,
+    }
+}
+
+/// Extract every scalar value at a path, flattening arrays
+///
+/// OpenNebula serializes a one-element list as a plain value, so `HOSTS.ID`
+/// may be either `"3"` or `["3", "4"]`; both yield a list of strings.
+pub fn extract_json_values(item: &serde_json::Value, path: &str) -> Vec<String> {
+    collect_json_path(item, path)
+        .into_iter()
+        .flat_map(|value| match value {
+            serde_json::Value::Array(arr) => arr.iter().collect(),
+            value => vec![value],
+        })
+        .map(|v| extract_json_value(v, ""))
+        .filter(|v| v != "-")
+        .collect()
+}
+
 /// Format OpenNebula VM state code to string
 pub fn format_vm_state(state: i32) -> String {
     match state {
@@ -78,6 +158,19 @@ pub fn format_vm_state(state: i32) -> String {
     }
 }
 
+/// State code for the VM pool `state` filter from a name (`running`, `poweroff`, ...)
+/// or a number; `running` is the ACTIVE state, `any` is -1
+pub fn vm_state_code(name: &str) -> Option<i64> {
+    if let Ok(code) = name.parse::<i64>() {
+        return Some(code);
+    }
+    match name.to_ascii_uppercase().as_str() {
+        "ANY" | "ALL" => Some(-1),
+        "RUNNING" => Some(3),
+        upper => (0..=11).find(|code| format_vm_state(*code as i32) == upper),
+    }
+}
+
 /// Format OpenNebula VM LCM state code to string
 pub fn format_lcm_state(lcm_state: i32) -> String {
     match lcm_state {
@@ -154,6 +247,302 @@ pub fn format_lcm_state(lcm_state: i32) -> String {
     }
 }
 
+/// Check whether a VM LCM state is a transitional (busy) state
+///
+/// Actions issued while a VM is mid-transition are usually rejected by
+/// OpenNebula, so callers use this to warn before acting.
+pub fn is_transitional_lcm_state(lcm_state: i32) -> bool {
+    let name = format_lcm_state(lcm_state);
+    !matches!(name.as_str(), "LCM_INIT" | "RUNNING" | "UNKNOWN")
+        && !name.ends_with("_FAILURE")
+        && !name.starts_with("LCM_UNKNOWN")
+}
+
+/// Format a byte count as a human-readable size (e.g. "12.4 GB")
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 6] = ["B", "KB", "MB", "GB", "TB", "PB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+/// Render a compact usage bar like `[███░░░░░░░]  31%`
+///
+/// The bar is clamped to its width but the percentage shows overcommit.
+pub fn format_usage_bar(used: f64, total: f64, width: usize) -> String {
+    let ratio = if total > 0.0 { used / total } else { 0.0 };
+    let filled = ((ratio.clamp(0.0, 1.0) * width as f64).round() as usize).min(width);
+    format!(
+        "[{}{}] {:>3}%",
+        "█".repeat(filled),
+        "░".repeat(width - filled),
+        (ratio * 100.0).round() as i64
+    )
+}
+
+/// Cell color for a usage ratio: green when idle, through yellow at half, to red when full
+pub fn usage_color(ratio: f64) -> [u8; 3] {
+    let ratio = ratio.clamp(0.0, 1.0);
+    if ratio < 0.5 {
+        [(ratio * 2.0 * 255.0).round() as u8, 255, 0]
+    } else {
+        [255, ((1.0 - ratio) * 2.0 * 255.0).round() as u8, 0]
+    }
+}
+
+/// Used fraction behind a usage-bar column (`host_cpu`, `host_mem`, `quota_usage`)
+pub fn usage_ratio(item: &serde_json::Value, col: &ColumnDef) -> Option<f64> {
+    let (used, total) = match col.format? {
+        ColumnFormat::HostCpu => host_share_usage(get_json_path(item, &col.json_path)?, "cpu")?,
+        ColumnFormat::HostMem => host_share_usage(get_json_path(item, &col.json_path)?, "mem")?,
+        ColumnFormat::QuotaUsage => (
+            extract_json_value(item, "USED").parse::<f64>().ok()?,
+            extract_json_value(item, "LIMIT").parse::<f64>().ok()?,
+        ),
+        _ => return None,
+    };
+    (total > 0.0).then(|| used / total)
+}
+
+/// Read allocated/total capacity from a host's `HOST_SHARE` block
+///
+/// `kind` is `"cpu"` (percent of a core, 100 = 1 core) or `"mem"` (KB).
+pub fn host_share_usage(share: &serde_json::Value, kind: &str) -> Option<(f64, f64)> {
+    let (used_key, total_key) = match kind {
+        "cpu" => ("CPU_USAGE", "MAX_CPU"),
+        "mem" => ("MEM_USAGE", "MAX_MEM"),
+        _ => return None,
+    };
+    let used = extract_json_value(share, used_key).parse::<f64>().ok()?;
+    let total = extract_json_value(share, total_key).parse::<f64>().ok()?;
+    Some((used, total))
+}
+
+/// Quota usage at or above this fraction of the limit is flagged `NEAR`
+const QUOTA_NEAR_RATIO: f64 = 0.8;
+
+/// Quota blocks of a user or group: section, element, scope label and (key, label) pairs
+type QuotaSection = (
+    &'static str,
+    &'static str,
+    &'static str,
+    &'static [(&'static str, &'static str)],
+);
+
+const QUOTA_SECTIONS: &[QuotaSection] = &[
+    (
+        "VM_QUOTA",
+        "VM",
+        "",
+        &[
+            ("VMS", "VMs"),
+            ("RUNNING_VMS", "Running VMs"),
+            ("CPU", "CPU"),
+            ("RUNNING_CPU", "Running CPU"),
+            ("MEMORY", "Memory (MB)"),
+            ("RUNNING_MEMORY", "Running memory (MB)"),
+            ("SYSTEM_DISK_SIZE", "System disk (MB)"),
+        ],
+    ),
+    (
+        "DATASTORE_QUOTA",
+        "DATASTORE",
+        "Datastore",
+        &[("IMAGES", "Images"), ("SIZE", "Size (MB)")],
+    ),
+    (
+        "NETWORK_QUOTA",
+        "NETWORK",
+        "Network",
+        &[("LEASES", "Leases")],
+    ),
+    ("IMAGE_QUOTA", "IMAGE", "Image", &[("RVMS", "Running VMs")]),
+];
+
+/// Flatten the quotas of a user or group (`one.user.info` / `one.group.info`)
+/// into rows with QUOTA, SCOPE, USED, LIMIT and STATUS
+///
+/// A limit of -1 means "use the default quota" and is looked up in `defaults`
+/// (the DEFAULT_USER_QUOTAS / DEFAULT_GROUP_QUOTAS block); -2 and unset
+/// defaults are unlimited.
+pub fn quota_rows(
+    owner: &serde_json::Value,
+    defaults: Option<&serde_json::Value>,
+) -> Vec<serde_json::Value> {
+    let mut rows = Vec::new();
+    for (section, element, scope_label, keys) in QUOTA_SECTIONS {
+        let path = format!("{}.{}[*]", section, element);
+        for entry in collect_json_path(owner, &path) {
+            let id = extract_json_value(entry, "ID");
+            let default_entry = defaults.and_then(|d| {
+                collect_json_path(d, &path)
+                    .into_iter()
+                    .find(|e| extract_json_value(e, "ID") == id)
+            });
+            let scope = if scope_label.is_empty() {
+                "-".to_string()
+            } else {
+                format!("{} {}", scope_label, id)
+            };
+            for (key, label) in keys.iter() {
+                let limit = extract_json_value(entry, key);
+                if limit == "-" {
+                    continue;
+                }
+                let limit = match limit.parse::<f64>() {
+                    Ok(-1.0) => default_entry
+                        .map(|d| extract_json_value(d, key))
+                        .filter(|d| d.parse::<f64>().is_ok_and(|d| d >= 0.0)),
+                    Ok(l) if l < 0.0 => None,
+                    _ => Some(limit),
+                };
+                let used = extract_json_value(entry, &format!("{}_USED", key));
+                rows.push(serde_json::json!({
+                    "ID": rows.len().to_string(),
+                    "QUOTA": label,
+                    "SCOPE": scope,
+                    "USED": used,
+                    "LIMIT": limit.as_deref().unwrap_or("unlimited"),
+                    "STATUS": quota_status(&used, limit.as_deref()),
+                }));
+            }
+        }
+    }
+    rows
+}
+
+/// `OK`, `NEAR` (at least 80% used), `FULL` or `UNLIMITED`
+fn quota_status(used: &str, limit: Option<&str>) -> &'static str {
+    let Some(limit) = limit.and_then(|l| l.parse::<f64>().ok()) else {
+        return "UNLIMITED";
+    };
+    let used = used.parse::<f64>().unwrap_or(0.0);
+    if used >= limit {
+        "FULL"
+    } else if used >= limit * QUOTA_NEAR_RATIO {
+        "NEAR"
+    } else {
+        "OK"
+    }
+}
+
+/// Sum VM usage history (`one.vmpool.accounting` records) into one row per VM
+///
+/// Each record is clipped to `from..=to` (unix seconds); records still open
+/// (ETIME 0) run until `to`. Rows carry HOURS, CPU_HOURS and MEMORY_GB_HOURS
+/// based on the CPU and MEMORY (MB) of the VM template.
+pub fn accounting_rows(
+    records: &[&serde_json::Value],
+    from: i64,
+    to: i64,
+) -> Vec<serde_json::Value> {
+    struct Usage {
+        name: String,
+        owner: String,
+        hours: f64,
+        cpu_hours: f64,
+        memory_gb_hours: f64,
+    }
+
+    let mut usage: Vec<(String, Usage)> = Vec::new();
+    for record in records {
+        let number = |path: &str| {
+            extract_json_value(record, path)
+                .parse::<f64>()
+                .unwrap_or(0.0)
+        };
+        let start = (number("STIME") as i64).max(from);
+        let end = match number("ETIME") as i64 {
+            0 => to,
+            etime => etime.min(to),
+        };
+        let hours = (end - start).max(0) as f64 / 3600.0;
+
+        let id = extract_json_value(record, "OID");
+        let index = match usage.iter().position(|(vm, _)| *vm == id) {
+            Some(index) => index,
+            None => {
+                usage.push((
+                    id,
+                    Usage {
+                        name: extract_json_value(record, "VM.NAME"),
+                        owner: extract_json_value(record, "VM.UNAME"),
+                        hours: 0.0,
+                        cpu_hours: 0.0,
+                        memory_gb_hours: 0.0,
+                    },
+                ));
+                usage.len() - 1
+            }
+        };
+        let vm = &mut usage[index].1;
+        vm.hours += hours;
+        vm.cpu_hours += number("VM.TEMPLATE.CPU") * hours;
+        vm.memory_gb_hours += number("VM.TEMPLATE.MEMORY") / 1024.0 * hours;
+    }
+
+    usage
+        .into_iter()
+        .map(|(id, vm)| {
+            serde_json::json!({
+                "ID": id,
+                "NAME": vm.name,
+                "UNAME": vm.owner,
+                "HOURS": format!("{:.1}", vm.hours),
+                "CPU_HOURS": format!("{:.1}", vm.cpu_hours),
+                "MEMORY_GB_HOURS": format!("{:.1}", vm.memory_gb_hours),
+            })
+        })
+        .collect()
+}
+
+/// Whether a flag field is set, accepting OpenNebula's encodings
+///
+/// Numbers are set when non-zero, strings when `1`/`true`/`yes`/`on`
+/// (case-insensitive), and objects such as `LOCK` when present and non-empty.
+pub fn is_flag_set(value: Option<&serde_json::Value>) -> bool {
+    use serde_json::Value;
+    match value {
+        Some(Value::Bool(b)) => *b,
+        Some(Value::Number(n)) => n.as_f64().is_some_and(|n| n != 0.0),
+        Some(Value::String(s)) => matches!(
+            s.trim().to_ascii_lowercase().as_str(),
+            "1" | "true" | "yes" | "on"
+        ),
+        Some(Value::Object(map)) => !map.is_empty(),
+        Some(Value::Array(items)) => !items.is_empty(),
+        Some(Value::Null) | None => false,
+    }
+}
+
+/// Address of each NIC under `nics` (a NIC object or a list of them)
+///
+/// A NIC without an IPv4 `IP` shows its `IP6_GLOBAL` or `IP6` address; NICs
+/// with no address at all are skipped.
+pub fn nic_ips(nics: Option<&serde_json::Value>) -> Vec<String> {
+    let nics: Vec<&serde_json::Value> = match nics {
+        Some(serde_json::Value::Array(items)) => items.iter().collect(),
+        Some(nic @ serde_json::Value::Object(_)) => vec![nic],
+        _ => Vec::new(),
+    };
+    nics.into_iter()
+        .filter_map(|nic| {
+            ["IP", "IP6_GLOBAL", "IP6"]
+                .iter()
+                .map(|key| extract_json_value(nic, key))
+                .find(|ip| ip != "-" && !ip.is_empty())
+        })
+        .collect()
+}
+
 /// Format OpenNebula host state code to string
 pub fn format_host_state(state: i32) -> String {
     match state {
@@ -196,3 +585,704 @@ pub fn format_datastore_state(state: i32) -> String {
         _ => format!("UNKNOWN({})", state),
     }
 }
+
+/// Format OpenNebula marketplace state code to string
+pub fn format_market_state(state: i32) -> String {
+    match state {
+        0 => "ENABLED".to_string(),
+        1 => "DISABLED".to_string(),
+        _ => format!("UNKNOWN({})", state),
+    }
+}
+
+/// Format OpenNebula marketplace app state code to string
+pub fn format_marketapp_state(state: i32) -> String {
+    match state {
+        0 => "INIT".to_string(),
+        1 => "READY".to_string(),
+        2 => "LOCKED".to_string(),
+        3 => "ERROR".to_string(),
+        4 => "DISABLED".to_string(),
+        _ => format!("UNKNOWN({})", state),
+    }
+}
+
+/// ACL id selectors, stored above the 32-bit object id in USER, RESOURCE and ZONE
+const ACL_INDIVIDUAL_ID: u64 = 0x1_0000_0000;
+const ACL_GROUP_ID: u64 = 0x2_0000_0000;
+const ACL_ALL_ID: u64 = 0x4_0000_0000;
+const ACL_CLUSTER_ID: u64 = 0x8_0000_0000;
+
+/// ACL resource type bits (OpenNebula's PoolObjectSQL::ObjectType)
+const ACL_RESOURCE_TYPES: &[(u64, &str)] = &[
+    (0x0000_0010_0000_0000, "VM"),
+    (0x0000_0020_0000_0000, "HOST"),
+    (0x0000_0040_0000_0000, "NET"),
+    (0x0000_0080_0000_0000, "IMAGE"),
+    (0x0000_0100_0000_0000, "USER"),
+    (0x0000_0200_0000_0000, "TEMPLATE"),
+    (0x0000_0400_0000_0000, "GROUP"),
+    (0x0000_1000_0000_0000, "DATASTORE"),
+    (0x0000_2000_0000_0000, "CLUSTER"),
+    (0x0000_4000_0000_0000, "DOCUMENT"),
+    (0x0000_8000_0000_0000, "ZONE"),
+    (0x0001_0000_0000_0000, "SECGROUP"),
+    (0x0002_0000_0000_0000, "VDC"),
+    (0x0004_0000_0000_0000, "VROUTER"),
+    (0x0008_0000_0000_0000, "MARKETPLACE"),
+    (0x0010_0000_0000_0000, "MARKETPLACEAPP"),
+    (0x0020_0000_0000_0000, "VMGROUP"),
+    (0x0040_0000_0000_0000, "VNTEMPLATE"),
+    (0x0080_0000_0000_0000, "HOOK"),
+    (0x0100_0000_0000_0000, "BACKUPJOB"),
+];
+
+/// ACL rights bits
+const ACL_RIGHTS: &[(u64, &str)] = &[
+    (0x1, "USE"),
+    (0x2, "MANAGE"),
+    (0x4, "ADMIN"),
+    (0x8, "CREATE"),
+];
+
+/// Format an ACL id selector: `#5` (user/object), `@1` (group), `%0` (cluster) or `*`
+fn format_acl_id(mask: u64) -> String {
+    let id = mask & 0xFFFF_FFFF;
+    if mask & ACL_ALL_ID != 0 {
+        "*".to_string()
+    } else if mask & ACL_INDIVIDUAL_ID != 0 {
+        format!("#{}", id)
+    } else if mask & ACL_GROUP_ID != 0 {
+        format!("@{}", id)
+    } else if mask & ACL_CLUSTER_ID != 0 {
+        format!("%{}", id)
+    } else {
+        "??".to_string()
+    }
+}
+
+/// Names of the bits set in `mask`, joined with `+`
+fn format_acl_bits(mask: u64, bits: &[(u64, &str)]) -> String {
+    let names: Vec<&str> = bits
+        .iter()
+        .filter(|(bit, _)| mask & bit != 0)
+        .map(|(_, name)| *name)
+        .collect();
+    if names.is_empty() {
+        "-".to_string()
+    } else {
+        names.join("+")
+    }
+}
+
+/// Format an ACL RESOURCE mask, e.g. `VM+NET/@1`
+pub fn format_acl_resource(mask: u64) -> String {
+    format!(
+        "{}/{}",
+        format_acl_bits(mask, ACL_RESOURCE_TYPES),
+        format_acl_id(mask)
+    )
+}
+
+/// Format an ACL RIGHTS mask, e.g. `USE+MANAGE`
+pub fn format_acl_rights(mask: u64) -> String {
+    format_acl_bits(mask, ACL_RIGHTS)
+}
+
+/// Decode a packed ACL field (hex, as returned by one.acl.info) using an `acl_*` format
+pub fn format_acl(format: ColumnFormat, value: &str) -> Option<String> {
+    let mask = u64::from_str_radix(value, 16).ok()?;
+    match format {
+        ColumnFormat::AclUser | ColumnFormat::AclZone => Some(format_acl_id(mask)),
+        ColumnFormat::AclResource => Some(format_acl_resource(mask)),
+        ColumnFormat::AclRights => Some(format_acl_rights(mask)),
+        _ => None,
+    }
+}
+
+/// An ACL rule in `oneacl` syntax, e.g. `@1 VM+NET/* USE+MANAGE #0`
+pub fn format_acl_rule(acl: &serde_json::Value) -> Option<String> {
+    let field = |name: &str, format| format_acl(format, &extract_json_value(acl, name));
+    Some(format!(
+        "{} {} {} {}",
+        field("USER", ColumnFormat::AclUser)?,
+        field("RESOURCE", ColumnFormat::AclResource)?,
+        field("RIGHTS", ColumnFormat::AclRights)?,
+        field("ZONE", ColumnFormat::AclZone)?
+    ))
+}
+
+/// ID of the host a VM is (or was last) placed on, from its newest history record
+pub fn current_host_id(vm: &serde_json::Value) -> Option<String> {
+    extract_json_values(vm, "HISTORY_RECORDS.HISTORY[*].HID").pop()
+}
+
+/// Name of the host a VM is (or was last) placed on, from its newest history record
+pub fn current_host_name(vm: &serde_json::Value) -> Option<String> {
+    extract_json_values(vm, "HISTORY_RECORDS.HISTORY[*].HOSTNAME").pop()
+}
+
+/// Format OpenNebula lock level (`LOCK.LOCKED`) to string
+pub fn format_lock_level(level: i32) -> String {
+    match level {
+        1 => "USE".to_string(),
+        2 => "MANAGE".to_string(),
+        3 => "ADMIN".to_string(),
+        4 => "ALL".to_string(),
+        _ => format!("UNKNOWN({})", level),
+    }
+}
+
+/// Lock level of a locked resource, or `None` if it has no `LOCK` block
+pub fn lock_level(item: &serde_json::Value) -> Option<String> {
+    let lock = item.get("LOCK")?;
+    if !is_flag_set(Some(lock)) {
+        return None;
+    }
+    let level = extract_json_value(lock, "LOCKED");
+    Some(format_state(ColumnFormat::LockLevel, &level).unwrap_or(level))
+}
+
+/// Relative time like `2d 4h ago` (or `in 3h` for future times), using the two
+/// largest units; 0 and negative timestamps mean "not set" and render as `-`
+pub fn format_epoch_relative(secs: i64, now: i64) -> String {
+    if secs <= 0 {
+        return "-".to_string();
+    }
+    let diff = now - secs;
+    let total = diff.unsigned_abs();
+    if total < 60 {
+        return "just now".to_string();
+    }
+    let units = [(86_400, "d"), (3_600, "h"), (60, "m"), (1, "s")];
+    let mut remaining = total;
+    let parts: Vec<String> = units
+        .iter()
+        .filter_map(|&(size, suffix)| {
+            let count = remaining / size;
+            remaining %= size;
+            (count > 0).then(|| format!("{}{}", count, suffix))
+        })
+        .take(2)
+        .collect();
+    let text = parts.join(" ");
+    if diff >= 0 {
+        format!("{} ago", text)
+    } else {
+        format!("in {}", text)
+    }
+}
+
+/// Local date and time like `2026-10-17 14:05`; 0 and negative timestamps render as `-`
+pub fn format_epoch_datetime(secs: i64) -> String {
+    if secs <= 0 {
+        return "-".to_string();
+    }
+    chrono::DateTime::from_timestamp(secs, 0)
+        .map(|t| {
+            t.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// Width of usage bars rendered in table cells
+const USAGE_BAR_WIDTH: usize = 10;
+
+/// Marker for set values in `"flag"` columns
+const DEFAULT_FLAG_SYMBOL: &str = "✓";
+
+/// Cell text of a column, with its `format` applied (state names, sizes, usage bars, flags)
+pub fn format_display_value(item: &serde_json::Value, col: &ColumnDef) -> String {
+    if col.format == Some(ColumnFormat::Flag) {
+        return if is_flag_set(get_json_path(item, &col.json_path)) {
+            col.flag_symbol
+                .clone()
+                .unwrap_or_else(|| DEFAULT_FLAG_SYMBOL.to_string())
+        } else {
+            String::new()
+        };
+    }
+
+    if col.format == Some(ColumnFormat::NicIps) {
+        let ips = nic_ips(get_json_path(item, &col.json_path));
+        return if ips.is_empty() {
+            "-".to_string()
+        } else {
+            ips.join(", ")
+        };
+    }
+
+    let raw_value = extract_json_value(item, &col.json_path);
+    let value = raw_value.as_str();
+    if let Some(format) = col.format {
+        if let Some(state) = format_state(format, value) {
+            return state;
+        }
+        match format {
+            ColumnFormat::Bytes => {
+                if let Ok(bytes) = value.parse::<u64>() {
+                    return format_bytes(bytes);
+                }
+            }
+            ColumnFormat::Kb => {
+                if let Ok(kb) = value.parse::<u64>() {
+                    return format_bytes(kb.saturating_mul(1024));
+                }
+            }
+            ColumnFormat::Mb => {
+                if let Ok(mb) = value.parse::<u64>() {
+                    return format_bytes(mb.saturating_mul(1024 * 1024));
+                }
+            }
+            ColumnFormat::HostCpu
+            | ColumnFormat::HostMem
+            | ColumnFormat::HostCpuAlloc
+            | ColumnFormat::HostMemAlloc => {
+                if let Some(text) = format_host_share(item, col, format) {
+                    return text;
+                }
+            }
+            ColumnFormat::EpochRelative => {
+                if let Ok(secs) = value.parse::<i64>() {
+                    return format_epoch_relative(secs, chrono::Utc::now().timestamp());
+                }
+            }
+            ColumnFormat::EpochDatetime => {
+                if let Ok(secs) = value.parse::<i64>() {
+                    return format_epoch_datetime(secs);
+                }
+            }
+            ColumnFormat::QuotaUsage => {
+                let used = extract_json_value(item, "USED").parse::<f64>();
+                let limit = extract_json_value(item, "LIMIT").parse::<f64>();
+                if let (Ok(used), Ok(limit)) = (used, limit) {
+                    return format_usage_bar(used, limit, USAGE_BAR_WIDTH);
+                }
+            }
+            ColumnFormat::AclUser
+            | ColumnFormat::AclResource
+            | ColumnFormat::AclRights
+            | ColumnFormat::AclZone => {
+                if let Some(text) = format_acl(format, value) {
+                    return text;
+                }
+            }
+            _ => {}
+        }
+    }
+    raw_value
+}
+
+/// Format host capacity columns whose `json_path` points at `HOST_SHARE`
+fn format_host_share(
+    item: &serde_json::Value,
+    col: &ColumnDef,
+    format: ColumnFormat,
+) -> Option<String> {
+    let share = get_json_path(item, &col.json_path)?;
+    match format {
+        ColumnFormat::HostCpu => {
+            let (used, total) = host_share_usage(share, "cpu")?;
+            Some(format_usage_bar(used, total, USAGE_BAR_WIDTH))
+        }
+        ColumnFormat::HostMem => {
+            let (used, total) = host_share_usage(share, "mem")?;
+            Some(format_usage_bar(used, total, USAGE_BAR_WIDTH))
+        }
+        ColumnFormat::HostCpuAlloc => {
+            let (used, total) = host_share_usage(share, "cpu")?;
+            Some(format!("{:.1}/{:.1}", used / 100.0, total / 100.0))
+        }
+        ColumnFormat::HostMemAlloc => {
+            let (used, total) = host_share_usage(share, "mem")?;
+            Some(format!(
+                "{}/{}",
+                format_bytes((used as u64).saturating_mul(1024)),
+                format_bytes((total as u64).saturating_mul(1024))
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// Whether a column's displayed value contains `needle` (already lowercased)
+pub fn column_matches(item: &serde_json::Value, col: &ColumnDef, needle: &str) -> bool {
+    format_display_value(item, col)
+        .to_lowercase()
+        .contains(needle)
+}
+
+/// Split a `header:value` filter into the column it targets and the value.
+/// None when there's no colon or the prefix names no column (e.g. a MAC address).
+pub fn parse_column_filter<'a>(
+    filter: &'a str,
+    columns: &'a [ColumnDef],
+) -> Option<(&'a ColumnDef, &'a str)> {
+    let (header, value) = filter.split_once(':')?;
+    let col = columns
+        .iter()
+        .find(|col| col.header.eq_ignore_ascii_case(header.trim()))?;
+    Some((col, value.trim()))
+}
+
+/// Order two items by a column: state codes by their name, numbers numerically,
+/// anything else case-insensitively; numbers sort before text
+pub fn compare_column_values(
+    a: &serde_json::Value,
+    b: &serde_json::Value,
+    col: &ColumnDef,
+) -> std::cmp::Ordering {
+    use std::cmp::Ordering;
+
+    match (column_sort_key(a, col), column_sort_key(b, col)) {
+        (SortKey::Number(x), SortKey::Number(y)) => x.partial_cmp(&y).unwrap_or(Ordering::Equal),
+        (SortKey::Number(_), SortKey::Text(_)) => Ordering::Less,
+        (SortKey::Text(_), SortKey::Number(_)) => Ordering::Greater,
+        (SortKey::Text(x), SortKey::Text(y)) => x.cmp(&y),
+    }
+}
+
+enum SortKey {
+    Number(f64),
+    Text(String),
+}
+
+fn column_sort_key(item: &serde_json::Value, col: &ColumnDef) -> SortKey {
+    // Order by the first address, numerically so 10.0.0.9 comes before 10.0.0.10
+    if col.format == Some(ColumnFormat::NicIps) {
+        let first = nic_ips(get_json_path(item, &col.json_path))
+            .into_iter()
+            .next()
+            .unwrap_or_default();
+        return match first.parse::<std::net::Ipv4Addr>() {
+            Ok(ip) => SortKey::Number(u32::from(ip) as f64),
+            Err(_) => SortKey::Text(first.to_lowercase()),
+        };
+    }
+
+    let raw = extract_json_value(item, &col.json_path);
+    if let Some(state) = col.format.and_then(|f| format_state(f, &raw)) {
+        return SortKey::Text(state.to_lowercase());
+    }
+    match raw.parse::<f64>() {
+        Ok(n) => SortKey::Number(n),
+        Err(_) => SortKey::Text(raw.to_lowercase()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(json_path: &str, format: Option<ColumnFormat>) -> ColumnDef {
+        ColumnDef {
+            header: "COL".to_string(),
+            json_path: json_path.to_string(),
+            width: 8,
+            color_map: None,
+            format,
+            flag_symbol: None,
+        }
+    }
+
+    #[test]
+    fn test_vm_state_code() {
+        assert_eq!(vm_state_code("running"), Some(3));
+        assert_eq!(vm_state_code("POWEROFF"), Some(8));
+        assert_eq!(vm_state_code("pending"), Some(1));
+        assert_eq!(vm_state_code("7"), Some(7));
+        assert_eq!(vm_state_code("bogus"), None);
+    }
+
+    #[test]
+    fn test_parse_column_filter() {
+        let mut host = column("HISTORY_RECORDS.HISTORY.HOSTNAME", None);
+        host.header = "HOST".to_string();
+        let columns = vec![column("NAME", None), host];
+
+        let (col, value) = parse_column_filter("host:node3", &columns).unwrap();
+        assert_eq!(col.header, "HOST");
+        assert_eq!(value, "node3");
+        assert!(parse_column_filter("node3", &columns).is_none());
+        // Not a header: stays a plain filter
+        assert!(parse_column_filter("02:00:0a", &columns).is_none());
+    }
+
+    #[test]
+    fn test_compare_column_values() {
+        use std::cmp::Ordering;
+
+        let id = column("ID", None);
+        let (nine, ten) = (
+            serde_json::json!({"ID": "9", "STATE": "8"}),
+            serde_json::json!({"ID": "10", "STATE": "3"}),
+        );
+        // Numeric, not lexicographic ("10" < "9" as text)
+        assert_eq!(compare_column_values(&nine, &ten, &id), Ordering::Less);
+
+        // States compare by name: ACTIVE < POWEROFF
+        let state = column("STATE", Some(ColumnFormat::VmState));
+        assert_eq!(
+            compare_column_values(&nine, &ten, &state),
+            Ordering::Greater
+        );
+
+        let name = column("NAME", None);
+        let (a, b) = (
+            serde_json::json!({"NAME": "alpha"}),
+            serde_json::json!({"NAME": "Beta"}),
+        );
+        assert_eq!(compare_column_values(&a, &b, &name), Ordering::Less);
+    }
+
+    #[test]
+    fn test_transitional_lcm_states() {
+        assert!(is_transitional_lcm_state(1)); // PROLOG
+        assert!(is_transitional_lcm_state(8)); // PROLOG_MIGRATE
+        assert!(is_transitional_lcm_state(16)); // HOTPLUG
+        assert!(!is_transitional_lcm_state(0)); // LCM_INIT
+        assert!(!is_transitional_lcm_state(3)); // RUNNING
+        assert!(!is_transitional_lcm_state(15)); // UNKNOWN
+        assert!(!is_transitional_lcm_state(36)); // BOOT_MIGRATE_FAILURE
+        assert!(!is_transitional_lcm_state(999));
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KB");
+        assert_eq!(format_bytes(13_314_398_618), "12.4 GB");
+    }
+
+    #[test]
+    fn test_format_usage_bar() {
+        assert_eq!(format_usage_bar(50.0, 100.0, 4), "[██░░]  50%");
+        assert_eq!(format_usage_bar(300.0, 200.0, 4), "[████] 150%");
+        assert_eq!(format_usage_bar(1.0, 0.0, 4), "[░░░░]   0%");
+    }
+
+    #[test]
+    fn test_host_share_usage() {
+        let share = serde_json::json!({"CPU_USAGE": "250", "MAX_CPU": "800"});
+        assert_eq!(host_share_usage(&share, "cpu"), Some((250.0, 800.0)));
+        assert_eq!(host_share_usage(&share, "mem"), None);
+    }
+
+    #[test]
+    fn test_extract_json_values() {
+        let item = serde_json::json!({
+            "ONE": {"ID": "3"},
+            "MANY": {"ID": ["3", 4]},
+            "NONE": {}
+        });
+        assert_eq!(extract_json_values(&item, "ONE.ID"), vec!["3"]);
+        assert_eq!(extract_json_values(&item, "MANY.ID"), vec!["3", "4"]);
+        assert!(extract_json_values(&item, "NONE.ID").is_empty());
+    }
+
+    #[test]
+    fn test_wildcard_path() {
+        let multi = serde_json::json!({
+            "TEMPLATE": {"NIC": [{"IP": "10.0.0.5"}, {"IP6": "fd00::1"}, {"IP": "192.168.1.9"}]}
+        });
+        let single = serde_json::json!({"TEMPLATE": {"NIC": {"IP": "10.0.0.5"}}});
+        let none = serde_json::json!({"TEMPLATE": {}});
+        assert_eq!(
+            extract_json_value(&multi, "TEMPLATE.NIC[*].IP"),
+            "10.0.0.5, 192.168.1.9"
+        );
+        assert_eq!(
+            extract_json_value(&single, "TEMPLATE.NIC[*].IP"),
+            "10.0.0.5"
+        );
+        assert_eq!(extract_json_value(&none, "TEMPLATE.NIC[*].IP"), "-");
+    }
+
+    #[test]
+    fn test_nic_ips_format() {
+        let col = column("TEMPLATE.NIC", Some(ColumnFormat::NicIps));
+        let multi = serde_json::json!({
+            "TEMPLATE": {"NIC": [
+                {"IP": "10.0.0.10"},
+                {"IP6_GLOBAL": "2001:db8::1", "IP6_LINK": "fe80::1"},
+                {"MAC": "02:00:00:00:00:01"}
+            ]}
+        });
+        let single = serde_json::json!({"TEMPLATE": {"NIC": {"IP": "10.0.0.9"}}});
+        let none = serde_json::json!({"TEMPLATE": {}});
+        assert_eq!(format_display_value(&multi, &col), "10.0.0.10, 2001:db8::1");
+        assert_eq!(format_display_value(&none, &col), "-");
+        assert_eq!(
+            compare_column_values(&single, &multi, &col),
+            std::cmp::Ordering::Less
+        );
+    }
+
+    #[test]
+    fn test_current_host_id() {
+        let migrated = serde_json::json!({
+            "HISTORY_RECORDS": {"HISTORY": [{"HID": "2"}, {"HID": "5"}]}
+        });
+        let single = serde_json::json!({"HISTORY_RECORDS": {"HISTORY": {"HID": "2"}}});
+        assert_eq!(current_host_id(&migrated).as_deref(), Some("5"));
+        assert_eq!(current_host_id(&single).as_deref(), Some("2"));
+        assert_eq!(
+            current_host_id(&serde_json::json!({"HISTORY_RECORDS": {}})),
+            None
+        );
+    }
+
+    #[test]
+    fn test_marketapp_state() {
+        assert_eq!(
+            format_state(ColumnFormat::MarketappState, "1").as_deref(),
+            Some("READY")
+        );
+        assert_eq!(
+            format_state(ColumnFormat::MarketState, "1").as_deref(),
+            Some("DISABLED")
+        );
+        assert_eq!(format_marketapp_state(9), "UNKNOWN(9)");
+    }
+
+    #[test]
+    fn test_lock_level() {
+        let locked = serde_json::json!({"LOCK": {"LOCKED": "2", "OWNER": "0"}});
+        assert_eq!(lock_level(&locked).as_deref(), Some("MANAGE"));
+        assert_eq!(lock_level(&serde_json::json!({"NAME": "vm"})), None);
+    }
+
+    #[test]
+    fn test_format_acl() {
+        use serde_json::json;
+        let acl = json!({
+            "ID": "0",
+            "USER": "200000001",
+            "RESOURCE": "7400000000",
+            "RIGHTS": "3",
+            "ZONE": "400000000"
+        });
+        assert_eq!(
+            format_acl_rule(&acl).as_deref(),
+            Some("@1 VM+HOST+NET/* USE+MANAGE *")
+        );
+        assert_eq!(
+            format_acl(ColumnFormat::AclUser, "100000005").as_deref(),
+            Some("#5")
+        );
+        assert_eq!(
+            format_acl(ColumnFormat::AclResource, "8100800000002").as_deref(),
+            Some("DATASTORE+MARKETPLACE/%2")
+        );
+        assert_eq!(format_acl(ColumnFormat::AclRights, "not-hex"), None);
+    }
+
+    #[test]
+    fn test_quota_rows() {
+        use serde_json::json;
+        let user = json!({
+            "VM_QUOTA": {"VM": {
+                "VMS": "10", "VMS_USED": "9",
+                "CPU": "-1", "CPU_USED": "2",
+                "MEMORY": "-2", "MEMORY_USED": "4096"
+            }},
+            "DATASTORE_QUOTA": {"DATASTORE": [
+                {"ID": "1", "IMAGES": "5", "IMAGES_USED": "1", "SIZE": "100", "SIZE_USED": "100"}
+            ]},
+            "NETWORK_QUOTA": {}
+        });
+        let defaults = json!({"VM_QUOTA": {"VM": {"CPU": "4"}}});
+        let rows = quota_rows(&user, Some(&defaults));
+        let row = |quota: &str, scope: &str| {
+            rows.iter()
+                .find(|r| r["QUOTA"] == quota && r["SCOPE"] == scope)
+                .map(|r| (r["LIMIT"].as_str().unwrap(), r["STATUS"].as_str().unwrap()))
+        };
+        assert_eq!(rows.len(), 5);
+        assert_eq!(row("VMs", "-"), Some(("10", "NEAR")));
+        assert_eq!(row("CPU", "-"), Some(("4", "OK")));
+        assert_eq!(row("Memory (MB)", "-"), Some(("unlimited", "UNLIMITED")));
+        assert_eq!(row("Size (MB)", "Datastore 1"), Some(("100", "FULL")));
+    }
+
+    #[test]
+    fn test_accounting_rows() {
+        use serde_json::json;
+        let vm =
+            json!({"NAME": "web", "UNAME": "alice", "TEMPLATE": {"CPU": "2", "MEMORY": "2048"}});
+        let records = [
+            json!({"OID": "7", "STIME": "0", "ETIME": "7200", "VM": vm}),
+            json!({"OID": "7", "STIME": "10800", "ETIME": "0", "VM": vm}),
+        ];
+        let records: Vec<&serde_json::Value> = records.iter().collect();
+        let rows = accounting_rows(&records, 3600, 14400);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0]["ID"], "7");
+        assert_eq!(rows[0]["HOURS"], "2.0");
+        assert_eq!(rows[0]["CPU_HOURS"], "4.0");
+        assert_eq!(rows[0]["MEMORY_GB_HOURS"], "4.0");
+    }
+
+    #[test]
+    fn test_usage_color() {
+        use serde_json::json;
+        assert_eq!(usage_color(0.0), [0, 255, 0]);
+        assert_eq!(usage_color(0.5), [255, 255, 0]);
+        assert_eq!(usage_color(1.5), [255, 0, 0]);
+
+        let host = json!({"HOST_SHARE": {"CPU_USAGE": "150", "MAX_CPU": "200"}});
+        let col = column("HOST_SHARE", Some(ColumnFormat::HostCpu));
+        assert_eq!(usage_ratio(&host, &col), Some(0.75));
+        assert_eq!(usage_ratio(&host, &column("HOST_SHARE", None)), None);
+    }
+
+    #[test]
+    fn test_format_epoch() {
+        let now = 1_700_000_000;
+        assert_eq!(format_epoch_relative(now - 30, now), "just now");
+        assert_eq!(
+            format_epoch_relative(now - (2 * 86_400 + 4 * 3_600 + 59), now),
+            "2d 4h ago"
+        );
+        assert_eq!(format_epoch_relative(now - 3 * 3_600, now), "3h ago");
+        assert_eq!(format_epoch_relative(now + 90, now), "in 1m 30s");
+        assert_eq!(format_epoch_relative(0, now), "-");
+        assert_eq!(format_epoch_relative(-1, now), "-");
+        assert_eq!(format_epoch_datetime(-1), "-");
+        assert_eq!(format_epoch_datetime(now).len(), "2023-11-14 22:13".len());
+    }
+
+    #[test]
+    fn test_size_formats() {
+        use serde_json::json;
+        let vm = json!({"TEMPLATE": {"MEMORY": "2048"}, "SIZE": "1536", "BAD": "n/a"});
+        let mb = |path: &str| format_display_value(&vm, &column(path, Some(ColumnFormat::Mb)));
+        assert_eq!(mb("TEMPLATE.MEMORY"), format_bytes(2 * 1024 * 1024 * 1024));
+        assert_eq!(
+            format_display_value(&vm, &column("SIZE", Some(ColumnFormat::Bytes))),
+            format_bytes(1536)
+        );
+        assert_eq!(mb("BAD"), "n/a");
+    }
+
+    #[test]
+    fn test_is_flag_set() {
+        use serde_json::json;
+        let item = json!({
+            "PERSISTENT": "1",
+            "RESCHED": "0",
+            "SHARED": "YES",
+            "LOCK": {"LOCKED": "1"},
+            "EMPTY": {}
+        });
+        assert!(is_flag_set(get_json_path(&item, "PERSISTENT")));
+        assert!(!is_flag_set(get_json_path(&item, "RESCHED")));
+        assert!(is_flag_set(get_json_path(&item, "SHARED")));
+        assert!(is_flag_set(get_json_path(&item, "LOCK")));
+        assert!(!is_flag_set(get_json_path(&item, "EMPTY")));
+        assert!(!is_flag_set(get_json_path(&item, "MISSING")));
+        assert!(is_flag_set(Some(&json!(true))));
+    }
+}
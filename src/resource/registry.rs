@@ -8,13 +8,13 @@ use serde_json::Value;
 use std::collections::HashMap;
 use std::sync::OnceLock;
 
-/// Embedded resource JSON files (compiled into the binary)
-const RESOURCE_FILES: &[&str] = &[
-    include_str!("../resources/common.json"),
-    include_str!("../resources/compute.json"),
-    include_str!("../resources/storage.json"),
-    include_str!("../resources/network.json"),
-    include_str!("../resources/system.json"),
+/// Embedded resource JSON files (compiled into the binary), by file name
+const RESOURCE_FILES: &[(&str, &str)] = &[
+    ("common.json", include_str!("../resources/common.json")),
+    ("compute.json", include_str!("../resources/compute.json")),
+    ("storage.json", include_str!("../resources/storage.json")),
+    ("network.json", include_str!("../resources/network.json")),
+    ("system.json", include_str!("../resources/system.json")),
 ];
 
 /// Color definition from JSON
@@ -33,7 +33,40 @@ pub struct ColumnDef {
     #[serde(default)]
     pub color_map: Option<String>,
     #[serde(default)]
-    pub format: Option<String>,
+    pub format: Option<ColumnFormat>,
+    /// Marker shown for set values of a `"flag"` column (defaults to ✓)
+    #[serde(default)]
+    pub flag_symbol: Option<String>,
+}
+
+/// How a column's raw value is displayed (`"format"` in the resource JSON)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColumnFormat {
+    VmState,
+    LcmState,
+    HostState,
+    ImageState,
+    DatastoreState,
+    MarketState,
+    MarketappState,
+    LockLevel,
+    NicIps,
+    QuotaUsage,
+    EpochRelative,
+    EpochDatetime,
+    AclUser,
+    AclResource,
+    AclRights,
+    AclZone,
+    Bytes,
+    Kb,
+    Mb,
+    Flag,
+    HostCpu,
+    HostMem,
+    HostCpuAlloc,
+    HostMemAlloc,
 }
 
 /// Sub-resource definition from JSON
@@ -44,6 +77,10 @@ pub struct SubResourceDef {
     pub shortcut: String,
     pub parent_id_field: String,
     pub filter_param: String,
+    /// Path on child items matched against the parent id, for pools the API
+    /// can't filter (applied client-side instead of sending `filter_param`)
+    #[serde(default)]
+    pub filter_field: Option<String>,
 }
 
 /// Confirmation config for actions
@@ -55,12 +92,64 @@ pub struct ConfirmConfig {
     pub default_yes: bool,
     #[serde(default)]
     pub destructive: bool,
+    /// Destructive actions only: the user must type the resource name
+    /// (or "yes" for marked items) before the action runs
+    #[serde(default)]
+    pub require_typed: bool,
+}
+
+/// Restricts an action to items in certain states
+#[derive(Debug, Clone, Deserialize)]
+pub struct StateGuard {
+    /// JSON path of the state field (e.g. "STATE")
+    pub field: String,
+    /// State format used to name the raw code (e.g. "vm_state")
+    #[serde(default)]
+    pub format: Option<ColumnFormat>,
+    /// States the action is allowed in (empty = any)
+    #[serde(default)]
+    pub allowed: Vec<String>,
+    /// States the action is blocked in
+    #[serde(default)]
+    pub blocked: Vec<String>,
+}
+
+impl StateGuard {
+    /// Return a warning message if the item's state forbids the action
+    pub fn check(&self, item: &Value, action_name: &str) -> Option<String> {
+        let raw = super::extract_json_value(item, &self.field);
+        let state = self
+            .format
+            .and_then(|f| super::format_state(f, &raw))
+            .unwrap_or(raw);
+
+        let matches = |patterns: &[String]| patterns.iter().any(|p| state_matches(p, &state));
+        if !self.allowed.is_empty() && !matches(&self.allowed) {
+            return Some(format!(
+                "{} requires state {} (current: {})",
+                action_name,
+                self.allowed.join("/"),
+                state
+            ));
+        }
+        if matches(&self.blocked) {
+            return Some(format!("{} is not allowed in state {}", action_name, state));
+        }
+        None
+    }
+}
+
+/// Match a state against a pattern; a leading `*` matches by suffix
+fn state_matches(pattern: &str, state: &str) -> bool {
+    match pattern.strip_prefix('*') {
+        Some(suffix) => state.ends_with(suffix),
+        None => pattern == state,
+    }
 }
 
 /// Action definition from JSON
 #[derive(Debug, Clone, Deserialize)]
 pub struct ActionDef {
-    #[allow(dead_code)]
     pub key: String,
     pub display_name: String,
     #[serde(default)]
@@ -73,9 +162,127 @@ pub struct ActionDef {
     pub needs_confirm: bool,
     #[serde(default)]
     pub confirm: Option<ConfirmConfig>,
+    #[serde(default)]
+    pub state_guard: Option<StateGuard>,
+    /// Pick a second parameter from a list before confirming
+    #[serde(default)]
+    pub select: Option<SelectConfig>,
+    /// Ask for a text value before running (replaces the yes/no confirm)
+    #[serde(default)]
+    pub input: Option<InputConfig>,
+    /// Fixed parameters sent with the action (e.g. `{"live": true}`)
+    #[serde(default)]
+    pub params: serde_json::Map<String, Value>,
+    /// Parameters read from the selected item, as param -> path (e.g. `{"vm_id": "VM_ID"}`)
+    #[serde(default)]
+    pub item_params: HashMap<String, String>,
+    /// Resource shown once the action succeeds (e.g. `one-vms` after instantiating)
+    #[serde(default)]
+    pub navigate_to: Option<String>,
+    /// Leave the action out of the key hints while the selected item's state blocks it
+    #[serde(default)]
+    pub hide_blocked: bool,
+    /// Create a new resource: the action runs without a selected item
+    #[serde(default)]
+    pub creates: bool,
+}
+
+/// List picker for actions needing a second parameter (target host, datastore, ...)
+#[derive(Debug, Clone, Deserialize)]
+pub struct SelectConfig {
+    /// Resource whose items are offered (e.g. `one-hosts`)
+    #[serde(default)]
+    pub resource: Option<String>,
+    /// Fixed choices offered instead of a resource's items (e.g. lock levels)
+    #[serde(default)]
+    pub options: Vec<SelectOption>,
+    /// Param receiving the chosen item's ID
+    pub param: String,
+    /// What is picked, for the title and confirm message (e.g. "host")
+    pub label: String,
+    /// Word joining the confirm message and the choice (e.g. "from"); "to" by default
+    #[serde(default)]
+    pub preposition: Option<String>,
+    /// Leave the selected VM's current host out of the list
+    #[serde(default)]
+    pub exclude_current_host: bool,
+    /// Only offer items whose field has this value, as path -> value
+    /// (e.g. `{"TYPE": "1"}` for system datastores)
+    #[serde(default)]
+    pub filter: HashMap<String, String>,
+    /// Choice listed before the resource's items (e.g. the default datastore, `-1`)
+    #[serde(default)]
+    pub default_option: Option<SelectOption>,
+    /// Picker shown once this one is chosen (e.g. a datastore after the host)
+    #[serde(default)]
+    pub then: Option<Box<SelectConfig>>,
+}
+
+impl SelectConfig {
+    /// Params set by this picker and the pickers chained after it
+    pub fn params(&self) -> Vec<&str> {
+        let mut params = vec![self.param.as_str()];
+        if let Some(next) = &self.then {
+            params.extend(next.params());
+        }
+        params
+    }
+
+    /// Client-side filters keeping only the items matching `filter`
+    pub fn filters(&self) -> Vec<ResourceFilter> {
+        self.filter
+            .iter()
+            .map(|(path, value)| ResourceFilter::client_side(path, path, vec![value.clone()]))
+            .collect()
+    }
+}
+
+/// A fixed choice of a select list
+#[derive(Debug, Clone, Deserialize)]
+pub struct SelectOption {
+    pub id: i64,
+    pub name: String,
+}
+
+/// Text prompt for actions needing typed values (a new name, a new CPU/MEMORY size, ...)
+#[derive(Debug, Clone, Deserialize)]
+pub struct InputConfig {
+    /// One text field per param, in display order
+    pub fields: Vec<InputFieldConfig>,
+    /// Ask for a yes/no confirmation once the values are typed
+    #[serde(default)]
+    pub confirm: bool,
+    /// Picker shown once the values are typed (e.g. a target datastore)
+    #[serde(default)]
+    pub then: Option<SelectConfig>,
+}
+
+/// A single field of an input prompt
+#[derive(Debug, Clone, Deserialize)]
+pub struct InputFieldConfig {
+    /// Param receiving the typed value
+    pub param: String,
+    /// Prompt shown above the text field
+    pub prompt: String,
+    /// Item path used to pre-fill the field (e.g. `NAME`)
+    #[serde(default)]
+    pub initial_field: Option<String>,
+    /// Checkbox toggled with Space instead of a text field, sent as a boolean
+    #[serde(default)]
+    pub toggle: bool,
+    /// Secret value (e.g. a password): echoed as `*` and wiped once sent
+    #[serde(default)]
+    pub masked: bool,
 }
 
 impl ActionDef {
+    /// Check the action's state guard against an item
+    pub fn check_state(&self, item: &Value) -> Option<String> {
+        self.state_guard
+            .as_ref()
+            .and_then(|guard| guard.check(item, &self.display_name))
+    }
+
     pub fn get_confirm_config(&self) -> Option<ConfirmConfig> {
         if let Some(ref config) = self.confirm {
             Some(config.clone())
@@ -84,6 +291,7 @@ impl ActionDef {
                 message: Some(self.display_name.clone()),
                 default_yes: false,
                 destructive: false,
+                require_typed: false,
             })
         } else {
             None
@@ -114,6 +322,51 @@ pub struct ResourceDef {
     pub detail_sdk_method: Option<String>,
     #[serde(default)]
     pub detail_sdk_method_params: Value,
+    /// Paths matched by the `/` filter (defaults to name and id)
+    #[serde(default)]
+    pub filter_fields: Vec<String>,
+    /// Items per page for pools taking `start`/`end` (unpaged when unset)
+    #[serde(default)]
+    pub page_size: Option<u32>,
+}
+
+impl ResourceDef {
+    /// Paths the `/` filter matches against
+    pub fn filter_paths(&self) -> Vec<&str> {
+        if self.filter_fields.is_empty() {
+            vec![self.name_field.as_str(), self.id_field.as_str()]
+        } else {
+            self.filter_fields.iter().map(String::as_str).collect()
+        }
+    }
+
+    /// Whether any filter path or displayed column of an item contains `needle`
+    /// (already lowercased)
+    pub fn matches_filter(&self, item: &Value, needle: &str) -> bool {
+        self.filter_paths().iter().any(|path| {
+            super::extract_json_value(item, path)
+                .to_lowercase()
+                .contains(needle)
+        }) || self
+            .columns
+            .iter()
+            .any(|col| super::column_matches(item, col, needle))
+    }
+
+    /// Filter paths and displayed `columns` of an item joined by tabs, for
+    /// regex filters that span fields
+    pub fn row_text(&self, item: &Value, columns: &[ColumnDef]) -> String {
+        self.filter_paths()
+            .iter()
+            .map(|path| super::extract_json_value(item, path))
+            .chain(
+                columns
+                    .iter()
+                    .map(|col| super::format_display_value(item, col)),
+            )
+            .collect::<Vec<_>>()
+            .join("\t")
+    }
 }
 
 /// Resource filter for parameterized queries
@@ -121,6 +374,8 @@ pub struct ResourceDef {
 pub struct ResourceFilter {
     pub name: String,
     pub values: Vec<String>,
+    /// Item path to match client-side; `None` sends the filter as a parameter
+    pub field: Option<String>,
 }
 
 impl ResourceFilter {
@@ -128,8 +383,27 @@ impl ResourceFilter {
         Self {
             name: name.to_string(),
             values,
+            field: None,
+        }
+    }
+
+    /// Filter applied to fetched items by matching `field` against `values`
+    pub fn client_side(name: &str, field: &str, values: Vec<String>) -> Self {
+        Self {
+            field: Some(field.to_string()),
+            ..Self::new(name, values)
         }
     }
+
+    /// Whether an item passes this filter (always true for server-side filters)
+    pub fn matches(&self, item: &Value) -> bool {
+        let Some(field) = &self.field else {
+            return true;
+        };
+        super::extract_json_values(item, field)
+            .iter()
+            .any(|v| self.values.contains(v))
+    }
 }
 
 /// Root structure of resources/*.json
@@ -141,6 +415,134 @@ pub struct ResourceConfig {
     pub resources: HashMap<String, ResourceDef>,
 }
 
+/// Resource file as parsed before each resource is checked individually
+#[derive(Deserialize)]
+struct RawResourceFile {
+    #[serde(default)]
+    color_maps: HashMap<String, Vec<ColorDef>>,
+    #[serde(default)]
+    resources: HashMap<String, Value>,
+}
+
+/// Parse one embedded resource file, naming the file and resource on error
+fn parse_resource_file(file: &str, content: &str) -> Result<ResourceConfig, String> {
+    let raw: RawResourceFile =
+        serde_json::from_str(content).map_err(|e| format!("{}: {}", file, e))?;
+
+    let mut resources = HashMap::new();
+    for (key, value) in raw.resources {
+        let resource: ResourceDef = serde_json::from_value(value)
+            .map_err(|e| format!("{}: resource '{}': {}", file, key, e))?;
+        if let Some(problem) = validate_resource(&resource) {
+            return Err(format!("{}: resource '{}': {}", file, key, problem));
+        }
+        resources.insert(key, resource);
+    }
+
+    Ok(ResourceConfig {
+        color_maps: raw.color_maps,
+        resources,
+    })
+}
+
+/// Check the fields serde can't: non-empty ids and well-formed json paths
+fn validate_resource(resource: &ResourceDef) -> Option<String> {
+    let mut paths = vec![
+        ("response_path", resource.response_path.as_str()),
+        ("id_field", resource.id_field.as_str()),
+        ("name_field", resource.name_field.as_str()),
+    ];
+    paths.extend(
+        resource
+            .columns
+            .iter()
+            .map(|c| ("column json_path", c.json_path.as_str())),
+    );
+    paths.extend(
+        resource
+            .filter_fields
+            .iter()
+            .map(|f| ("filter_fields", f.as_str())),
+    );
+    paths.extend(
+        resource
+            .sub_resources
+            .iter()
+            .filter_map(|s| s.filter_field.as_deref())
+            .map(|f| ("filter_field", f)),
+    );
+
+    paths
+        .into_iter()
+        .find(|(_, path)| !is_valid_json_path(path))
+        .map(|(field, path)| format!("invalid {} '{}'", field, path))
+}
+
+/// Whether a dot path is well formed (`A.B[0].C`, `NIC[*].IP`)
+fn is_valid_json_path(path: &str) -> bool {
+    !path.is_empty()
+        && path.split('.').all(|part| match part.split_once('[') {
+            None => !part.is_empty() && !part.contains(']'),
+            Some((key, index)) => {
+                let Some(index) = index.strip_suffix(']') else {
+                    return false;
+                };
+                !key.is_empty()
+                    && (index == "*"
+                        || (!index.is_empty() && index.chars().all(|c| c.is_ascii_digit())))
+            }
+        })
+}
+
+/// List columns and actions referencing unknown color maps, resources or paths
+fn find_unknown_references(config: &ResourceConfig) -> Vec<String> {
+    let mut offenders = Vec::new();
+    for (key, resource) in &config.resources {
+        for col in &resource.columns {
+            if let Some(map) = &col.color_map {
+                if !config.color_maps.contains_key(map) {
+                    offenders.push(format!("{}.{}: color_map '{}'", key, col.header, map));
+                }
+            }
+        }
+        for action in &resource.actions {
+            let selects = std::iter::successors(action.select.as_ref(), |s| s.then.as_deref());
+            for select in selects {
+                match &select.resource {
+                    Some(target) if !config.resources.contains_key(target) => {
+                        offenders.push(format!(
+                            "{}.{}: select resource '{}'",
+                            key, action.key, target
+                        ));
+                    }
+                    None if select.options.is_empty() => {
+                        offenders.push(format!("{}.{}: select without choices", key, action.key));
+                    }
+                    _ => {}
+                }
+            }
+            if let Some(target) = &action.navigate_to {
+                if !config.resources.contains_key(target) {
+                    offenders.push(format!("{}.{}: navigate_to '{}'", key, action.key, target));
+                }
+            }
+            for (param, path) in &action.item_params {
+                if !is_valid_json_path(path) {
+                    offenders.push(format!("{}.{}: item param '{}'", key, action.key, param));
+                }
+            }
+            let input_fields = action.input.iter().flat_map(|i| &i.fields);
+            for path in input_fields.filter_map(|f| f.initial_field.as_ref()) {
+                if !is_valid_json_path(path) {
+                    offenders.push(format!("{}.{}: initial_field '{}'", key, action.key, path));
+                }
+            }
+        }
+    }
+    offenders.sort();
+    offenders
+}
+
 /// Global registry loaded from JSON
 static REGISTRY: OnceLock<ResourceConfig> = OnceLock::new();
 
@@ -152,13 +554,26 @@ pub fn get_registry() -> &'static ResourceConfig {
             resources: HashMap::new(),
         };
 
-        for content in RESOURCE_FILES {
-            let partial: ResourceConfig = serde_json::from_str(content)
-                .unwrap_or_else(|e| panic!("Failed to parse embedded resource JSON: {}", e));
+        for (file, content) in RESOURCE_FILES {
+            let partial = parse_resource_file(file, content)
+                .unwrap_or_else(|e| panic!("Invalid embedded resource JSON: {}", e));
             final_config.color_maps.extend(partial.color_maps);
             final_config.resources.extend(partial.resources);
         }
 
+        let offenders = find_unknown_references(&final_config);
+        if !offenders.is_empty() {
+            tracing::warn!(
+                "Resource registry has unknown references: {}",
+                offenders.join(", ")
+            );
+        }
+        debug_assert!(
+            offenders.is_empty(),
+            "Unknown registry references: {:?}",
+            offenders
+        );
+
         final_config
     })
 }
@@ -183,10 +598,21 @@ pub fn get_color_map(name: &str) -> Option<&'static Vec<ColorDef>> {
 }
 
 /// Get color for a value based on color map name
+///
+/// Exact matches win; a map value starting with `*` matches by suffix
+/// (e.g. `*_FAILURE` matches `BOOT_FAILURE`).
 pub fn get_color_for_value(color_map_name: &str, value: &str) -> Option<[u8; 3]> {
-    get_color_map(color_map_name)?
+    let color_map = get_color_map(color_map_name)?;
+    color_map
         .iter()
         .find(|c| c.value == value)
+        .or_else(|| {
+            color_map.iter().find(|c| {
+                c.value
+                    .strip_prefix('*')
+                    .is_some_and(|suffix| value.ends_with(suffix))
+            })
+        })
         .map(|c| c.color)
 }
 
@@ -213,6 +639,238 @@ mod tests {
         assert_eq!(resource.service, "vm");
     }
 
+    #[test]
+    fn test_color_suffix_match() {
+        assert_eq!(
+            get_color_for_value("lcm_state", "BOOT_FAILURE"),
+            Some([255, 0, 0])
+        );
+        assert_eq!(
+            get_color_for_value("lcm_state", "RUNNING"),
+            Some([0, 255, 0])
+        );
+        assert_eq!(get_color_for_value("lcm_state", "LCM_INIT"), None);
+    }
+
+    #[test]
+    fn test_state_guard() {
+        let guard = StateGuard {
+            field: "STATE".to_string(),
+            format: Some(ColumnFormat::VmState),
+            allowed: vec!["PENDING".to_string()],
+            blocked: Vec::new(),
+        };
+        assert!(guard
+            .check(&serde_json::json!({"STATE": "1"}), "Deploy")
+            .is_none());
+        let warning = guard.check(&serde_json::json!({"STATE": "3"}), "Deploy");
+        assert_eq!(
+            warning.as_deref(),
+            Some("Deploy requires state PENDING (current: ACTIVE)")
+        );
+    }
+
+    #[test]
+    fn test_image_delete_blocked_when_used() {
+        let images = get_resource("one-images").unwrap();
+        let delete = images.actions.iter().find(|a| a.key == "delete").unwrap();
+        let warning = delete.check_state(&serde_json::json!({"STATE": "8"}));
+        assert_eq!(
+            warning.as_deref(),
+            Some("Delete is not allowed in state USED_PERS")
+        );
+        assert!(delete
+            .check_state(&serde_json::json!({"STATE": "2"}))
+            .is_some());
+        assert!(delete
+            .check_state(&serde_json::json!({"STATE": "1"}))
+            .is_none());
+    }
+
+    #[test]
+    fn test_terminate_requires_typed_confirmation() {
+        let vms = get_resource("one-vms").unwrap();
+        let terminate = vms.actions.iter().find(|a| a.key == "terminate").unwrap();
+        let config = terminate.get_confirm_config().unwrap();
+        assert!(config.destructive && config.require_typed);
+
+        let resume = vms.actions.iter().find(|a| a.key == "resume").unwrap();
+        assert!(!resume.get_confirm_config().unwrap().require_typed);
+    }
+
+    #[test]
+    fn test_filter_matches_other_columns() {
+        let vms = get_resource("one-vms").unwrap();
+        let vm = serde_json::json!({
+            "ID": "7",
+            "NAME": "db-01",
+            "UNAME": "alice",
+            "STATE": "3",
+            "LCM_STATE": "3"
+        });
+        // Only in the USER column
+        assert!(vms.matches_filter(&vm, "alice"));
+        // Formatted LCM column ("3" is shown as RUNNING)
+        assert!(vms.matches_filter(&vm, "running"));
+        assert!(!vms.matches_filter(&vm, "poweroff"));
+    }
+
+    #[test]
+    fn test_filter_fields() {
+        let vms = get_resource("one-vms").unwrap();
+        let vm = serde_json::json!({
+            "ID": "42",
+            "NAME": "web-01",
+            "TEMPLATE": {"NIC": [{"IP": "10.0.0.5"}, {"IP": "192.168.1.9"}]},
+            "HISTORY_RECORDS": {"HISTORY": {"HOSTNAME": "node3"}}
+        });
+        assert!(vms.matches_filter(&vm, "web"));
+        assert!(vms.matches_filter(&vm, "42"));
+        assert!(vms.matches_filter(&vm, "192.168"));
+        assert!(vms.matches_filter(&vm, "node3"));
+        assert!(!vms.matches_filter(&vm, "db-"));
+
+        let users = get_resource("one-users").unwrap();
+        assert_eq!(users.filter_paths(), vec!["NAME", "ID"]);
+    }
+
+    #[test]
+    fn test_recover_only_in_failure_states() {
+        let vms = get_resource("one-vms").unwrap();
+        let recover = vms.actions.iter().find(|a| a.key == "recover").unwrap();
+        let vm = |lcm: &str| serde_json::json!({"STATE": "3", "LCM_STATE": lcm});
+        // 36 = BOOT_MIGRATE_FAILURE, 3 = RUNNING
+        assert!(recover.check_state(&vm("36")).is_none());
+        assert!(recover.check_state(&vm("3")).is_some());
+        assert!(recover.hide_blocked);
+    }
+
+    #[test]
+    fn test_backup_blocked_while_backing_up() {
+        let vms = get_resource("one-vms").unwrap();
+        let backup = vms.actions.iter().find(|a| a.key == "backup").unwrap();
+        let vm = |lcm: &str| serde_json::json!({"STATE": "3", "LCM_STATE": lcm});
+        // 3 = RUNNING, 68 = BACKUP, 69 = BACKUP_POWEROFF
+        assert!(backup.check_state(&vm("3")).is_none());
+        assert!(backup.check_state(&vm("68")).is_some());
+        assert!(backup.check_state(&vm("69")).is_some());
+        let select = backup.select.as_ref().unwrap();
+        assert_eq!(select.filter.get("TYPE").map(String::as_str), Some("3"));
+    }
+
+    #[test]
+    fn test_deploy_picks_host_then_datastore() {
+        let vms = get_resource("one-vms").unwrap();
+        let deploy = vms.actions.iter().find(|a| a.key == "deploy").unwrap();
+        let host = deploy.select.as_ref().unwrap();
+        let datastore = host.then.as_deref().unwrap();
+        assert_eq!(host.param, "host_id");
+        assert_eq!(datastore.param, "ds_id");
+        assert_eq!(host.params(), vec!["host_id", "ds_id"]);
+        assert_eq!(datastore.default_option.as_ref().map(|o| o.id), Some(-1));
+
+        let system = serde_json::json!({"TYPE": "1"});
+        assert!(host.filters().is_empty());
+        let filters = datastore.filters();
+        assert!(filters.iter().all(|f| f.matches(&system)));
+        assert!(!filters
+            .iter()
+            .all(|f| f.matches(&serde_json::json!({"TYPE": "0"}))));
+    }
+
+    #[test]
+    fn test_row_text_regex() {
+        let vms = get_resource("one-vms").unwrap();
+        let vm = serde_json::json!({
+            "ID": "42",
+            "NAME": "vm-stage-12",
+            "STATE": "3",
+            "LCM_STATE": "3"
+        });
+        let text = vms.row_text(&vm, &vms.columns);
+        // Formatted columns are included, so states match by name
+        assert!(text.contains("RUNNING"));
+        let regex = regex::Regex::new(r"vm-(prod|stage)-\d+").unwrap();
+        assert!(regex.is_match(&text));
+    }
+
+    #[test]
+    fn test_client_side_filter() {
+        let filter = ResourceFilter::client_side("cluster_id", "CLUSTERS.ID", vec!["100".into()]);
+        assert!(filter.matches(&serde_json::json!({"CLUSTERS": {"ID": ["0", "100"]}})));
+        assert!(!filter.matches(&serde_json::json!({"CLUSTERS": {"ID": "0"}})));
+        assert!(ResourceFilter::new("cluster_id", vec!["100".into()]).matches(&Value::Null));
+    }
+
+    #[test]
+    fn test_embedded_files_valid() {
+        for (file, content) in RESOURCE_FILES {
+            if let Err(e) = parse_resource_file(file, content) {
+                panic!("{}", e);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_errors_name_file_and_resource() {
+        let err = parse_resource_file("bad.json", "{").err().unwrap();
+        assert!(err.starts_with("bad.json: "));
+
+        let missing_id = r#"{"resources": {"one-x": {
+            "display_name": "X", "service": "vm", "sdk_method": "list",
+            "response_path": "X_POOL.X", "name_field": "NAME", "columns": []
+        }}}"#;
+        let err = parse_resource_file("x.json", missing_id).err().unwrap();
+        assert!(err.starts_with("x.json: resource 'one-x': missing field `id_field`"));
+
+        let bad_path = missing_id.replace(r#""name_field""#, r#""id_field": "ID[", "name_field""#);
+        let err = parse_resource_file("x.json", &bad_path).err().unwrap();
+        assert_eq!(err, "x.json: resource 'one-x': invalid id_field 'ID['");
+    }
+
+    #[test]
+    fn test_json_path_syntax() {
+        assert!(is_valid_json_path("HISTORY_RECORDS.HISTORY.HOSTNAME"));
+        assert!(is_valid_json_path("TEMPLATE.DISK[0].SIZE"));
+        assert!(is_valid_json_path("TEMPLATE.NIC[*].IP"));
+        assert!(!is_valid_json_path(""));
+        assert!(!is_valid_json_path("TEMPLATE..NIC"));
+        assert!(!is_valid_json_path("NIC[x].IP"));
+        assert!(!is_valid_json_path("NIC[0"));
+    }
+
+    #[test]
+    fn test_unknown_references() {
+        assert!(find_unknown_references(get_registry()).is_empty());
+
+        let config: ResourceConfig = serde_json::from_value(serde_json::json!({
+            "resources": {
+                "one-test": {
+                    "display_name": "Test",
+                    "service": "vm",
+                    "sdk_method": "list",
+                    "response_path": "VM_POOL.VM",
+                    "id_field": "ID",
+                    "name_field": "NAME",
+                    "columns": [
+                        { "header": "STATE", "json_path": "STATE", "width": 10,
+                          "color_map": "vm_stat", "format": "vm_state" }
+                    ]
+                }
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            find_unknown_references(&config),
+            vec!["one-test.STATE: color_map 'vm_stat'"]
+        );
+        // Unknown formats are rejected when the JSON is parsed
+        let column: Result<ColumnDef, _> = serde_json::from_value(serde_json::json!(
+            { "header": "SIZE", "json_path": "SIZE", "width": 10, "format": "gb" }
+        ));
+        assert!(column.is_err());
+    }
+
     #[test]
     fn test_get_all_resource_keys() {
         let keys = get_all_resource_keys();
@@ -2,6 +2,9 @@
 //!
 //! Maps SDK method names to actual OpenNebula API calls.
 
+use super::{accounting_rows, format_acl_rule, quota_rows};
+use crate::config::parse_duration;
+use crate::one::client::resize_template;
 use crate::one::OneClient;
 use anyhow::Result;
 use serde_json::Value;
@@ -20,116 +23,337 @@ pub async fn invoke_sdk_method(
         "vnet" => invoke_vnet(method, client, params).await,
         "image" => invoke_image(method, client, params).await,
         "template" => invoke_template(method, client, params).await,
+        "vmgroup" => invoke_vmgroup(method, client, params).await,
         "cluster" => invoke_cluster(method, client, params).await,
+        "secgroup" => invoke_secgroup(method, client, params).await,
+        "vrouter" => invoke_vrouter(method, client, params).await,
+        "market" => invoke_market(method, client, params).await,
+        "marketapp" => invoke_marketapp(method, client, params).await,
         "user" => invoke_user(method, client, params).await,
         "group" => invoke_group(method, client, params).await,
+        "acl" => invoke_acl(method, client, params).await,
         "zone" => invoke_zone(method, client, params).await,
         "system" => invoke_system(method, client, params).await,
         _ => Err(anyhow::anyhow!("Unknown service: {}", service)),
     }
 }
 
+/// OpenNebula's raw XML for one object of a service, for the describe XML view
+pub async fn fetch_raw_xml(service: &str, client: &OneClient, id: i64) -> Result<String> {
+    let object = match service {
+        "vnet" => "vn",
+        "vm" | "host" | "datastore" | "image" | "template" | "vmgroup" | "cluster" | "secgroup"
+        | "vrouter" | "market" | "marketapp" | "user" | "group" | "zone" => service,
+        _ => return Err(anyhow::anyhow!("No XML view for service: {}", service)),
+    };
+    client.info_xml(object, id).await
+}
+
 /// VM service methods
 async fn invoke_vm(method: &str, client: &OneClient, params: &Value) -> Result<Value> {
     match method {
         "list" | "list_vms" => {
-            let filter = params.get("filter").and_then(|v| v.as_i64()).unwrap_or(-2) as i32;
-            let start = params.get("start").and_then(|v| v.as_i64()).unwrap_or(-1) as i32;
-            let end = params.get("end").and_then(|v| v.as_i64()).unwrap_or(-1) as i32;
-            let state = params.get("state").and_then(|v| v.as_i64()).unwrap_or(-1) as i32;
+            let filter = params.get("filter").and_then(|v| v.as_i64()).unwrap_or(-2);
+            let start = params.get("start").and_then(|v| v.as_i64()).unwrap_or(-1);
+            let end = params.get("end").and_then(|v| v.as_i64()).unwrap_or(-1);
+            let state = params.get("state").and_then(|v| v.as_i64()).unwrap_or(-1);
             client.list_vms(filter, start, end, state).await
         }
         "get" | "get_vm" => {
             let id = params
                 .get("id")
                 .and_then(|v| v.as_i64())
-                .ok_or_else(|| anyhow::anyhow!("Missing VM id"))? as i32;
+                .ok_or_else(|| anyhow::anyhow!("Missing VM id"))?;
             client.get_vm(id).await
         }
+        "accounting" => {
+            let filter = params.get("filter").and_then(|v| v.as_i64()).unwrap_or(-2);
+            let now = chrono::Utc::now().timestamp();
+            let from = time_param(params, "from", false)?
+                .unwrap_or(now - DEFAULT_ACCOUNTING_DAYS * SECONDS_PER_DAY);
+            let to = time_param(params, "to", true)?.unwrap_or(now);
+            let history = client.vmpool_accounting(filter, from, to).await?;
+            let records = super::collect_json_path(&history, "HISTORY_RECORDS.HISTORY[*]");
+            let rows = accounting_rows(&records, from, to.min(now));
+            Ok(serde_json::json!({ "ACCOUNTING": { "VM": rows } }))
+        }
+        "list_nics" => {
+            // The id comes from the parent VM row (sub-resource filter), sent as a string
+            let id = params
+                .get("id")
+                .and_then(|v| v.as_i64().or_else(|| v.as_str()?.parse().ok()))
+                .ok_or_else(|| anyhow::anyhow!("Missing VM id"))?;
+            let mut vm = client.get_vm(id).await?;
+            // Tag each NIC with its VM so NIC actions know which VM to call
+            match vm.pointer_mut("/VM/TEMPLATE/NIC") {
+                Some(Value::Array(nics)) => nics.iter_mut().for_each(|nic| tag_vm_id(nic, id)),
+                Some(nic) => tag_vm_id(nic, id),
+                None => {}
+            }
+            Ok(vm)
+        }
+        "list_sched_actions" => {
+            let id = parent_id_param(params, "VM")?;
+            let mut vm = client.get_vm(id).await?;
+            // Tag each scheduled action with its VM so deleting it knows which VM to call
+            match vm.pointer_mut("/VM/TEMPLATE/SCHED_ACTION") {
+                Some(Value::Array(actions)) => {
+                    actions.iter_mut().for_each(|action| tag_vm_id(action, id))
+                }
+                Some(action) => tag_vm_id(action, id),
+                None => {}
+            }
+            Ok(vm)
+        }
+        "sched_add" => {
+            let id = params
+                .get("id")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| anyhow::anyhow!("Missing VM id"))?;
+            let action = params
+                .get("action")
+                .and_then(|v| v.as_str())
+                .map(|action| action.trim().to_lowercase())
+                .ok_or_else(|| anyhow::anyhow!("Missing action to schedule"))?;
+            if !SCHEDULABLE_ACTIONS.contains(&action.as_str()) {
+                return Err(anyhow::anyhow!(
+                    "Cannot schedule '{}'; expected one of: {}",
+                    action,
+                    SCHEDULABLE_ACTIONS.join(", ")
+                ));
+            }
+            let time = params
+                .get("time")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| anyhow::anyhow!("Missing time"))?;
+            let time = sched_time(time, chrono::Local::now())?;
+            let template = format!(
+                "SCHED_ACTION = [ ACTION = \"{}\", TIME = \"{}\" ]",
+                action, time
+            );
+            client.vm_sched_add(id, &template).await
+        }
+        "backup" => {
+            let id = params
+                .get("id")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| anyhow::anyhow!("Missing VM id"))?;
+            let datastore_id = params
+                .get("datastore_id")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| anyhow::anyhow!("Missing backup datastore id"))?;
+            client.vm_backup(id, datastore_id).await
+        }
+        "sched_delete" => {
+            let sched_id = params
+                .get("id")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| anyhow::anyhow!("Missing scheduled action id"))?;
+            let vm_id = params
+                .get("vm_id")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| anyhow::anyhow!("Missing VM id"))?;
+            client.vm_sched_delete(vm_id, sched_id).await
+        }
+        "attach_nic" => {
+            let id = params
+                .get("id")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| anyhow::anyhow!("Missing VM id"))?;
+            let network_id = params
+                .get("network_id")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| anyhow::anyhow!("Missing network id"))?;
+            let template = format!("NIC = [ NETWORK_ID = \"{}\" ]", network_id);
+            client.vm_attach_nic(id, &template).await
+        }
+        "detach_nic" => {
+            let nic_id = params
+                .get("id")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| anyhow::anyhow!("Missing NIC id"))?;
+            let vm_id = params
+                .get("vm_id")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| anyhow::anyhow!("Missing VM id"))?;
+            client.vm_detach_nic(vm_id, nic_id).await
+        }
         "resume" => {
             let id = params
                 .get("id")
                 .and_then(|v| v.as_i64())
-                .ok_or_else(|| anyhow::anyhow!("Missing VM id"))? as i32;
+                .ok_or_else(|| anyhow::anyhow!("Missing VM id"))?;
             client.vm_action("resume", id).await
         }
         "suspend" => {
             let id = params
                 .get("id")
                 .and_then(|v| v.as_i64())
-                .ok_or_else(|| anyhow::anyhow!("Missing VM id"))? as i32;
+                .ok_or_else(|| anyhow::anyhow!("Missing VM id"))?;
             client.vm_action("suspend", id).await
         }
         "stop" => {
             let id = params
                 .get("id")
                 .and_then(|v| v.as_i64())
-                .ok_or_else(|| anyhow::anyhow!("Missing VM id"))? as i32;
+                .ok_or_else(|| anyhow::anyhow!("Missing VM id"))?;
             client.vm_action("stop", id).await
         }
         "poweroff" => {
             let id = params
                 .get("id")
                 .and_then(|v| v.as_i64())
-                .ok_or_else(|| anyhow::anyhow!("Missing VM id"))? as i32;
+                .ok_or_else(|| anyhow::anyhow!("Missing VM id"))?;
             client.vm_action("poweroff", id).await
         }
         "poweroff-hard" => {
             let id = params
                 .get("id")
                 .and_then(|v| v.as_i64())
-                .ok_or_else(|| anyhow::anyhow!("Missing VM id"))? as i32;
+                .ok_or_else(|| anyhow::anyhow!("Missing VM id"))?;
             client.vm_action("poweroff-hard", id).await
         }
         "reboot" => {
             let id = params
                 .get("id")
                 .and_then(|v| v.as_i64())
-                .ok_or_else(|| anyhow::anyhow!("Missing VM id"))? as i32;
+                .ok_or_else(|| anyhow::anyhow!("Missing VM id"))?;
             client.vm_action("reboot", id).await
         }
         "reboot-hard" => {
             let id = params
                 .get("id")
                 .and_then(|v| v.as_i64())
-                .ok_or_else(|| anyhow::anyhow!("Missing VM id"))? as i32;
+                .ok_or_else(|| anyhow::anyhow!("Missing VM id"))?;
             client.vm_action("reboot-hard", id).await
         }
         "terminate" => {
             let id = params
                 .get("id")
                 .and_then(|v| v.as_i64())
-                .ok_or_else(|| anyhow::anyhow!("Missing VM id"))? as i32;
+                .ok_or_else(|| anyhow::anyhow!("Missing VM id"))?;
             client.vm_action("terminate", id).await
         }
         "terminate-hard" => {
             let id = params
                 .get("id")
                 .and_then(|v| v.as_i64())
-                .ok_or_else(|| anyhow::anyhow!("Missing VM id"))? as i32;
+                .ok_or_else(|| anyhow::anyhow!("Missing VM id"))?;
             client.vm_action("terminate-hard", id).await
         }
         "undeploy" => {
             let id = params
                 .get("id")
                 .and_then(|v| v.as_i64())
-                .ok_or_else(|| anyhow::anyhow!("Missing VM id"))? as i32;
+                .ok_or_else(|| anyhow::anyhow!("Missing VM id"))?;
             client.vm_action("undeploy", id).await
         }
         "hold" => {
             let id = params
                 .get("id")
                 .and_then(|v| v.as_i64())
-                .ok_or_else(|| anyhow::anyhow!("Missing VM id"))? as i32;
+                .ok_or_else(|| anyhow::anyhow!("Missing VM id"))?;
             client.vm_action("hold", id).await
         }
         "release" => {
             let id = params
                 .get("id")
                 .and_then(|v| v.as_i64())
-                .ok_or_else(|| anyhow::anyhow!("Missing VM id"))? as i32;
+                .ok_or_else(|| anyhow::anyhow!("Missing VM id"))?;
             client.vm_action("release", id).await
         }
+        "deploy" => {
+            let id = params
+                .get("id")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| anyhow::anyhow!("Missing VM id"))?;
+            let host_id = params
+                .get("host_id")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| anyhow::anyhow!("Missing host id"))?;
+            let enforce = params
+                .get("enforce")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let ds_id = params.get("ds_id").and_then(|v| v.as_i64()).unwrap_or(-1);
+            client.vm_deploy(id, host_id, enforce, ds_id).await
+        }
+        "resize" => {
+            let id = params
+                .get("id")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| anyhow::anyhow!("Missing VM id"))?;
+            let cpu = capacity_param(params, "cpu", "CPU")?;
+            let vcpu = capacity_param(params, "vcpu", "VCPU")?;
+            let memory = capacity_param(params, "memory", "MEMORY")?;
+            if cpu.is_none() && vcpu.is_none() && memory.is_none() {
+                return Err(anyhow::anyhow!("Nothing to resize"));
+            }
+            let template = resize_template(cpu, vcpu, memory);
+            let enforce = params
+                .get("enforce")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            client.vm_resize(id, &template, enforce).await
+        }
+        "rename" => {
+            let (id, name) = rename_params(params, "VM")?;
+            client.vm_rename(id, name).await
+        }
+        "save_as_template" => {
+            let (id, name) = rename_params(params, "VM")?;
+            let persistent = params
+                .get("persistent")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            client.vm_save_as_template(id, name, persistent).await
+        }
+        "chown" => {
+            let (id, user_id, group_id) = chown_params(params, "VM")?;
+            client.chown("vm", id, user_id, group_id).await
+        }
+        "lock" => {
+            let (id, level) = lock_params(params, "VM")?;
+            client.vm_lock(id, level).await
+        }
+        "recover" => {
+            let id = params
+                .get("id")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| anyhow::anyhow!("Missing VM id"))?;
+            let operation = params
+                .get("operation")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| anyhow::anyhow!("Missing recover operation"))?;
+            client.vm_recover(id, operation).await
+        }
+        "unlock" => {
+            let id = params
+                .get("id")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| anyhow::anyhow!("Missing VM id"))?;
+            client.vm_unlock(id).await
+        }
+        "migrate" => {
+            let id = params
+                .get("id")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| anyhow::anyhow!("Missing VM id"))?;
+            let host_id = params
+                .get("host_id")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| anyhow::anyhow!("Missing host id"))?;
+            let live = params
+                .get("live")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let enforce = params
+                .get("enforce")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let ds_id = params.get("ds_id").and_then(|v| v.as_i64()).unwrap_or(-1);
+            client.vm_migrate(id, host_id, live, enforce, ds_id).await
+        }
         _ => Err(anyhow::anyhow!("Unknown VM method: {}", method)),
     }
 }
@@ -142,9 +366,32 @@ async fn invoke_host(method: &str, client: &OneClient, params: &Value) -> Result
             let id = params
                 .get("id")
                 .and_then(|v| v.as_i64())
-                .ok_or_else(|| anyhow::anyhow!("Missing host id"))? as i32;
+                .ok_or_else(|| anyhow::anyhow!("Missing host id"))?;
             client.get_host(id).await
         }
+        "rename" => {
+            let (id, name) = rename_params(params, "host")?;
+            client.rename("host", id, name).await
+        }
+        "enable" | "disable" | "offline" => {
+            let id = params
+                .get("id")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| anyhow::anyhow!("Missing host id"))?;
+            let status = match method {
+                "enable" => 0,
+                "disable" => 1,
+                _ => 2,
+            };
+            client.host_status(id, status).await
+        }
+        "flush" => {
+            let id = params
+                .get("id")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| anyhow::anyhow!("Missing host id"))?;
+            client.host_flush(id).await
+        }
         _ => Err(anyhow::anyhow!("Unknown host method: {}", method)),
     }
 }
@@ -157,10 +404,16 @@ async fn invoke_datastore(method: &str, client: &OneClient, params: &Value) -> R
             let id = params
                 .get("id")
                 .and_then(|v| v.as_i64())
-                .ok_or_else(|| anyhow::anyhow!("Missing datastore id"))?
-                as i32;
+                .ok_or_else(|| anyhow::anyhow!("Missing datastore id"))?;
             client.get_datastore(id).await
         }
+        "enable" | "disable" => {
+            let id = params
+                .get("id")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| anyhow::anyhow!("Missing datastore id"))?;
+            client.datastore_enable(id, method == "enable").await
+        }
         _ => Err(anyhow::anyhow!("Unknown datastore method: {}", method)),
     }
 }
@@ -169,18 +422,84 @@ async fn invoke_datastore(method: &str, client: &OneClient, params: &Value) -> R
 async fn invoke_vnet(method: &str, client: &OneClient, params: &Value) -> Result<Value> {
     match method {
         "list" | "list_vnets" => {
-            let filter = params.get("filter").and_then(|v| v.as_i64()).unwrap_or(-2) as i32;
-            let start = params.get("start").and_then(|v| v.as_i64()).unwrap_or(-1) as i32;
-            let end = params.get("end").and_then(|v| v.as_i64()).unwrap_or(-1) as i32;
+            let filter = params.get("filter").and_then(|v| v.as_i64()).unwrap_or(-2);
+            let start = params.get("start").and_then(|v| v.as_i64()).unwrap_or(-1);
+            let end = params.get("end").and_then(|v| v.as_i64()).unwrap_or(-1);
             client.list_vnets(filter, start, end).await
         }
         "get" | "get_vnet" => {
             let id = params
                 .get("id")
                 .and_then(|v| v.as_i64())
-                .ok_or_else(|| anyhow::anyhow!("Missing vnet id"))? as i32;
+                .ok_or_else(|| anyhow::anyhow!("Missing vnet id"))?;
             client.get_vnet(id).await
         }
+        "rename" => {
+            let (id, name) = rename_params(params, "vnet")?;
+            client.rename("vn", id, name).await
+        }
+        "chown" => {
+            let (id, user_id, group_id) = chown_params(params, "vnet")?;
+            client.chown("vn", id, user_id, group_id).await
+        }
+        "lock" => {
+            let (id, level) = lock_params(params, "vnet")?;
+            client.lock("vn", id, level).await
+        }
+        "unlock" => {
+            let id = params
+                .get("id")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| anyhow::anyhow!("Missing vnet id"))?;
+            client.unlock("vn", id).await
+        }
+        "list_ars" => {
+            let id = parent_id_param(params, "vnet")?;
+            let mut vnet = client.get_vnet(id).await?;
+            // Tag each range with its vnet, like VM NICs
+            match vnet.pointer_mut("/VNET/AR_POOL/AR") {
+                Some(Value::Array(ars)) => {
+                    ars.iter_mut().for_each(|ar| tag_field(ar, "VNET_ID", id))
+                }
+                Some(ar) => tag_field(ar, "VNET_ID", id),
+                None => {}
+            }
+            Ok(vnet)
+        }
+        "list_leases" => {
+            let id = parent_id_param(params, "vnet")?;
+            let vnet = client.get_vnet(id).await?;
+            Ok(serde_json::json!({ "LEASES": vnet_leases(&vnet, id) }))
+        }
+        "hold" | "release" => {
+            // From a lease row the vnet comes as `vnet_id`, from a vnet row as `id`
+            let id = params
+                .get("vnet_id")
+                .or_else(|| params.get("id"))
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| anyhow::anyhow!("Missing vnet id"))?;
+            let address = params
+                .get("ip")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| anyhow::anyhow!("Missing IP address"))?;
+            let template = lease_template(address)?;
+            if method == "hold" {
+                client.vn_hold(id, &template).await
+            } else {
+                client.vn_release(id, &template).await
+            }
+        }
+        "reserve" => {
+            let (id, name) = rename_params(params, "vnet")?;
+            let size: u32 = capacity_param(params, "size", "SIZE")?
+                .ok_or_else(|| anyhow::anyhow!("Missing reservation size"))?;
+            let template = format!(
+                "SIZE = \"{}\"\nNAME = \"{}\"",
+                size,
+                name.replace('"', "\\\"")
+            );
+            client.vn_reserve(id, &template).await
+        }
         _ => Err(anyhow::anyhow!("Unknown vnet method: {}", method)),
     }
 }
@@ -189,18 +508,71 @@ async fn invoke_vnet(method: &str, client: &OneClient, params: &Value) -> Result
 async fn invoke_image(method: &str, client: &OneClient, params: &Value) -> Result<Value> {
     match method {
         "list" | "list_images" => {
-            let filter = params.get("filter").and_then(|v| v.as_i64()).unwrap_or(-2) as i32;
-            let start = params.get("start").and_then(|v| v.as_i64()).unwrap_or(-1) as i32;
-            let end = params.get("end").and_then(|v| v.as_i64()).unwrap_or(-1) as i32;
+            let filter = params.get("filter").and_then(|v| v.as_i64()).unwrap_or(-2);
+            let start = params.get("start").and_then(|v| v.as_i64()).unwrap_or(-1);
+            let end = params.get("end").and_then(|v| v.as_i64()).unwrap_or(-1);
             client.list_images(filter, start, end).await
         }
         "get" | "get_image" => {
             let id = params
                 .get("id")
                 .and_then(|v| v.as_i64())
-                .ok_or_else(|| anyhow::anyhow!("Missing image id"))? as i32;
+                .ok_or_else(|| anyhow::anyhow!("Missing image id"))?;
             client.get_image(id).await
         }
+        "rename" => {
+            let (id, name) = rename_params(params, "image")?;
+            client.rename("image", id, name).await
+        }
+        "clone" => {
+            let (id, name) = rename_params(params, "image")?;
+            let datastore_id = params
+                .get("datastore_id")
+                .and_then(|v| v.as_i64())
+                .unwrap_or(-1);
+            client.image_clone(id, name, datastore_id).await
+        }
+        "chown" => {
+            let (id, user_id, group_id) = chown_params(params, "image")?;
+            client.chown("image", id, user_id, group_id).await
+        }
+        "lock" => {
+            let (id, level) = lock_params(params, "image")?;
+            client.lock("image", id, level).await
+        }
+        "unlock" => {
+            let id = params
+                .get("id")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| anyhow::anyhow!("Missing image id"))?;
+            client.unlock("image", id).await
+        }
+        "enable" | "disable" => {
+            let id = params
+                .get("id")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| anyhow::anyhow!("Missing image id"))?;
+            client.image_enable(id, method == "enable").await
+        }
+        "toggle_persistent" => {
+            let id = params
+                .get("id")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| anyhow::anyhow!("Missing image id"))?;
+            // Current PERSISTENT flag of the image (1 = persistent)
+            let persistent = params
+                .get("persistent")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| anyhow::anyhow!("Missing image persistent flag"))?;
+            client.image_persistent(id, persistent == 0).await
+        }
+        "delete" => {
+            let id = params
+                .get("id")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| anyhow::anyhow!("Missing image id"))?;
+            client.image_delete(id).await
+        }
         _ => Err(anyhow::anyhow!("Unknown image method: {}", method)),
     }
 }
@@ -209,22 +581,106 @@ async fn invoke_image(method: &str, client: &OneClient, params: &Value) -> Resul
 async fn invoke_template(method: &str, client: &OneClient, params: &Value) -> Result<Value> {
     match method {
         "list" | "list_templates" => {
-            let filter = params.get("filter").and_then(|v| v.as_i64()).unwrap_or(-2) as i32;
-            let start = params.get("start").and_then(|v| v.as_i64()).unwrap_or(-1) as i32;
-            let end = params.get("end").and_then(|v| v.as_i64()).unwrap_or(-1) as i32;
+            let filter = params.get("filter").and_then(|v| v.as_i64()).unwrap_or(-2);
+            let start = params.get("start").and_then(|v| v.as_i64()).unwrap_or(-1);
+            let end = params.get("end").and_then(|v| v.as_i64()).unwrap_or(-1);
             client.list_templates(filter, start, end).await
         }
         "get" | "get_template" => {
             let id = params
                 .get("id")
                 .and_then(|v| v.as_i64())
-                .ok_or_else(|| anyhow::anyhow!("Missing template id"))? as i32;
+                .ok_or_else(|| anyhow::anyhow!("Missing template id"))?;
             client.get_template(id).await
         }
+        "rename" => {
+            let (id, name) = rename_params(params, "template")?;
+            client.rename("template", id, name).await
+        }
+        "chown" => {
+            let (id, user_id, group_id) = chown_params(params, "template")?;
+            client.chown("template", id, user_id, group_id).await
+        }
+        "lock" => {
+            let (id, level) = lock_params(params, "template")?;
+            client.lock("template", id, level).await
+        }
+        "unlock" => {
+            let id = params
+                .get("id")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| anyhow::anyhow!("Missing template id"))?;
+            client.unlock("template", id).await
+        }
+        "instantiate" => {
+            let id = params
+                .get("id")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| anyhow::anyhow!("Missing template id"))?;
+            let name = params.get("name").and_then(|v| v.as_str()).unwrap_or("");
+            let hold = params
+                .get("hold")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            let extra_template = params
+                .get("extra_template")
+                .and_then(|v| v.as_str())
+                .unwrap_or("");
+            client
+                .template_instantiate(id, name, hold, extra_template)
+                .await
+        }
+        "clone" => {
+            let (id, name) = rename_params(params, "template")?;
+            let recursive = params
+                .get("recursive")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            client.template_clone(id, name, recursive).await
+        }
+        "delete" => {
+            let id = params
+                .get("id")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| anyhow::anyhow!("Missing template id"))?;
+            let recursive = params
+                .get("recursive")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            client.template_delete(id, recursive).await
+        }
         _ => Err(anyhow::anyhow!("Unknown template method: {}", method)),
     }
 }
 
+/// VM group service methods
+async fn invoke_vmgroup(method: &str, client: &OneClient, params: &Value) -> Result<Value> {
+    match method {
+        "list" | "list_vmgroups" => {
+            let filter = params.get("filter").and_then(|v| v.as_i64()).unwrap_or(-2);
+            let start = params.get("start").and_then(|v| v.as_i64()).unwrap_or(-1);
+            let end = params.get("end").and_then(|v| v.as_i64()).unwrap_or(-1);
+            client.list_vmgroups(filter, start, end).await
+        }
+        "get" | "get_vmgroup" => {
+            let id = params
+                .get("id")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| anyhow::anyhow!("Missing VM group id"))?;
+            client.get_vmgroup(id).await
+        }
+        "list_roles" => {
+            // The id comes from the parent group row (sub-resource filter), sent as a string
+            let id = params
+                .get("id")
+                .and_then(|v| v.as_i64().or_else(|| v.as_str()?.parse().ok()))
+                .ok_or_else(|| anyhow::anyhow!("Missing VM group id"))?;
+            client.get_vmgroup(id).await
+        }
+        _ => Err(anyhow::anyhow!("Unknown VM group method: {}", method)),
+    }
+}
+
 /// Cluster service methods
 async fn invoke_cluster(method: &str, client: &OneClient, params: &Value) -> Result<Value> {
     match method {
@@ -233,13 +689,99 @@ async fn invoke_cluster(method: &str, client: &OneClient, params: &Value) -> Res
             let id = params
                 .get("id")
                 .and_then(|v| v.as_i64())
-                .ok_or_else(|| anyhow::anyhow!("Missing cluster id"))? as i32;
+                .ok_or_else(|| anyhow::anyhow!("Missing cluster id"))?;
             client.get_cluster(id).await
         }
         _ => Err(anyhow::anyhow!("Unknown cluster method: {}", method)),
     }
 }
 
+/// Security group service methods
+async fn invoke_secgroup(method: &str, client: &OneClient, params: &Value) -> Result<Value> {
+    match method {
+        "list" | "list_security_groups" => {
+            let filter = params.get("filter").and_then(|v| v.as_i64()).unwrap_or(-2);
+            let start = params.get("start").and_then(|v| v.as_i64()).unwrap_or(-1);
+            let end = params.get("end").and_then(|v| v.as_i64()).unwrap_or(-1);
+            client.list_security_groups(filter, start, end).await
+        }
+        "get" | "get_security_group" => {
+            let id = params
+                .get("id")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| anyhow::anyhow!("Missing security group id"))?;
+            client.get_security_group(id).await
+        }
+        _ => Err(anyhow::anyhow!("Unknown security group method: {}", method)),
+    }
+}
+
+/// Virtual router service methods
+async fn invoke_vrouter(method: &str, client: &OneClient, params: &Value) -> Result<Value> {
+    match method {
+        "list" | "list_vrouters" => {
+            let filter = params.get("filter").and_then(|v| v.as_i64()).unwrap_or(-2);
+            let start = params.get("start").and_then(|v| v.as_i64()).unwrap_or(-1);
+            let end = params.get("end").and_then(|v| v.as_i64()).unwrap_or(-1);
+            client.list_vrouters(filter, start, end).await
+        }
+        "get" | "get_vrouter" => {
+            let id = params
+                .get("id")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| anyhow::anyhow!("Missing virtual router id"))?;
+            client.get_vrouter(id).await
+        }
+        "list_nics" => {
+            // The id comes from the parent router row (sub-resource filter), sent as a string
+            let id = params
+                .get("id")
+                .and_then(|v| v.as_i64().or_else(|| v.as_str()?.parse().ok()))
+                .ok_or_else(|| anyhow::anyhow!("Missing virtual router id"))?;
+            client.get_vrouter(id).await
+        }
+        _ => Err(anyhow::anyhow!("Unknown virtual router method: {}", method)),
+    }
+}
+
+/// Marketplace service methods
+async fn invoke_market(method: &str, client: &OneClient, params: &Value) -> Result<Value> {
+    match method {
+        "list" | "list_marketplaces" => client.list_marketplaces().await,
+        "get" | "get_marketplace" => {
+            let id = params
+                .get("id")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| anyhow::anyhow!("Missing marketplace id"))?;
+            client.get_marketplace(id).await
+        }
+        _ => Err(anyhow::anyhow!("Unknown marketplace method: {}", method)),
+    }
+}
+
+/// Marketplace app service methods
+async fn invoke_marketapp(method: &str, client: &OneClient, params: &Value) -> Result<Value> {
+    match method {
+        "list" | "list_market_apps" => {
+            let filter = params.get("filter").and_then(|v| v.as_i64()).unwrap_or(-2);
+            let start = params.get("start").and_then(|v| v.as_i64()).unwrap_or(-1);
+            let end = params.get("end").and_then(|v| v.as_i64()).unwrap_or(-1);
+            client.list_market_apps(filter, start, end).await
+        }
+        "get" | "get_market_app" => {
+            let id = params
+                .get("id")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| anyhow::anyhow!("Missing marketplace app id"))?;
+            client.get_market_app(id).await
+        }
+        _ => Err(anyhow::anyhow!(
+            "Unknown marketplace app method: {}",
+            method
+        )),
+    }
+}
+
 /// User service methods
 async fn invoke_user(method: &str, client: &OneClient, params: &Value) -> Result<Value> {
     match method {
@@ -248,25 +790,126 @@ async fn invoke_user(method: &str, client: &OneClient, params: &Value) -> Result
             let id = params
                 .get("id")
                 .and_then(|v| v.as_i64())
-                .ok_or_else(|| anyhow::anyhow!("Missing user id"))? as i32;
+                .ok_or_else(|| anyhow::anyhow!("Missing user id"))?;
             client.get_user(id).await
         }
+        "create" => {
+            let name = params
+                .get("name")
+                .and_then(|v| v.as_str())
+                .filter(|name| !name.trim().is_empty())
+                .ok_or_else(|| anyhow::anyhow!("Missing user name"))?;
+            let driver = params
+                .get("driver")
+                .and_then(|v| v.as_str())
+                .unwrap_or("core");
+            client
+                .user_create(name.trim(), password_param(params)?, driver)
+                .await
+        }
+        "passwd" => {
+            let id = params
+                .get("id")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| anyhow::anyhow!("Missing user id"))?;
+            client.user_passwd(id, password_param(params)?).await
+        }
+        "addgroup" | "delgroup" => {
+            let id = params
+                .get("id")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| anyhow::anyhow!("Missing user id"))?;
+            let group_id = params
+                .get("group_id")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| anyhow::anyhow!("Missing group id"))?;
+            if method == "addgroup" {
+                client.user_addgroup(id, group_id).await
+            } else {
+                client.user_delgroup(id, group_id).await
+            }
+        }
+        "list_quotas" => {
+            let id = parent_id_param(params, "user")?;
+            let user = client.get_user(id).await?;
+            Ok(quotas_response(&user, "USER", "DEFAULT_USER_QUOTAS"))
+        }
         _ => Err(anyhow::anyhow!("Unknown user method: {}", method)),
     }
 }
 
 /// Group service methods
-async fn invoke_group(method: &str, client: &OneClient, _params: &Value) -> Result<Value> {
+async fn invoke_group(method: &str, client: &OneClient, params: &Value) -> Result<Value> {
     match method {
         "list" | "list_groups" => client.list_groups().await,
+        "get" | "get_group" => {
+            let id = params
+                .get("id")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| anyhow::anyhow!("Missing group id"))?;
+            client.get_group(id).await
+        }
+        "list_quotas" => {
+            let id = parent_id_param(params, "group")?;
+            let group = client.get_group(id).await?;
+            Ok(quotas_response(&group, "GROUP", "DEFAULT_GROUP_QUOTAS"))
+        }
+        "create" => {
+            let name = params
+                .get("name")
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .ok_or_else(|| anyhow::anyhow!("Missing group name"))?;
+            client.group_create(name).await
+        }
         _ => Err(anyhow::anyhow!("Unknown group method: {}", method)),
     }
 }
 
+/// Parent id of a sub-resource list; it comes from the parent row as a string
+fn parent_id_param(params: &Value, label: &str) -> Result<i64> {
+    params
+        .get("id")
+        .and_then(|v| v.as_i64().or_else(|| v.as_str()?.parse().ok()))
+        .ok_or_else(|| anyhow::anyhow!("Missing {} id", label))
+}
+
+/// Quota rows of a user or group info response, as `{"QUOTAS": {"QUOTA": [...]}}`
+fn quotas_response(info: &Value, kind: &str, defaults_key: &str) -> Value {
+    let owner = info.get(kind).unwrap_or(&Value::Null);
+    let rows = quota_rows(owner, owner.get(defaults_key));
+    serde_json::json!({ "QUOTAS": { "QUOTA": rows } })
+}
+
+/// ACL service methods
+async fn invoke_acl(method: &str, client: &OneClient, _params: &Value) -> Result<Value> {
+    match method {
+        "list" | "list_acls" => {
+            let mut pool = client.list_acls().await?;
+            // Add the decoded rule so describe shows it next to the packed masks
+            match pool.pointer_mut("/ACL_POOL/ACL") {
+                Some(Value::Array(acls)) => acls.iter_mut().for_each(add_acl_rule),
+                Some(acl) => add_acl_rule(acl),
+                None => {}
+            }
+            Ok(pool)
+        }
+        _ => Err(anyhow::anyhow!("Unknown ACL method: {}", method)),
+    }
+}
+
 /// Zone service methods
-async fn invoke_zone(method: &str, client: &OneClient, _params: &Value) -> Result<Value> {
+async fn invoke_zone(method: &str, client: &OneClient, params: &Value) -> Result<Value> {
     match method {
         "list" | "list_zones" => client.list_zones().await,
+        "get" | "get_zone" => {
+            let id = params
+                .get("id")
+                .and_then(|v| v.as_i64())
+                .ok_or_else(|| anyhow::anyhow!("Missing zone id"))?;
+            client.get_zone(id).await
+        }
         _ => Err(anyhow::anyhow!("Unknown zone method: {}", method)),
     }
 }
@@ -279,3 +922,281 @@ async fn invoke_system(method: &str, client: &OneClient, _params: &Value) -> Res
         _ => Err(anyhow::anyhow!("Unknown system method: {}", method)),
     }
 }
+
+/// Read the `id` and `name` params of a rename call
+fn rename_params<'a>(params: &'a Value, kind: &str) -> Result<(i64, &'a str)> {
+    let id = params
+        .get("id")
+        .and_then(|v| v.as_i64())
+        .ok_or_else(|| anyhow::anyhow!("Missing {} id", kind))?;
+    let name = params
+        .get("name")
+        .and_then(|v| v.as_str())
+        .filter(|name| !name.trim().is_empty())
+        .ok_or_else(|| anyhow::anyhow!("Missing new name"))?;
+    Ok((id, name))
+}
+
+/// Password typed for a user action; sent as is, surrounding spaces included
+fn password_param(params: &Value) -> Result<&str> {
+    params
+        .get("password")
+        .and_then(|v| v.as_str())
+        .filter(|password| !password.is_empty())
+        .ok_or_else(|| anyhow::anyhow!("Missing password"))
+}
+
+/// Target owner and group of a chown; a missing user or group stays unchanged (-1)
+fn chown_params(params: &Value, kind: &str) -> Result<(i64, i64, i64)> {
+    let id = params
+        .get("id")
+        .and_then(|v| v.as_i64())
+        .ok_or_else(|| anyhow::anyhow!("Missing {} id", kind))?;
+    let user_id = params.get("user_id").and_then(|v| v.as_i64()).unwrap_or(-1);
+    let group_id = params
+        .get("group_id")
+        .and_then(|v| v.as_i64())
+        .unwrap_or(-1);
+    if user_id == -1 && group_id == -1 {
+        return Err(anyhow::anyhow!("No new owner or group given"));
+    }
+    Ok((id, user_id, group_id))
+}
+
+/// Object to lock and the lock level picked for it
+fn lock_params(params: &Value, kind: &str) -> Result<(i64, i64)> {
+    let id = params
+        .get("id")
+        .and_then(|v| v.as_i64())
+        .ok_or_else(|| anyhow::anyhow!("Missing {} id", kind))?;
+    let level = params
+        .get("level")
+        .and_then(|v| v.as_i64())
+        .ok_or_else(|| anyhow::anyhow!("Missing lock level"))?;
+    Ok((id, level))
+}
+
+fn add_acl_rule(acl: &mut Value) {
+    if let Some(rule) = format_acl_rule(acl) {
+        if let Value::Object(map) = acl {
+            map.insert("RULE".to_string(), Value::String(rule));
+        }
+    }
+}
+
+fn tag_vm_id(nic: &mut Value, vm_id: i64) {
+    tag_field(nic, "VM_ID", vm_id);
+}
+
+/// Add a parent ID to a nested item so actions on it know the parent
+fn tag_field(item: &mut Value, field: &str, id: i64) {
+    if let Value::Object(map) = item {
+        map.insert(field.to_string(), Value::String(id.to_string()));
+    }
+}
+
+/// Leases of every address range of a vnet (`one.vn.info`), each tagged with
+/// its vnet, its range and a running `LEASE_ID`
+fn vnet_leases(vnet: &Value, vnet_id: i64) -> Vec<Value> {
+    let ars = super::collect_json_path(vnet, "VNET.AR_POOL.AR[*]");
+    let leases = ars.into_iter().flat_map(|ar| {
+        let ar_id = super::extract_json_value(ar, "AR_ID");
+        super::collect_json_path(ar, "LEASES.LEASE[*]")
+            .into_iter()
+            .map(move |lease| (ar_id.clone(), lease.clone()))
+    });
+    leases
+        .enumerate()
+        .map(|(index, (ar_id, mut lease))| {
+            tag_field(&mut lease, "VNET_ID", vnet_id);
+            tag_field(&mut lease, "LEASE_ID", index as i64);
+            if let Value::Object(map) = &mut lease {
+                map.insert("AR_ID".to_string(), Value::String(ar_id));
+            }
+            lease
+        })
+        .collect()
+}
+
+/// Lease template of `one.vn.hold` / `one.vn.release` for an IPv4 or MAC address
+fn lease_template(address: &str) -> Result<String> {
+    let address = address.trim();
+    let is_mac = address.split(':').count() == 6
+        && address
+            .split(':')
+            .all(|part| part.len() == 2 && part.chars().all(|c| c.is_ascii_hexdigit()));
+    if is_mac {
+        Ok(format!("LEASES = [ MAC = \"{}\" ]", address))
+    } else if address.parse::<std::net::Ipv4Addr>().is_ok() {
+        Ok(format!("LEASES = [ IP = \"{}\" ]", address))
+    } else {
+        Err(anyhow::anyhow!("Invalid IP address '{}'", address))
+    }
+}
+
+/// VM actions OpenNebula can schedule without extra arguments
+const SCHEDULABLE_ACTIONS: &[&str] = &[
+    "terminate",
+    "terminate-hard",
+    "undeploy",
+    "undeploy-hard",
+    "hold",
+    "release",
+    "stop",
+    "suspend",
+    "resume",
+    "reboot",
+    "reboot-hard",
+    "poweroff",
+    "poweroff-hard",
+];
+
+/// Unix time of a scheduled action typed by the user: `+2h` (from now, see
+/// `parse_duration`, plus `d` for days), unix seconds, `HH:MM` (next occurrence)
+/// or `YYYY-MM-DD HH:MM`, in local time
+fn sched_time(raw: &str, now: chrono::DateTime<chrono::Local>) -> Result<i64> {
+    use chrono::TimeZone;
+
+    let raw = raw.trim();
+    let invalid = || {
+        anyhow::anyhow!(
+            "Invalid time '{}'; use +2h, HH:MM, YYYY-MM-DD HH:MM or unix seconds",
+            raw
+        )
+    };
+    if let Some(offset) = raw.strip_prefix('+') {
+        let secs = match offset.strip_suffix('d') {
+            Some(days) => days.parse::<i64>().ok().map(|days| days * SECONDS_PER_DAY),
+            None => parse_duration(offset).and_then(|d| i64::try_from(d.as_secs()).ok()),
+        };
+        return Ok(now.timestamp() + secs.ok_or_else(invalid)?);
+    }
+    if let Ok(secs) = raw.parse::<i64>() {
+        return Ok(secs);
+    }
+    let local = |naive: chrono::NaiveDateTime| {
+        chrono::Local
+            .from_local_datetime(&naive)
+            .earliest()
+            .map(|t| t.timestamp())
+            .ok_or_else(invalid)
+    };
+    if let Ok(naive) = chrono::NaiveDateTime::parse_from_str(raw, "%Y-%m-%d %H:%M") {
+        return local(naive);
+    }
+    let time = chrono::NaiveTime::parse_from_str(raw, "%H:%M").map_err(|_| invalid())?;
+    let today = local(now.date_naive().and_time(time))?;
+    Ok(if today > now.timestamp() {
+        today
+    } else {
+        today + SECONDS_PER_DAY
+    })
+}
+
+/// Accounting range used when no `from` is given
+const DEFAULT_ACCOUNTING_DAYS: i64 = 30;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Optional point in time typed by the user: unix seconds or a `YYYY-MM-DD` date (UTC).
+/// With `end_of_day`, a date means the end of that day so ranges include it.
+fn time_param(params: &Value, key: &str, end_of_day: bool) -> Result<Option<i64>> {
+    let raw = match params.get(key) {
+        None | Some(Value::Null) => return Ok(None),
+        Some(Value::Number(n)) => return Ok(n.as_i64()),
+        Some(v) => v.as_str().unwrap_or_default().trim(),
+    };
+    if let Ok(secs) = raw.parse::<i64>() {
+        return Ok(Some(secs));
+    }
+    let date = chrono::NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+        .map_err(|_| anyhow::anyhow!("{} must be a date (YYYY-MM-DD), got '{}'", key, raw))?;
+    let start = date
+        .and_hms_opt(0, 0, 0)
+        .unwrap_or_default()
+        .and_utc()
+        .timestamp();
+    Ok(Some(if end_of_day {
+        start + SECONDS_PER_DAY - 1
+    } else {
+        start
+    }))
+}
+
+/// Optional capacity value typed by the user (string or number); must be a positive number
+fn capacity_param<T>(params: &Value, key: &str, label: &str) -> Result<Option<T>>
+where
+    T: std::str::FromStr + PartialOrd + Default,
+{
+    let raw = match params.get(key) {
+        None | Some(Value::Null) => return Ok(None),
+        Some(Value::String(s)) => s.trim().to_string(),
+        Some(other) => other.to_string(),
+    };
+    match raw.parse::<T>() {
+        Ok(value) if value > T::default() => Ok(Some(value)),
+        _ => Err(anyhow::anyhow!("Invalid {} value '{}'", label, raw)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vnet_leases_span_address_ranges() {
+        let vnet = serde_json::json!({"VNET": {"AR_POOL": {"AR": [
+            {"AR_ID": "0", "LEASES": {"LEASE": {"IP": "10.0.0.2", "VM": "-1"}}},
+            {"AR_ID": "1", "LEASES": {"LEASE": [{"IP": "10.0.1.2"}, {"IP": "10.0.1.3"}]}},
+            {"AR_ID": "2", "LEASES": {}}
+        ]}}});
+        let leases = vnet_leases(&vnet, 7);
+        let ids: Vec<(&str, &str)> = leases
+            .iter()
+            .map(|l| {
+                (
+                    l["AR_ID"].as_str().unwrap(),
+                    l["LEASE_ID"].as_str().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(ids, vec![("0", "0"), ("1", "1"), ("1", "2")]);
+        assert_eq!(leases[0]["VNET_ID"], "7");
+    }
+
+    #[test]
+    fn test_sched_time() {
+        use chrono::TimeZone;
+        let now = chrono::Local
+            .with_ymd_and_hms(2026, 10, 17, 21, 0, 0)
+            .unwrap();
+        let at = |h, m| {
+            chrono::Local
+                .with_ymd_and_hms(2026, 10, 17, h, m, 0)
+                .unwrap()
+                .timestamp()
+        };
+        assert_eq!(sched_time("+2h", now).unwrap(), now.timestamp() + 7200);
+        assert_eq!(sched_time("+1d", now).unwrap(), now.timestamp() + 86_400);
+        assert_eq!(sched_time("1760000000", now).unwrap(), 1_760_000_000);
+        assert_eq!(sched_time("22:00", now).unwrap(), at(22, 0));
+        // Already past today: tomorrow
+        assert_eq!(sched_time("08:30", now).unwrap(), at(8, 30) + 86_400);
+        assert_eq!(sched_time("2026-10-17 23:15", now).unwrap(), at(23, 15));
+        assert!(sched_time("+soon", now).is_err());
+        assert!(sched_time("tonight", now).is_err());
+    }
+
+    #[test]
+    fn test_lease_template() {
+        assert_eq!(
+            lease_template(" 10.0.0.5 ").unwrap(),
+            "LEASES = [ IP = \"10.0.0.5\" ]"
+        );
+        assert_eq!(
+            lease_template("02:00:0a:00:00:05").unwrap(),
+            "LEASES = [ MAC = \"02:00:0a:00:00:05\" ]"
+        );
+        assert!(lease_template("10.0.0.5\" ]").is_err());
+    }
+}
@@ -1,6 +1,6 @@
 //! Command box component
 
-use crate::app::App;
+use crate::app::{fuzzy_match, App};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
@@ -10,6 +10,7 @@ use ratatui::{
 };
 
 pub fn render(f: &mut Frame, app: &App) {
+    let theme = &app.theme;
     let area = bottom_rect(f.area());
     f.render_widget(Clear, area);
 
@@ -21,11 +22,11 @@ pub fn render(f: &mut Frame, app: &App) {
     // Command input
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan))
+        .border_style(Style::default().fg(theme.accent))
         .title(Span::styled(
             " Command ",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.accent)
                 .add_modifier(Modifier::BOLD),
         ));
 
@@ -57,27 +58,33 @@ pub fn render(f: &mut Frame, app: &App) {
     if !app.command_suggestions.is_empty() {
         let suggestions_block = Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::DarkGray))
+            .border_style(Style::default().fg(theme.border))
             .title(Span::styled(
                 " Suggestions ",
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme.muted),
             ));
 
+        let input = app.command_text.to_lowercase();
         let items: Vec<ListItem> = app
             .command_suggestions
             .iter()
             .enumerate()
             .take(10)
             .map(|(i, s)| {
-                let style = if i == app.command_suggestion_selected {
-                    Style::default()
+                let (style, match_style) = if i == app.command_suggestion_selected {
+                    let style = Style::default()
                         .fg(Color::Black)
-                        .bg(Color::Cyan)
-                        .add_modifier(Modifier::BOLD)
+                        .bg(theme.accent)
+                        .add_modifier(Modifier::BOLD);
+                    (style, style.add_modifier(Modifier::UNDERLINED))
                 } else {
-                    Style::default().fg(Color::White)
+                    let style = Style::default().fg(Color::White);
+                    (
+                        style,
+                        style.fg(theme.highlight).add_modifier(Modifier::BOLD),
+                    )
                 };
-                ListItem::new(Line::from(vec![Span::styled(format!(" {}", s), style)]))
+                ListItem::new(highlight_matches(s, &input, style, match_style))
             })
             .collect();
 
@@ -86,6 +93,28 @@ pub fn render(f: &mut Frame, app: &App) {
     }
 }
 
+/// Suggestion line with the characters matched by the typed text highlighted
+fn highlight_matches(
+    suggestion: &str,
+    input: &str,
+    style: Style,
+    match_style: Style,
+) -> Line<'static> {
+    let matched = fuzzy_match(input, suggestion)
+        .map(|(_, positions)| positions)
+        .unwrap_or_default();
+    let mut spans = vec![Span::styled(" ", style)];
+    spans.extend(suggestion.chars().enumerate().map(|(i, c)| {
+        let style = if matched.contains(&i) {
+            match_style
+        } else {
+            style
+        };
+        Span::styled(c.to_string(), style)
+    }));
+    Line::from(spans)
+}
+
 fn bottom_rect(r: Rect) -> Rect {
     let height = 15.min(r.height.saturating_sub(5));
     Layout::default()
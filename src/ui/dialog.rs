@@ -1,11 +1,12 @@
 //! Dialog components for confirmations and warnings
 
-use crate::app::{App, Mode};
+use super::theme::Theme;
+use crate::app::{App, InputField, Mode};
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, Paragraph},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
     Frame,
 };
 
@@ -13,22 +14,174 @@ pub fn render(f: &mut Frame, app: &App) {
     match app.mode {
         Mode::Confirm => render_confirm(f, app),
         Mode::Warning => render_warning(f, app),
+        Mode::Select => render_select(f, app),
+        Mode::Input => render_input(f, app),
         _ => {}
     }
 }
 
+fn render_select(f: &mut Frame, app: &App) {
+    let theme = &app.theme;
+    let Some(select) = &app.select else {
+        return;
+    };
+
+    let height = (select.options.len() as u16 + 3).clamp(5, 17);
+    let area = centered_rect(50, height, f.area());
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(Span::styled(
+            format!(" Select {} ", select.label),
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .title_bottom(Line::from(Span::styled(
+            " j/k: move  Enter: select  Esc: cancel ",
+            Style::default().fg(theme.muted),
+        )))
+        .title_alignment(Alignment::Center);
+
+    let items: Vec<ListItem> = select
+        .options
+        .iter()
+        .map(|(id, name)| ListItem::new(format!(" {:>4}  {}", id, name)))
+        .collect();
+
+    let list = List::new(items).block(block).highlight_style(
+        Style::default()
+            .fg(Color::Black)
+            .bg(theme.accent)
+            .add_modifier(Modifier::BOLD),
+    );
+
+    let mut state = ListState::default();
+    state.select(Some(select.selected));
+    f.render_stateful_widget(list, area, &mut state);
+}
+
+fn render_input(f: &mut Frame, app: &App) {
+    let theme = &app.theme;
+    let Some(input) = &app.input else {
+        return;
+    };
+
+    // Prompt line plus field line per field, with a blank line between fields
+    let height = input.fields.len() as u16 * 3 + 2;
+    let area = centered_rect(50, height, f.area());
+    f.render_widget(Clear, area);
+
+    let hints = if input.fields.iter().any(|field| field.toggle.is_some()) {
+        " Enter: apply  Tab: next  Space: toggle  Esc: cancel "
+    } else if input.fields.len() > 1 {
+        " Enter: apply  Tab: next field  Esc: cancel "
+    } else {
+        " Enter: apply  Esc: cancel  Ctrl+u: clear "
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.accent))
+        .title(Span::styled(
+            format!(" {} ", input.pending.message.trim_end_matches('?')),
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .title_bottom(Line::from(Span::styled(
+            hints,
+            Style::default().fg(theme.muted),
+        )))
+        .title_alignment(Alignment::Center);
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            input
+                .fields
+                .iter()
+                .map(|_| Constraint::Length(3))
+                .collect::<Vec<_>>(),
+        )
+        .split(inner);
+
+    for (i, (field, chunk)) in input.fields.iter().zip(chunks.iter()).enumerate() {
+        let focused = i == input.focused;
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Length(1)])
+            .split(*chunk);
+
+        let prompt_style = if focused {
+            Style::default().fg(Color::White)
+        } else {
+            Style::default().fg(theme.muted)
+        };
+        f.render_widget(
+            Paragraph::new(Span::styled(&field.prompt, prompt_style)),
+            rows[0],
+        );
+        f.render_widget(render_field(field, focused, theme), rows[1]);
+    }
+}
+
+/// Field text, with a block cursor on the char under the cursor when focused
+fn render_field(field: &InputField, focused: bool, theme: &Theme) -> Paragraph<'static> {
+    if let Some(on) = field.toggle {
+        let style = if focused {
+            Style::default().fg(Color::Black).bg(theme.highlight)
+        } else {
+            Style::default().fg(Color::Gray)
+        };
+        let mark = if on { "[x]" } else { "[ ]" };
+        return Paragraph::new(Line::from(vec![Span::raw(" "), Span::styled(mark, style)]));
+    }
+    let text = if field.masked {
+        "*".repeat(field.buffer.chars().count())
+    } else {
+        field.buffer.clone()
+    };
+    if !focused {
+        return Paragraph::new(Line::from(vec![
+            Span::raw(" "),
+            Span::styled(text, Style::default().fg(Color::Gray)),
+        ]));
+    }
+    let before: String = text.chars().take(field.cursor).collect();
+    let under: String = text
+        .chars()
+        .nth(field.cursor)
+        .map(String::from)
+        .unwrap_or_else(|| " ".to_string());
+    let after: String = text.chars().skip(field.cursor + 1).collect();
+    Paragraph::new(Line::from(vec![
+        Span::raw(" "),
+        Span::styled(before, Style::default().fg(theme.highlight)),
+        Span::styled(under, Style::default().fg(Color::Black).bg(theme.highlight)),
+        Span::styled(after, Style::default().fg(theme.highlight)),
+    ]))
+}
+
 fn render_confirm(f: &mut Frame, app: &App) {
+    let theme = &app.theme;
     let Some(pending) = &app.pending_action else {
         return;
     };
 
-    let area = centered_rect(50, 8, f.area());
+    let warning_height = if pending.warning.is_some() { 2 } else { 0 };
+    let preview_height = if pending.preview.is_some() { 1 } else { 0 };
+    let area = centered_rect(50, 8 + warning_height + preview_height, f.area());
     f.render_widget(Clear, area);
 
     let border_color = if pending.destructive {
-        Color::Red
+        theme.error
     } else {
-        Color::Yellow
+        theme.highlight
     };
 
     let block = Block::default()
@@ -53,6 +206,8 @@ fn render_confirm(f: &mut Frame, app: &App) {
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(2),
+            Constraint::Length(preview_height),
+            Constraint::Length(warning_height),
             Constraint::Length(1),
             Constraint::Length(1),
         ])
@@ -66,27 +221,48 @@ fn render_confirm(f: &mut Frame, app: &App) {
     .alignment(Alignment::Center);
     f.render_widget(message, chunks[0]);
 
+    // Resolved API call
+    if let Some(ref preview) = pending.preview {
+        let preview_para = Paragraph::new(Line::from(vec![Span::styled(
+            preview,
+            Style::default().fg(theme.muted),
+        )]))
+        .alignment(Alignment::Center);
+        f.render_widget(preview_para, chunks[1]);
+    }
+
+    // Transitional state warning
+    if let Some(ref warning) = pending.warning {
+        let warning_para = Paragraph::new(Line::from(vec![Span::styled(
+            warning,
+            Style::default().fg(theme.highlight),
+        )]))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+        f.render_widget(warning_para, chunks[2]);
+    }
+
     // Buttons
     let yes_style = if pending.selected_yes {
         Style::default()
             .fg(Color::Black)
             .bg(if pending.destructive {
-                Color::Red
+                theme.error
             } else {
                 Color::Green
             })
             .add_modifier(Modifier::BOLD)
     } else {
-        Style::default().fg(Color::DarkGray)
+        Style::default().fg(theme.muted)
     };
 
     let no_style = if !pending.selected_yes {
         Style::default()
             .fg(Color::Black)
-            .bg(Color::Yellow)
+            .bg(theme.highlight)
             .add_modifier(Modifier::BOLD)
     } else {
-        Style::default().fg(Color::DarkGray)
+        Style::default().fg(theme.muted)
     };
 
     let buttons = Line::from(vec![
@@ -97,18 +273,19 @@ fn render_confirm(f: &mut Frame, app: &App) {
         Span::raw("       "),
     ]);
     let buttons_para = Paragraph::new(buttons).alignment(Alignment::Center);
-    f.render_widget(buttons_para, chunks[1]);
+    f.render_widget(buttons_para, chunks[3]);
 
     // Hint
     let hint = Paragraph::new(Line::from(vec![Span::styled(
         "y/n or Enter to confirm, Esc to cancel",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme.muted),
     )]))
     .alignment(Alignment::Center);
-    f.render_widget(hint, chunks[2]);
+    f.render_widget(hint, chunks[4]);
 }
 
 fn render_warning(f: &mut Frame, app: &App) {
+    let theme = &app.theme;
     let Some(message) = &app.warning_message else {
         return;
     };
@@ -118,11 +295,11 @@ fn render_warning(f: &mut Frame, app: &App) {
 
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Yellow))
+        .border_style(Style::default().fg(theme.highlight))
         .title(Span::styled(
             " Warning ",
             Style::default()
-                .fg(Color::Yellow)
+                .fg(theme.highlight)
                 .add_modifier(Modifier::BOLD),
         ))
         .title_alignment(Alignment::Center);
@@ -144,7 +321,7 @@ fn render_warning(f: &mut Frame, app: &App) {
 
     let hint = Paragraph::new(Line::from(vec![Span::styled(
         "Press Enter or Esc to close",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme.muted),
     )]))
     .alignment(Alignment::Center);
     f.render_widget(hint, chunks[1]);
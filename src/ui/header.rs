@@ -10,13 +10,14 @@ use ratatui::{
 };
 
 pub fn render(f: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.theme;
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::DarkGray))
+        .border_style(Style::default().fg(theme.border))
         .title(Span::styled(
             " tone ",
             Style::default()
-                .fg(Color::Magenta)
+                .fg(theme.header)
                 .add_modifier(Modifier::BOLD),
         ))
         .title_alignment(Alignment::Center);
@@ -34,20 +35,46 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
         .split(inner);
 
     // Line 1: Endpoint
-    let endpoint_line = Line::from(vec![
-        Span::styled(" Endpoint: ", Style::default().fg(Color::DarkGray)),
-        Span::styled(&app.endpoint, Style::default().fg(Color::Cyan)),
-    ]);
+    let status_dot = if app.connected {
+        Span::styled(" ● ", Style::default().fg(theme.success))
+    } else {
+        Span::styled(" ● ", Style::default().fg(theme.error))
+    };
+    let mut endpoint_spans = vec![
+        status_dot,
+        Span::styled("Endpoint: ", Style::default().fg(theme.muted)),
+        Span::styled(&app.endpoint, Style::default().fg(theme.accent)),
+    ];
+    if let Some(ref version) = app.server_version {
+        endpoint_spans.push(Span::styled(" | ", Style::default().fg(theme.muted)));
+        endpoint_spans.push(Span::styled(
+            "OpenNebula ",
+            Style::default().fg(theme.muted),
+        ));
+        endpoint_spans.push(Span::styled(version, Style::default().fg(theme.accent)));
+    }
+    if let Some(ref zone) = app.zone {
+        endpoint_spans.push(Span::styled(" | ", Style::default().fg(theme.muted)));
+        endpoint_spans.push(Span::styled("Zone: ", Style::default().fg(theme.muted)));
+        endpoint_spans.push(Span::styled(zone, Style::default().fg(theme.accent)));
+    }
+    if !app.connected {
+        endpoint_spans.push(Span::styled(
+            " | unreachable, data may be stale",
+            Style::default().fg(theme.error),
+        ));
+    }
+    let endpoint_line = Line::from(endpoint_spans);
     f.render_widget(Paragraph::new(endpoint_line), chunks[0]);
 
     // Line 2: User info
     let user_line = Line::from(vec![
-        Span::styled(" User: ", Style::default().fg(Color::DarkGray)),
+        Span::styled(" User: ", Style::default().fg(theme.muted)),
         Span::styled(&app.username, Style::default().fg(Color::Green)),
-        Span::styled(" | ", Style::default().fg(Color::DarkGray)),
-        Span::styled("Mode: ", Style::default().fg(Color::DarkGray)),
+        Span::styled(" | ", Style::default().fg(theme.muted)),
+        Span::styled("Mode: ", Style::default().fg(theme.muted)),
         if app.readonly {
-            Span::styled("READ-ONLY", Style::default().fg(Color::Yellow))
+            Span::styled("READ-ONLY", Style::default().fg(theme.highlight))
         } else {
             Span::styled("READ-WRITE", Style::default().fg(Color::Green))
         },
@@ -57,16 +84,16 @@ pub fn render(f: &mut Frame, app: &App, area: Rect) {
     // Line 3: Shortcuts
     let shortcuts_line = Line::from(vec![
         Span::styled(" ", Style::default()),
-        Span::styled("?", Style::default().fg(Color::Yellow)),
-        Span::styled(":help ", Style::default().fg(Color::DarkGray)),
-        Span::styled(":", Style::default().fg(Color::Yellow)),
-        Span::styled(":command ", Style::default().fg(Color::DarkGray)),
-        Span::styled("/", Style::default().fg(Color::Yellow)),
-        Span::styled(":filter ", Style::default().fg(Color::DarkGray)),
-        Span::styled("R", Style::default().fg(Color::Yellow)),
-        Span::styled(":refresh ", Style::default().fg(Color::DarkGray)),
-        Span::styled("q", Style::default().fg(Color::Yellow)),
-        Span::styled(":quit", Style::default().fg(Color::DarkGray)),
+        Span::styled("?", Style::default().fg(theme.highlight)),
+        Span::styled(":help ", Style::default().fg(theme.muted)),
+        Span::styled(":", Style::default().fg(theme.highlight)),
+        Span::styled(":command ", Style::default().fg(theme.muted)),
+        Span::styled("/", Style::default().fg(theme.highlight)),
+        Span::styled(":filter ", Style::default().fg(theme.muted)),
+        Span::styled("R", Style::default().fg(theme.highlight)),
+        Span::styled(":refresh ", Style::default().fg(theme.muted)),
+        Span::styled("q", Style::default().fg(theme.highlight)),
+        Span::styled(":quit", Style::default().fg(theme.muted)),
     ]);
     f.render_widget(Paragraph::new(shortcuts_line), chunks[2]);
 }
@@ -3,23 +3,24 @@
 use crate::app::App;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph},
     Frame,
 };
 
-pub fn render(f: &mut Frame, _app: &App) {
+pub fn render(f: &mut Frame, app: &App) {
+    let theme = &app.theme;
     let area = centered_rect(70, 80, f.area());
     f.render_widget(Clear, area);
 
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan))
+        .border_style(Style::default().fg(theme.accent))
         .title(Span::styled(
             " Help ",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.accent)
                 .add_modifier(Modifier::BOLD),
         ))
         .title_alignment(Alignment::Center);
@@ -28,137 +29,382 @@ pub fn render(f: &mut Frame, _app: &App) {
         Line::from(vec![Span::styled(
             "Navigation",
             Style::default()
-                .fg(Color::Yellow)
+                .fg(theme.highlight)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(vec![
-            Span::styled("  j/k, Up/Down  ", Style::default().fg(Color::Cyan)),
+            Span::styled("  j/k, Up/Down  ", Style::default().fg(theme.accent)),
             Span::raw("Navigate up/down"),
         ]),
         Line::from(vec![
-            Span::styled("  gg            ", Style::default().fg(Color::Cyan)),
+            Span::styled("  gg            ", Style::default().fg(theme.accent)),
             Span::raw("Go to top"),
         ]),
         Line::from(vec![
-            Span::styled("  G             ", Style::default().fg(Color::Cyan)),
+            Span::styled("  G             ", Style::default().fg(theme.accent)),
             Span::raw("Go to bottom"),
         ]),
         Line::from(vec![
-            Span::styled("  Ctrl+f/b      ", Style::default().fg(Color::Cyan)),
+            Span::styled("  Ctrl+f/b      ", Style::default().fg(theme.accent)),
             Span::raw("Page down/up"),
         ]),
         Line::from(vec![
-            Span::styled("  b, Backspace  ", Style::default().fg(Color::Cyan)),
+            Span::styled("  ] / [         ", Style::default().fg(theme.accent)),
+            Span::raw("Next/previous page"),
+        ]),
+        Line::from(vec![
+            Span::styled("  b, Backspace  ", Style::default().fg(theme.accent)),
             Span::raw("Go back"),
         ]),
         Line::from(""),
         Line::from(vec![Span::styled(
             "Commands",
             Style::default()
-                .fg(Color::Yellow)
+                .fg(theme.highlight)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(vec![
-            Span::styled("  :             ", Style::default().fg(Color::Cyan)),
+            Span::styled("  :             ", Style::default().fg(theme.accent)),
             Span::raw("Open command mode"),
         ]),
         Line::from(vec![
-            Span::styled("  /             ", Style::default().fg(Color::Cyan)),
-            Span::raw("Filter items"),
+            Span::styled("  /             ", Style::default().fg(theme.accent)),
+            Span::raw("Filter items (header:value for one column, ~ for regex)"),
         ]),
         Line::from(vec![
-            Span::styled("  Enter, d      ", Style::default().fg(Color::Cyan)),
+            Span::styled("  Enter, d      ", Style::default().fg(theme.accent)),
             Span::raw("View details (JSON)"),
         ]),
         Line::from(vec![
-            Span::styled("  R             ", Style::default().fg(Color::Cyan)),
+            Span::styled("  > / <         ", Style::default().fg(theme.accent)),
+            Span::raw("Sort by next column / reverse order"),
+        ]),
+        Line::from(vec![
+            Span::styled("  ← / →         ", Style::default().fg(theme.accent)),
+            Span::raw("Scroll columns of wide tables"),
+        ]),
+        Line::from(vec![
+            Span::styled("  R             ", Style::default().fg(theme.accent)),
             Span::raw("Refresh"),
         ]),
         Line::from(vec![
-            Span::styled("  ?             ", Style::default().fg(Color::Cyan)),
+            Span::styled("  Esc           ", Style::default().fg(theme.accent)),
+            Span::raw("Cancel loading"),
+        ]),
+        Line::from(vec![
+            Span::styled("  .             ", Style::default().fg(theme.accent)),
+            Span::raw("Repeat last action on selected item"),
+        ]),
+        Line::from(vec![
+            Span::styled("  Space         ", Style::default().fg(theme.accent)),
+            Span::raw("Mark item for batch actions (Esc clears)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  E             ", Style::default().fg(theme.accent)),
+            Span::raw("Action log (also :log)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  ?             ", Style::default().fg(theme.accent)),
             Span::raw("Show this help"),
         ]),
         Line::from(vec![
-            Span::styled("  q             ", Style::default().fg(Color::Cyan)),
+            Span::styled("  q             ", Style::default().fg(theme.accent)),
             Span::raw("Quit"),
         ]),
         Line::from(""),
         Line::from(vec![Span::styled(
             "VM Actions",
             Style::default()
-                .fg(Color::Yellow)
+                .fg(theme.highlight)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(vec![
-            Span::styled("  r             ", Style::default().fg(Color::Cyan)),
+            Span::styled("  r             ", Style::default().fg(theme.accent)),
             Span::raw("Resume VM"),
         ]),
         Line::from(vec![
-            Span::styled("  u             ", Style::default().fg(Color::Cyan)),
+            Span::styled("  u             ", Style::default().fg(theme.accent)),
             Span::raw("Suspend VM"),
         ]),
         Line::from(vec![
-            Span::styled("  s             ", Style::default().fg(Color::Cyan)),
+            Span::styled("  s             ", Style::default().fg(theme.accent)),
             Span::raw("Stop VM"),
         ]),
         Line::from(vec![
-            Span::styled("  S             ", Style::default().fg(Color::Cyan)),
+            Span::styled("  S             ", Style::default().fg(theme.accent)),
             Span::raw("Power off VM"),
         ]),
         Line::from(vec![
-            Span::styled("  R             ", Style::default().fg(Color::Cyan)),
+            Span::styled("  R             ", Style::default().fg(theme.accent)),
             Span::raw("Reboot VM"),
         ]),
         Line::from(vec![
-            Span::styled("  Ctrl+d        ", Style::default().fg(Color::Red)),
+            Span::styled("  D             ", Style::default().fg(theme.accent)),
+            Span::raw("Deploy VM to a host and datastore"),
+        ]),
+        Line::from(vec![
+            Span::styled("  n             ", Style::default().fg(theme.accent)),
+            Span::raw("Rename (VMs, hosts, images, templates, vnets)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  z             ", Style::default().fg(theme.accent)),
+            Span::raw("Resize VM CPU/memory (powered off)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  T             ", Style::default().fg(theme.accent)),
+            Span::raw("Save VM as template (powered off)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  a             ", Style::default().fg(theme.accent)),
+            Span::raw("Attach NIC from a virtual network"),
+        ]),
+        Line::from(vec![
+            Span::styled("  i             ", Style::default().fg(theme.accent)),
+            Span::raw("List VM NICs (x: detach NIC)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  A / w         ", Style::default().fg(theme.accent)),
+            Span::raw("Schedule an action / list scheduled actions"),
+        ]),
+        Line::from(vec![
+            Span::styled("  B             ", Style::default().fg(theme.accent)),
+            Span::raw("Back up VM to a backup datastore"),
+        ]),
+        Line::from(vec![
+            Span::styled("  m             ", Style::default().fg(theme.accent)),
+            Span::raw("Live migrate VM to another host"),
+        ]),
+        Line::from(vec![
+            Span::styled("  c             ", Style::default().fg(theme.accent)),
+            Span::raw("Connect to VM console (connect_command)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  F             ", Style::default().fg(theme.accent)),
+            Span::raw("Recover VM stuck in a *_FAILURE state"),
+        ]),
+        Line::from(vec![
+            Span::styled("  Ctrl+d        ", Style::default().fg(theme.error)),
             Span::raw("Terminate VM (destructive)"),
         ]),
         Line::from(""),
+        Line::from(vec![Span::styled(
+            "Host Actions",
+            Style::default()
+                .fg(theme.highlight)
+                .add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(vec![
+            Span::styled("  e / x / o     ", Style::default().fg(theme.accent)),
+            Span::raw("Enable / disable / set host offline"),
+        ]),
+        Line::from(vec![
+            Span::styled("  F             ", Style::default().fg(theme.error)),
+            Span::raw("Flush host (destructive)"),
+        ]),
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "Datastore Actions",
+            Style::default()
+                .fg(theme.highlight)
+                .add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(vec![
+            Span::styled("  e / x         ", Style::default().fg(theme.accent)),
+            Span::raw("Enable / disable datastore"),
+        ]),
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "Image Actions",
+            Style::default()
+                .fg(theme.highlight)
+                .add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(vec![
+            Span::styled("  e / x         ", Style::default().fg(theme.accent)),
+            Span::raw("Enable / disable image"),
+        ]),
+        Line::from(vec![
+            Span::styled("  p             ", Style::default().fg(theme.accent)),
+            Span::raw("Toggle persistent"),
+        ]),
+        Line::from(vec![
+            Span::styled("  c             ", Style::default().fg(theme.accent)),
+            Span::raw("Clone image (name, then datastore)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  Ctrl+d        ", Style::default().fg(theme.error)),
+            Span::raw("Delete image (destructive, not while in use)"),
+        ]),
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "Template Actions",
+            Style::default()
+                .fg(theme.highlight)
+                .add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(vec![
+            Span::styled("  i             ", Style::default().fg(theme.accent)),
+            Span::raw("Instantiate a VM (optionally on hold)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  c             ", Style::default().fg(theme.accent)),
+            Span::raw("Clone template"),
+        ]),
+        Line::from(vec![
+            Span::styled("  Ctrl+d / X    ", Style::default().fg(theme.error)),
+            Span::raw("Delete template / with its images (destructive)"),
+        ]),
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "Virtual Network Actions",
+            Style::default()
+                .fg(theme.highlight)
+                .add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(vec![
+            Span::styled("  h / r         ", Style::default().fg(theme.accent)),
+            Span::raw("Hold a lease / reserve addresses"),
+        ]),
+        Line::from(vec![
+            Span::styled("  x             ", Style::default().fg(theme.accent)),
+            Span::raw("Release a held lease (in the lease list)"),
+        ]),
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "User Actions",
+            Style::default()
+                .fg(theme.highlight)
+                .add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(vec![
+            Span::styled("  a / P         ", Style::default().fg(theme.accent)),
+            Span::raw("Create user / change password"),
+        ]),
+        Line::from(vec![
+            Span::styled("  A / D         ", Style::default().fg(theme.accent)),
+            Span::raw("Add user to / remove from a group"),
+        ]),
+        Line::from(vec![
+            Span::styled("  a             ", Style::default().fg(theme.accent)),
+            Span::raw("Create group (in the group list)"),
+        ]),
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "Ownership and locks (VMs, images, templates, vnets)",
+            Style::default()
+                .fg(theme.highlight)
+                .add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(vec![
+            Span::styled("  O / C         ", Style::default().fg(theme.accent)),
+            Span::raw("Change owner / group"),
+        ]),
+        Line::from(vec![
+            Span::styled("  L / U         ", Style::default().fg(theme.accent)),
+            Span::raw("Lock (pick a level) / unlock"),
+        ]),
+        Line::from(""),
         Line::from(vec![Span::styled(
             "Resources",
             Style::default()
-                .fg(Color::Yellow)
+                .fg(theme.highlight)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(vec![
-            Span::styled("  :one-vms      ", Style::default().fg(Color::Cyan)),
-            Span::raw("Virtual Machines"),
+            Span::styled("  :one-vms      ", Style::default().fg(theme.accent)),
+            Span::raw("Virtual Machines (state=running, poweroff, ...)"),
         ]),
         Line::from(vec![
-            Span::styled("  :one-hosts    ", Style::default().fg(Color::Cyan)),
+            Span::styled("  :one-hosts    ", Style::default().fg(theme.accent)),
             Span::raw("Hosts"),
         ]),
         Line::from(vec![
-            Span::styled("  :one-datastores", Style::default().fg(Color::Cyan)),
+            Span::styled("  :one-datastores", Style::default().fg(theme.accent)),
             Span::raw("Datastores"),
         ]),
         Line::from(vec![
-            Span::styled("  :one-vnets    ", Style::default().fg(Color::Cyan)),
-            Span::raw("Virtual Networks"),
+            Span::styled("  :one-vnets    ", Style::default().fg(theme.accent)),
+            Span::raw("Virtual Networks (s/a/l: secgroups/ARs/leases)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  :one-secgroups ", Style::default().fg(theme.accent)),
+            Span::raw("Security Groups"),
+        ]),
+        Line::from(vec![
+            Span::styled("  :one-vrouters ", Style::default().fg(theme.accent)),
+            Span::raw("Virtual Routers (v: VMs, i: NICs)"),
         ]),
         Line::from(vec![
-            Span::styled("  :one-images   ", Style::default().fg(Color::Cyan)),
+            Span::styled("  :one-images   ", Style::default().fg(theme.accent)),
             Span::raw("Images"),
         ]),
         Line::from(vec![
-            Span::styled("  :one-templates", Style::default().fg(Color::Cyan)),
+            Span::styled("  :one-templates", Style::default().fg(theme.accent)),
             Span::raw("VM Templates"),
         ]),
         Line::from(vec![
-            Span::styled("  :one-clusters ", Style::default().fg(Color::Cyan)),
+            Span::styled("  :one-vmgroups ", Style::default().fg(theme.accent)),
+            Span::raw("VM Groups (r: roles, v: VMs)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  :one-marketplaces ", Style::default().fg(theme.accent)),
+            Span::raw("Marketplaces (a: apps)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  :one-clusters ", Style::default().fg(theme.accent)),
             Span::raw("Clusters"),
         ]),
         Line::from(vec![
-            Span::styled("  :one-users    ", Style::default().fg(Color::Cyan)),
-            Span::raw("Users"),
+            Span::styled("  :one-users    ", Style::default().fg(theme.accent)),
+            Span::raw("Users (Q: quotas)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  :one-acls     ", Style::default().fg(theme.accent)),
+            Span::raw("ACL rules"),
+        ]),
+        Line::from(vec![
+            Span::styled("  :capacity     ", Style::default().fg(theme.accent)),
+            Span::raw("Host capacity"),
+        ]),
+        Line::from(vec![
+            Span::styled("  :one-accounting ", Style::default().fg(theme.accent)),
+            Span::raw("VM usage (from=YYYY-MM-DD to=YYYY-MM-DD)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  :vm 42 / :42  ", Style::default().fg(theme.accent)),
+            Span::raw("Describe an item by ID"),
+        ]),
+        Line::from(vec![
+            Span::styled("  :login        ", Style::default().fg(theme.accent)),
+            Span::raw("Reload credentials and reconnect"),
+        ]),
+        Line::from(vec![
+            Span::styled("  :zone 100     ", Style::default().fg(theme.accent)),
+            Span::raw("Switch to a federation zone's endpoint"),
+        ]),
+        Line::from(vec![
+            Span::styled("  :export csv   ", Style::default().fg(theme.accent)),
+            Span::raw("Save the list to the config dir (csv or json)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  :readonly on  ", Style::default().fg(theme.accent)),
+            Span::raw("Disable actions (off asks before enabling them)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  :col add H P  ", Style::default().fg(theme.accent)),
+            Span::raw("Add column H from JSON path P (:col rm H)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  Ctrl+p/n      ", Style::default().fg(theme.accent)),
+            Span::raw("Previous/next command from history"),
         ]),
         Line::from(""),
         Line::from(vec![
-            Span::styled("Press ", Style::default().fg(Color::DarkGray)),
-            Span::styled("q", Style::default().fg(Color::Yellow)),
-            Span::styled(" or ", Style::default().fg(Color::DarkGray)),
-            Span::styled("Esc", Style::default().fg(Color::Yellow)),
-            Span::styled(" to close", Style::default().fg(Color::DarkGray)),
+            Span::styled("Press ", Style::default().fg(theme.muted)),
+            Span::styled("q", Style::default().fg(theme.highlight)),
+            Span::styled(" or ", Style::default().fg(theme.muted)),
+            Span::styled("Esc", Style::default().fg(theme.highlight)),
+            Span::styled(" to close", Style::default().fg(theme.muted)),
         ]),
     ];
 
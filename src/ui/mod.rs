@@ -7,9 +7,12 @@ mod dialog;
 mod header;
 mod help;
 pub mod splash;
+pub mod theme;
 
-use crate::app::{App, Mode};
-use crate::resource::{extract_json_value, get_color_for_value, ColumnDef};
+use crate::app::{App, DescribeFormat, Mode};
+use crate::resource::{
+    format_display_value, get_color_for_value, lock_level, usage_color, usage_ratio, ColumnDef,
+};
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
@@ -20,6 +23,7 @@ use ratatui::{
     },
     Frame,
 };
+use serde_json::Value;
 
 pub fn render(f: &mut Frame, app: &App) {
     let chunks = Layout::default()
@@ -39,6 +43,12 @@ pub fn render(f: &mut Frame, app: &App) {
         Mode::Describe => {
             render_describe_view(f, app, chunks[1]);
         }
+        Mode::Command if app.describe_data.is_some() => {
+            render_describe_view(f, app, chunks[1]);
+        }
+        Mode::Log => {
+            render_log_view(f, app, chunks[1]);
+        }
         _ => {
             render_main_content(f, app, chunks[1]);
         }
@@ -52,7 +62,7 @@ pub fn render(f: &mut Frame, app: &App) {
         Mode::Help => {
             help::render(f, app);
         }
-        Mode::Confirm | Mode::Warning => {
+        Mode::Confirm | Mode::Warning | Mode::Select | Mode::Input => {
             dialog::render(f, app);
         }
         Mode::Command => {
@@ -79,12 +89,14 @@ fn render_main_content(f: &mut Frame, app: &App, area: Rect) {
 }
 
 fn render_filter_bar(f: &mut Frame, app: &App, area: Rect) {
-    let cursor_style = if app.filter_active {
+    let cursor_style = if app.filter_error().is_some() {
+        Style::default().fg(app.theme.error)
+    } else if app.filter_active {
         Style::default()
-            .fg(Color::Yellow)
+            .fg(app.theme.highlight)
             .add_modifier(Modifier::BOLD)
     } else {
-        Style::default().fg(Color::DarkGray)
+        Style::default().fg(app.theme.muted)
     };
 
     let filter_display = if app.filter_active {
@@ -99,13 +111,20 @@ fn render_filter_bar(f: &mut Frame, app: &App, area: Rect) {
 
 fn render_dynamic_table(f: &mut Frame, app: &App, area: Rect) {
     let Some(resource) = app.current_resource() else {
-        let msg = Paragraph::new("Unknown resource").style(Style::default().fg(Color::Red));
+        let msg = Paragraph::new("Unknown resource").style(Style::default().fg(app.theme.error));
         f.render_widget(msg, area);
         return;
     };
 
+    // Rows of the previous view don't belong under the new header
+    let items: &[Value] = if app.view_pending() {
+        &[]
+    } else {
+        &app.filtered_items
+    };
+
     let title = {
-        let count = app.filtered_items.len();
+        let count = items.len();
         let total = app.items.len();
 
         let page_info = if app.pagination.has_more || app.pagination.current_page > 1 {
@@ -118,6 +137,20 @@ fn render_dynamic_table(f: &mut Frame, app: &App, area: Rect) {
             String::new()
         };
 
+        // Server-side filters from the command, e.g. " state=3"
+        let page_info = app
+            .command_filters
+            .iter()
+            .map(|f| format!(" {}={}", f.name, f.values.join(",")))
+            .collect::<String>()
+            + &page_info;
+
+        let page_info = if app.selected_set.is_empty() {
+            page_info
+        } else {
+            format!("{} {} marked", page_info, app.selected_set.len())
+        };
+
         if app.filter_text.is_empty() {
             format!(" {}[{}]{} ", resource.display_name, count, page_info)
         } else {
@@ -130,50 +163,105 @@ fn render_dynamic_table(f: &mut Frame, app: &App, area: Rect) {
 
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::DarkGray))
+        .border_style(Style::default().fg(app.theme.border))
         .title(Span::styled(
             title,
             Style::default()
-                .fg(Color::Cyan)
+                .fg(app.theme.accent)
                 .add_modifier(Modifier::BOLD),
         ))
         .title_alignment(Alignment::Center);
 
     let inner_area = block.inner(area);
+    // One line goes to the header row
+    app.table_viewport
+        .set((inner_area.height as usize).saturating_sub(1));
+
+    let all_columns = app.visible_columns();
+    let offset = app.column_offset.min(all_columns.len().saturating_sub(1));
+    let shown = columns_that_fit(&all_columns, offset, inner_area.width);
+    let hidden_right = all_columns.len() - offset - shown;
+    app.columns_hidden_right.set(hidden_right > 0);
+    let columns = &all_columns[offset..offset + shown];
+
+    // `<` / `>` when columns are scrolled out of view
+    let more_style = Style::default().fg(app.theme.muted);
+    let block = if offset > 0 {
+        block.title_bottom(Line::styled(format!(" < {} more ", offset), more_style).left_aligned())
+    } else {
+        block
+    };
+    let block = if hidden_right > 0 {
+        block.title_bottom(
+            Line::styled(format!(" {} more > ", hidden_right), more_style).right_aligned(),
+        )
+    } else {
+        block
+    };
     f.render_widget(block, area);
 
     // Build header
-    let header_cells = resource.columns.iter().map(|col| {
-        Cell::from(format!(" {}", col.header)).style(
+    let header_cells = columns.iter().enumerate().map(|(i, col)| {
+        let i = offset + i;
+        let arrow = match app.sort_column {
+            Some(sorted) if sorted == i && app.sort_desc => " ▼",
+            Some(sorted) if sorted == i => " ▲",
+            _ => "",
+        };
+        Cell::from(format!(" {}{}", col.header, arrow)).style(
             Style::default()
-                .fg(Color::Yellow)
+                .fg(app.theme.highlight)
                 .add_modifier(Modifier::BOLD),
         )
     });
     let header = Row::new(header_cells).height(1);
 
     // Build rows
-    let rows = app.filtered_items.iter().map(|item| {
-        let cells = resource.columns.iter().map(|col| {
-            let raw_value = extract_json_value(item, &col.json_path);
-            let display_value = format_display_value(&raw_value, col);
-            let style = get_cell_style(&display_value, col);
-            Cell::from(format!(" {}", truncate_string(&display_value, 38))).style(style)
+    let rows = items.iter().map(|item| {
+        let lock = lock_level(item);
+        let marked = app.is_marked(item);
+        let cells = columns.iter().enumerate().map(move |(i, col)| {
+            let display_value = format_display_value(item, col);
+            let style = get_cell_style(item, &display_value, col);
+            let prefix = if marked && i == 0 { MARK_SYMBOL } else { " " };
+            let text = format!("{}{}", prefix, truncate_string(&display_value, 38));
+            match &lock {
+                Some(level) if col.json_path == resource.name_field => {
+                    Cell::from(Line::from(vec![
+                        Span::styled(text, style),
+                        Span::styled(
+                            format!(" {} {}", LOCK_SYMBOL, level.to_lowercase()),
+                            Style::default().fg(app.theme.highlight),
+                        ),
+                    ]))
+                }
+                _ => Cell::from(text).style(style),
+            }
         });
-        Row::new(cells)
+        let row = Row::new(cells);
+        if marked {
+            row.style(
+                Style::default()
+                    .fg(app.theme.highlight)
+                    .add_modifier(Modifier::BOLD),
+            )
+        } else if app.config.row_state_colors {
+            row.style(get_row_style(item, &all_columns))
+        } else {
+            row
+        }
     });
 
-    // Build column widths
-    let widths: Vec<Constraint> = resource
-        .columns
+    // Build column widths, sharing out any space freed by scrolled-away columns
+    let widths: Vec<Constraint> = columns
         .iter()
-        .map(|col| Constraint::Percentage(col.width))
+        .map(|col| Constraint::Fill(column_width(col, inner_area.width)))
         .collect();
 
     let table = Table::new(rows, widths).header(header).row_highlight_style(
         Style::default()
-            .bg(Color::DarkGray)
-            .fg(Color::White)
+            .bg(app.theme.selection_bg)
+            .fg(app.theme.selection_fg)
             .add_modifier(Modifier::BOLD),
     );
 
@@ -181,43 +269,51 @@ fn render_dynamic_table(f: &mut Frame, app: &App, area: Rect) {
     state.select(Some(app.selected));
 
     f.render_stateful_widget(table, inner_area, &mut state);
+    app.table_area.set(inner_area);
+    app.table_offset.set(state.offset());
 }
 
-fn format_display_value(value: &str, col: &ColumnDef) -> String {
-    if let Some(ref format) = col.format {
-        match format.as_str() {
-            "vm_state" => {
-                if let Ok(state) = value.parse::<i32>() {
-                    return crate::resource::format_vm_state(state);
-                }
-            }
-            "lcm_state" => {
-                if let Ok(state) = value.parse::<i32>() {
-                    return crate::resource::format_lcm_state(state);
-                }
-            }
-            "host_state" => {
-                if let Ok(state) = value.parse::<i32>() {
-                    return crate::resource::format_host_state(state);
-                }
-            }
-            "image_state" => {
-                if let Ok(state) = value.parse::<i32>() {
-                    return crate::resource::format_image_state(state);
-                }
-            }
-            "datastore_state" => {
-                if let Ok(state) = value.parse::<i32>() {
-                    return crate::resource::format_datastore_state(state);
-                }
-            }
-            _ => {}
+/// Narrowest a table column gets before later columns are scrolled out of view
+const MIN_COLUMN_WIDTH: u16 = 10;
+
+/// Width in cells of a column: its share of the table, at least `MIN_COLUMN_WIDTH`
+fn column_width(col: &ColumnDef, table_width: u16) -> u16 {
+    let share = u32::from(table_width) * u32::from(col.width) / 100;
+    (share as u16).max(MIN_COLUMN_WIDTH)
+}
+
+/// Number of columns from `offset` that fit in `table_width` (at least one)
+fn columns_that_fit(columns: &[ColumnDef], offset: usize, table_width: u16) -> usize {
+    let mut used = 0u32;
+    let mut count = 0;
+    for col in columns.iter().skip(offset) {
+        used += u32::from(column_width(col, table_width));
+        if count > 0 && used > u32::from(table_width) {
+            break;
         }
+        count += 1;
     }
-    value.to_string()
+    count
+}
+
+/// Loading spinner animation, one frame per UI tick
+const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+fn spinner(app: &App) -> &'static str {
+    SPINNER_FRAMES[app.spinner_frame % SPINNER_FRAMES.len()]
 }
 
-fn get_cell_style(value: &str, col: &ColumnDef) -> Style {
+/// Marker in the first column of rows marked for a batch action
+const MARK_SYMBOL: &str = "●";
+
+/// Marker appended to the name of locked resources
+const LOCK_SYMBOL: &str = "🔒";
+
+fn get_cell_style(item: &serde_json::Value, value: &str, col: &ColumnDef) -> Style {
+    if let Some(ratio) = usage_ratio(item, col) {
+        let [r, g, b] = usage_color(ratio);
+        return Style::default().fg(Color::Rgb(r, g, b));
+    }
     if let Some(ref color_map_name) = col.color_map {
         if let Some([r, g, b]) = get_color_for_value(color_map_name, value) {
             return Style::default().fg(Color::Rgb(r, g, b));
@@ -226,35 +322,177 @@ fn get_cell_style(value: &str, col: &ColumnDef) -> Style {
     Style::default()
 }
 
+/// Row tint from the most specific state column (the last color-mapped column
+/// with a matching color, so a VM's LCM state wins over its coarse state)
+fn get_row_style(item: &serde_json::Value, columns: &[ColumnDef]) -> Style {
+    columns
+        .iter()
+        .rev()
+        .filter_map(|col| {
+            let color_map_name = col.color_map.as_ref()?;
+            get_color_for_value(color_map_name, &format_display_value(item, col))
+        })
+        .next()
+        .map(|[r, g, b]| Style::default().fg(Color::Rgb(r, g, b)))
+        .unwrap_or_default()
+}
+
 fn truncate_string(s: &str, max_len: usize) -> String {
-    if s.len() > max_len {
-        format!("{}...", &s[..max_len.saturating_sub(3)])
+    if s.chars().count() > max_len {
+        let truncated: String = s.chars().take(max_len.saturating_sub(3)).collect();
+        format!("{}...", truncated)
     } else {
         s.to_string()
     }
 }
 
 fn render_describe_view(f: &mut Frame, app: &App, area: Rect) {
-    let json = app
-        .selected_item_json()
+    let text = app
+        .describe_text()
         .unwrap_or_else(|| "No item selected".to_string());
 
-    let lines: Vec<Line> = json.lines().map(highlight_json_line).collect();
-    let total_lines = lines.len();
+    let lines: Vec<Line> = match app.pipe_output {
+        Some(ref output) if !output.success => text
+            .lines()
+            .map(|l| Line::styled(l.to_string(), Style::default().fg(app.theme.error)))
+            .collect(),
+        Some(_) => text.lines().map(|l| Line::raw(l.to_string())).collect(),
+        None => match app.describe_format {
+            DescribeFormat::Json => text.lines().map(highlight_json_line).collect(),
+            DescribeFormat::Yaml => text.lines().map(highlight_yaml_line).collect(),
+            DescribeFormat::Xml => text.lines().map(highlight_xml_line).collect(),
+        },
+    };
+    let lines = highlight_search_matches(app, lines);
+
+    let wrap = if app.describe_wrap { ", wrap" } else { "" };
+    let title = if let Some(ref output) = app.pipe_output {
+        format!(" | {} ", output.command)
+    } else if let Some(resource) = app.current_resource() {
+        format!(
+            " {} Details [{}{}] ",
+            resource.display_name,
+            app.describe_format.label(),
+            wrap
+        )
+    } else {
+        format!(" Details [{}{}] ", app.describe_format.label(), wrap)
+    };
+
+    let mut title_spans = vec![Span::styled(
+        title,
+        Style::default()
+            .fg(app.theme.accent)
+            .add_modifier(Modifier::BOLD),
+    )];
+    // Show the lock before the user acts on a protected item
+    let lock = app
+        .describe_data
+        .as_ref()
+        .filter(|_| app.pipe_output.is_none())
+        .and_then(lock_level);
+    if let Some(level) = lock {
+        title_spans.push(Span::styled(
+            format!("{} locked: {} ", LOCK_SYMBOL, level),
+            Style::default().fg(app.theme.highlight),
+        ));
+    }
+
+    // The list item is shown while the full object loads
+    if app.detail_loading() {
+        title_spans.push(Span::styled(
+            format!("{} ", spinner(app)),
+            Style::default().fg(app.theme.highlight),
+        ));
+    }
+
+    if app.describe_search_active || !app.describe_search.is_empty() {
+        let cursor = if app.describe_search_active { "_" } else { "" };
+        let position = if app.describe_matches.is_empty() {
+            "no match".to_string()
+        } else {
+            format!("{}/{}", app.describe_match + 1, app.describe_matches.len())
+        };
+        title_spans.push(Span::styled(
+            format!("/{}{} [{}] ", app.describe_search, cursor, position),
+            Style::default().fg(app.theme.highlight),
+        ));
+    }
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.accent))
+        .title(Line::from(title_spans));
 
-    let title = if let Some(resource) = app.current_resource() {
-        format!(" {} Details ", resource.display_name)
+    let inner_area = block.inner(area);
+    f.render_widget(block, area);
+
+    let visible_lines = inner_area.height as usize;
+    app.describe_viewport.set(visible_lines);
+    // Leave the scrollbar column free
+    let width = (inner_area.width as usize).saturating_sub(1);
+    app.describe_width.set(width);
+    let max_scroll = app.describe_max_scroll();
+    let scroll = app.describe_scroll.min(max_scroll);
+
+    let paragraph = if app.describe_wrap && width > 0 {
+        // Scroll by source line, then wrap what's left so search jumps stay aligned
+        let rows: Vec<Line> = lines
+            .into_iter()
+            .skip(scroll)
+            .flat_map(|line| wrap_line(line, width))
+            .take(visible_lines)
+            .collect();
+        Paragraph::new(rows)
     } else {
-        " Details ".to_string()
+        Paragraph::new(lines).scroll((scroll as u16, 0))
     };
+    f.render_widget(paragraph, inner_area);
+
+    if max_scroll > 0 {
+        let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(Some("^"))
+            .end_symbol(Some("v"));
+        let mut scrollbar_state = ScrollbarState::new(max_scroll + visible_lines).position(scroll);
+        f.render_stateful_widget(scrollbar, inner_area, &mut scrollbar_state);
+    }
+}
+
+/// The action log, newest entry last; scrolls with the describe state
+fn render_log_view(f: &mut Frame, app: &App, area: Rect) {
+    let lines: Vec<Line> = if app.action_log.is_empty() {
+        vec![Line::styled(
+            "No actions yet",
+            Style::default().fg(app.theme.muted),
+        )]
+    } else {
+        app.action_log
+            .iter()
+            .map(|entry| {
+                let color = if entry.ok {
+                    app.theme.success
+                } else {
+                    app.theme.error
+                };
+                Line::from(vec![
+                    Span::styled(
+                        format!("{} ", entry.time),
+                        Style::default().fg(app.theme.muted),
+                    ),
+                    Span::styled(entry.text.clone(), Style::default().fg(color)),
+                ])
+            })
+            .collect()
+    };
+    let total_lines = lines.len();
 
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan))
+        .border_style(Style::default().fg(app.theme.accent))
         .title(Span::styled(
-            title,
+            format!(" Action Log ({}) ", app.action_log.len()),
             Style::default()
-                .fg(Color::Cyan)
+                .fg(app.theme.accent)
                 .add_modifier(Modifier::BOLD),
         ));
 
@@ -262,10 +500,11 @@ fn render_describe_view(f: &mut Frame, app: &App, area: Rect) {
     f.render_widget(block, area);
 
     let visible_lines = inner_area.height as usize;
+    app.describe_viewport.set(visible_lines);
     let max_scroll = total_lines.saturating_sub(visible_lines);
     let scroll = app.describe_scroll.min(max_scroll);
 
-    let paragraph = Paragraph::new(lines.clone()).scroll((scroll as u16, 0));
+    let paragraph = Paragraph::new(lines).scroll((scroll as u16, 0));
     f.render_widget(paragraph, inner_area);
 
     if total_lines > visible_lines {
@@ -370,6 +609,126 @@ fn highlight_json_line(line: &str) -> Line<'static> {
     Line::from(spans)
 }
 
+/// Background on lines matching the describe search; the current match stands out
+fn highlight_search_matches(app: &App, mut lines: Vec<Line<'static>>) -> Vec<Line<'static>> {
+    for (i, &line_number) in app.describe_matches.iter().enumerate() {
+        let bg = if i == app.describe_match {
+            Color::Blue
+        } else {
+            Color::DarkGray
+        };
+        if let Some(line) = lines.get_mut(line_number) {
+            *line = std::mem::take(line).patch_style(Style::default().bg(bg));
+        }
+    }
+    lines
+}
+
+/// Split a highlighted line into rows of at most `width` chars, keeping each
+/// span's style on continuation rows (see `app::wrapped_rows`)
+fn wrap_line(line: Line<'static>, width: usize) -> Vec<Line<'static>> {
+    let mut rows = vec![Line::default().style(line.style)];
+    let mut used = 0;
+    for span in line.spans {
+        let mut rest: &str = &span.content;
+        while !rest.is_empty() {
+            if used == width {
+                rows.push(Line::default().style(line.style));
+                used = 0;
+            }
+            let take = (width - used).min(rest.chars().count());
+            let split = rest.char_indices().nth(take).map_or(rest.len(), |(i, _)| i);
+            if let Some(row) = rows.last_mut() {
+                row.spans
+                    .push(Span::styled(rest[..split].to_string(), span.style));
+            }
+            used += take;
+            rest = &rest[split..];
+        }
+    }
+    rows
+}
+
+/// Tags in cyan and text (including CDATA contents) in green
+fn highlight_xml_line(line: &str) -> Line<'static> {
+    const CDATA_START: &str = "<![CDATA[";
+    const CDATA_END: &str = "]]>";
+    let tag = Style::default().fg(Color::Cyan);
+    let text = Style::default().fg(Color::Green);
+    let mut spans = Vec::new();
+    let mut rest = line;
+
+    while !rest.is_empty() {
+        if let Some(cdata) = rest.strip_prefix(CDATA_START) {
+            let end = cdata.find(CDATA_END).unwrap_or(cdata.len());
+            spans.push(Span::styled(CDATA_START.to_string(), tag));
+            spans.push(Span::styled(cdata[..end].to_string(), text));
+            let close = cdata[end..].len().min(CDATA_END.len());
+            spans.push(Span::styled(cdata[end..end + close].to_string(), tag));
+            rest = &cdata[end + close..];
+        } else if rest.starts_with('<') {
+            let end = rest.find('>').map_or(rest.len(), |i| i + 1);
+            spans.push(Span::styled(rest[..end].to_string(), tag));
+            rest = &rest[end..];
+        } else {
+            let end = rest.find('<').unwrap_or(rest.len());
+            spans.push(Span::styled(rest[..end].to_string(), text));
+            rest = &rest[end..];
+        }
+    }
+    Line::from(spans)
+}
+
+fn highlight_yaml_line(line: &str) -> Line<'static> {
+    let indent_len = line.len() - line.trim_start().len();
+    let (indent, mut rest) = line.split_at(indent_len);
+    let mut spans = vec![Span::raw(indent.to_string())];
+
+    if let Some(item) = rest
+        .strip_prefix("- ")
+        .or(rest.strip_prefix('-').filter(|r| r.is_empty()))
+    {
+        spans.push(Span::styled(
+            "- ".to_string(),
+            Style::default().fg(Color::Yellow),
+        ));
+        rest = item;
+    }
+
+    // `key: value` or `key:` opening a nested block; quoted scalars have no key
+    let key_end = rest
+        .find(": ")
+        .filter(|_| !rest.starts_with(['\'', '"']))
+        .or_else(|| rest.strip_suffix(':').map(str::len));
+    if let Some(end) = key_end {
+        spans.push(Span::styled(
+            rest[..end].to_string(),
+            Style::default().fg(Color::Cyan),
+        ));
+        spans.push(Span::styled(
+            ":".to_string(),
+            Style::default().fg(Color::White),
+        ));
+        rest = &rest[end + 1..];
+    }
+
+    if !rest.trim().is_empty() {
+        spans.push(Span::styled(rest.to_string(), get_yaml_value_style(rest)));
+    }
+    Line::from(spans)
+}
+
+fn get_yaml_value_style(value: &str) -> Style {
+    let trimmed = value.trim();
+    if trimmed == "null" || trimmed == "~" || trimmed == "{}" || trimmed == "[]" {
+        Style::default().fg(Color::DarkGray)
+    } else if trimmed == "true" || trimmed == "false" || trimmed.parse::<f64>().is_ok() {
+        get_json_value_style(trimmed)
+    } else {
+        Style::default().fg(Color::Green)
+    }
+}
+
 fn get_json_value_style(value: &str) -> Style {
     let trimmed = value.trim();
     if trimmed == "null" {
@@ -383,26 +742,87 @@ fn get_json_value_style(value: &str) -> Style {
     }
 }
 
-fn render_crumb(f: &mut Frame, app: &App, area: Rect) {
-    let breadcrumb = app.get_breadcrumb();
-    let crumb_display = breadcrumb.join(" > ");
+/// Most key hints shown in the footer for one mode
+const MAX_KEY_HINTS: usize = 6;
 
-    let shortcuts_hint = if let Some(resource) = app.current_resource() {
-        if !resource.sub_resources.is_empty() && app.mode == Mode::Normal {
-            let hints: Vec<String> = resource
-                .sub_resources
-                .iter()
-                .map(|s| format!("{}:{}", s.shortcut, s.display_name))
-                .collect();
-            format!(" | {}", hints.join(" "))
-        } else {
-            String::new()
+/// Key hints for the current mode, shown in the footer
+fn mode_key_hints(app: &App) -> Vec<String> {
+    let fixed: &[&str] = match app.mode {
+        Mode::Normal if app.filter_active => &["type:filter", "Enter:apply", "Esc:clear"],
+        Mode::Normal => return normal_key_hints(app),
+        Mode::Describe if app.describe_search_active => {
+            &["type:search", "Enter:apply", "Esc:clear"]
         }
-    } else {
-        String::new()
+        Mode::Describe if !app.describe_matches.is_empty() => {
+            &["n/N:next/prev", "/:search", "j/k:scroll", "::cmd", "q:back"]
+        }
+        Mode::Describe => &[
+            "j/k:scroll",
+            "/:search",
+            "y:json/yaml",
+            "x:xml",
+            "w:wrap",
+            "|:pipe",
+            "::cmd",
+            "q:back",
+        ],
+        Mode::Command => &[
+            "Tab:next",
+            "→:complete",
+            "^p/^n:history",
+            "Enter:run",
+            "Esc:cancel",
+        ],
+        Mode::Confirm => &["y:yes", "n:no", "Tab:toggle", "Enter:choose", "Esc:cancel"],
+        Mode::Select => &["j/k:move", "g/G:top/bottom", "Enter:select", "Esc:cancel"],
+        Mode::Input => &["Enter:apply", "←/→:move", "^u:clear", "Esc:cancel"],
+        Mode::Warning => &["Enter:ok"],
+        Mode::Help => &["Esc:close"],
+        Mode::Log => &["j/k:scroll", "g/G:top/bottom", "q:back"],
     };
+    fixed.iter().map(|s| s.to_string()).collect()
+}
 
-    let pagination_hint = if app.pagination.has_more || app.pagination.current_page > 1 {
+/// Normal-mode hints: sub-resources and actions from the registry, then help
+fn normal_key_hints(app: &App) -> Vec<String> {
+    let mut hints = Vec::new();
+    if let Some(resource) = app.current_resource() {
+        for sub in &resource.sub_resources {
+            hints.push(format!("{}:{}", sub.shortcut, sub.display_name));
+        }
+        for action in &resource.actions {
+            if app.readonly && action.sdk_method != "get" {
+                continue;
+            }
+            if action.hide_blocked
+                && app
+                    .selected_item()
+                    .is_none_or(|item| action.check_state(item).is_some())
+            {
+                continue;
+            }
+            if let Some(shortcut) = &action.shortcut {
+                let key = shortcut.replace("ctrl+", "^");
+                hints.push(format!("{}:{}", key, action.display_name));
+            }
+        }
+    }
+
+    hints.truncate(MAX_KEY_HINTS - 1);
+    if hints.is_empty() {
+        hints.extend(["d:describe", "/:filter", "::cmd"].map(String::from));
+    }
+    hints.push("?:help".to_string());
+    hints
+}
+
+fn render_crumb(f: &mut Frame, app: &App, area: Rect) {
+    let breadcrumb = app.get_breadcrumb();
+    let crumb_display = breadcrumb.join(" > ");
+
+    let pagination_hint = if app.mode == Mode::Normal
+        && (app.pagination.has_more || app.pagination.current_page > 1)
+    {
         let mut hints = Vec::new();
         if app.pagination.current_page > 1 {
             hints.push("[:prev");
@@ -417,28 +837,32 @@ fn render_crumb(f: &mut Frame, app: &App, area: Rect) {
 
     let status_text = if let Some(err) = &app.error_message {
         format!("Error: {}", err)
-    } else if app.loading {
-        "Loading...".to_string()
-    } else if app.mode == Mode::Describe {
-        "j/k: scroll | q/d/Esc: back".to_string()
-    } else if app.filter_active {
-        "Type to filter | Enter: apply | Esc: clear".to_string()
+    } else if let Some(reason) = app.filter_error() {
+        format!("Invalid regex: {}", reason)
+    } else if app.is_loading() {
+        format!("{} Loading... (Esc to cancel)", spinner(app))
+    } else if let Some((status, _)) = &app.status_message {
+        status.clone()
     } else {
-        format!("{}{}", shortcuts_hint, pagination_hint)
+        format!("{}{}", mode_key_hints(app).join(" "), pagination_hint)
     };
 
-    let style = if app.error_message.is_some() {
-        Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
-    } else if app.loading {
-        Style::default().fg(Color::Yellow)
+    let style = if app.error_message.is_some() || app.filter_error().is_some() {
+        Style::default()
+            .fg(app.theme.error)
+            .add_modifier(Modifier::BOLD)
+    } else if app.is_loading() {
+        Style::default().fg(app.theme.highlight)
+    } else if app.status_message.is_some() {
+        Style::default().fg(app.theme.success)
     } else {
-        Style::default().fg(Color::DarkGray)
+        Style::default().fg(app.theme.muted)
     };
 
     let crumb = Line::from(vec![
         Span::styled(
             format!("<{}>", crumb_display),
-            Style::default().fg(Color::Black).bg(Color::Cyan),
+            Style::default().fg(Color::Black).bg(app.theme.accent),
         ),
         Span::raw(" "),
         Span::styled(status_text, style),
@@ -7,6 +7,7 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
 const LOGO: &str = r#"
   _
@@ -21,6 +22,65 @@ pub struct SplashState {
     pub current_step: usize,
     pub total_steps: usize,
     pub message: String,
+    /// Username/password form shown when no stored credentials exist
+    pub prompt: Option<CredentialPrompt>,
+}
+
+/// Capacity reserved for the password so typing never reallocates (and
+/// leaves unzeroized copies behind)
+const PASSWORD_CAPACITY: usize = 256;
+
+/// First-run credential form
+#[derive(Zeroize, ZeroizeOnDrop)]
+pub struct CredentialPrompt {
+    #[zeroize(skip)]
+    pub username: String,
+    pub password: String,
+    /// Focused field: 0 = username, 1 = password, 2 = save checkbox
+    #[zeroize(skip)]
+    pub focused: usize,
+    /// Write the credentials to ~/.one/one_auth after a successful login
+    #[zeroize(skip)]
+    pub save: bool,
+}
+
+impl CredentialPrompt {
+    pub fn new() -> Self {
+        Self {
+            username: String::new(),
+            password: String::with_capacity(PASSWORD_CAPACITY),
+            focused: 0,
+            save: false,
+        }
+    }
+
+    pub fn focus_next(&mut self) {
+        self.focused = (self.focused + 1) % 3;
+    }
+
+    pub fn focus_previous(&mut self) {
+        self.focused = (self.focused + 2) % 3;
+    }
+
+    pub fn push(&mut self, c: char) {
+        match self.focused {
+            0 => self.username.push(c),
+            1 if self.password.len() + c.len_utf8() <= PASSWORD_CAPACITY => self.password.push(c),
+            _ => {}
+        }
+    }
+
+    pub fn pop(&mut self) {
+        match self.focused {
+            0 => {
+                self.username.pop();
+            }
+            1 => {
+                self.password.pop();
+            }
+            _ => {}
+        }
+    }
 }
 
 impl SplashState {
@@ -29,6 +89,7 @@ impl SplashState {
             current_step: 0,
             total_steps: 2,
             message: "Initializing...".to_string(),
+            prompt: None,
         }
     }
 
@@ -62,6 +123,12 @@ pub fn render(f: &mut Frame, state: &SplashState) {
         .alignment(Alignment::Center);
     f.render_widget(logo, chunks[1]);
 
+    if let Some(ref prompt) = state.prompt {
+        render_prompt(f, prompt, chunks[2].union(chunks[3]));
+        render_border(f);
+        return;
+    }
+
     // Progress area
     let progress_chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -89,7 +156,45 @@ pub fn render(f: &mut Frame, state: &SplashState) {
     .alignment(Alignment::Center);
     f.render_widget(status, progress_chunks[1]);
 
-    // Border
+    render_border(f);
+}
+
+fn render_prompt(f: &mut Frame, prompt: &CredentialPrompt, area: ratatui::layout::Rect) {
+    let field = |index: usize, label: &str, value: String| {
+        let style = if prompt.focused == index {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default().fg(Color::Gray)
+        };
+        let cursor = if prompt.focused == index { "_" } else { "" };
+        Line::from(vec![
+            Span::styled(
+                format!("{:>10} ", label),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled(format!("{}{}", value, cursor), style),
+        ])
+    };
+    let checkbox = if prompt.save { "[x]" } else { "[ ]" };
+    let lines = vec![
+        Line::styled(
+            "No OpenNebula credentials found (ONE_AUTH or ~/.one/one_auth)",
+            Style::default().fg(Color::Cyan),
+        ),
+        Line::from(""),
+        field(0, "Username:", prompt.username.clone()),
+        field(1, "Password:", "*".repeat(prompt.password.chars().count())),
+        field(2, "", format!("{} Save to ~/.one/one_auth", checkbox)),
+        Line::from(""),
+        Line::styled(
+            "Tab: next field  Space: toggle save  Enter: log in  Esc: quit",
+            Style::default().fg(Color::DarkGray),
+        ),
+    ];
+    f.render_widget(Paragraph::new(lines).alignment(Alignment::Center), area);
+}
+
+fn render_border(f: &mut Frame) {
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::DarkGray))
@@ -0,0 +1,95 @@
+//! Color theme
+//!
+//! Semantic colors used by the render functions. Defaults reproduce the
+//! built-in look; the `theme` section of the config file overrides them.
+
+use crate::config::ThemeConfig;
+use ratatui::style::Color;
+use std::str::FromStr;
+
+/// Resolved UI colors
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    /// The "tone" title in the header box
+    pub header: Color,
+    /// Titles and borders of focused views, dialogs and the command box
+    pub accent: Color,
+    /// Borders of the header and the resource table
+    pub border: Color,
+    /// Background and text of the selected table row
+    pub selection_bg: Color,
+    pub selection_fg: Color,
+    /// Key names, sort arrows, the active filter and warnings
+    pub highlight: Color,
+    /// Error messages and failed pipe output
+    pub error: Color,
+    /// Confirmation of a successful action
+    pub success: Color,
+    /// Labels and secondary text
+    pub muted: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            header: Color::Magenta,
+            accent: Color::Cyan,
+            border: Color::DarkGray,
+            selection_bg: Color::DarkGray,
+            selection_fg: Color::White,
+            highlight: Color::Yellow,
+            error: Color::Red,
+            success: Color::Green,
+            muted: Color::DarkGray,
+        }
+    }
+}
+
+impl Theme {
+    /// Apply the configured overrides; unparsable colors keep their default
+    pub fn from_config(config: &ThemeConfig) -> Self {
+        let defaults = Self::default();
+        Self {
+            header: parse_or("header", &config.header, defaults.header),
+            accent: parse_or("accent", &config.accent, defaults.accent),
+            border: parse_or("border", &config.border, defaults.border),
+            selection_bg: parse_or("selection_bg", &config.selection_bg, defaults.selection_bg),
+            selection_fg: parse_or("selection_fg", &config.selection_fg, defaults.selection_fg),
+            highlight: parse_or("highlight", &config.highlight, defaults.highlight),
+            error: parse_or("error", &config.error, defaults.error),
+            success: parse_or("success", &config.success, defaults.success),
+            muted: parse_or("muted", &config.muted, defaults.muted),
+        }
+    }
+}
+
+/// Parse a color name (`"light-blue"`), hex code (`"#1e90ff"`) or ANSI index (`"33"`)
+fn parse_or(name: &str, value: &Option<String>, default: Color) -> Color {
+    let Some(value) = value else {
+        return default;
+    };
+    Color::from_str(value).unwrap_or_else(|_| {
+        tracing::warn!("Invalid theme color {}: '{}'", name, value);
+        default
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_theme_overrides_and_fallback() {
+        let config = ThemeConfig {
+            accent: Some("#1e90ff".to_string()),
+            selection_bg: Some("blue".to_string()),
+            error: Some("not-a-color".to_string()),
+            ..Default::default()
+        };
+        let theme = Theme::from_config(&config);
+        assert_eq!(theme.accent, Color::Rgb(0x1e, 0x90, 0xff));
+        assert_eq!(theme.selection_bg, Color::Blue);
+        assert_eq!(theme.error, Color::Red);
+        assert_eq!(theme.header, Color::Magenta);
+    }
+}